@@ -0,0 +1,1139 @@
+//! One subcommand per router operation.
+//!
+//! Token amounts are whole tokens scaled by the token's `--decimals…`, CSPR amounts are in CSPR,
+//! and LP amounts use the pairs' 9 decimals. Token decimals and minimum amounts have no defaults,
+//! so a deploy never silently assumes them. Recipients default to the signer.
+
+use std::path::PathBuf;
+
+use casper_execution_engine::core::engine_state::executable_deploy_item::ExecutableDeployItem;
+use casper_types::{ContractHash, ContractPackageHash, Key, U256};
+use clap::{Args, Subcommand};
+
+use swappery_sdk::cspr::{self, CsprSessionCall};
+use swappery_sdk::{router, EntryPointCall};
+
+use crate::deploy::{self, Context};
+use crate::error::Error;
+use crate::parse::{self, Amount, Leg, CSPR_DECIMALS};
+
+#[derive(Debug, Args)]
+pub struct Router {
+    /// Package hash of the router.
+    #[clap(long, parse(try_from_str = parse::package))]
+    router: ContractPackageHash,
+}
+
+#[derive(Debug, Args)]
+pub struct Recipient {
+    /// Account (hex public key or `account-hash-…`) or contract package (`hash-…`) receiving
+    /// the proceeds; defaults to the signer.
+    #[clap(long, parse(try_from_str = parse::recipient))]
+    to: Option<Key>,
+}
+
+#[derive(Debug, Args)]
+pub struct SessionWasm {
+    /// Path of `swappery_cspr_session.wasm`, which funds the call with CSPR.
+    #[clap(long)]
+    session_wasm: PathBuf,
+}
+
+#[derive(Debug, Args)]
+pub struct SwapPath {
+    /// Comma-separated tokens from the one paid in to the one paid out.
+    #[clap(
+        long,
+        required = true,
+        use_value_delimiter = true,
+        parse(try_from_str = parse::token)
+    )]
+    path: Vec<ContractHash>,
+}
+
+#[derive(Debug, Args)]
+pub struct SwapDecimals {
+    /// Decimals of the token paid in.
+    #[clap(long)]
+    decimals_in: u8,
+    /// Decimals of the token paid out.
+    #[clap(long)]
+    decimals_out: u8,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Installs the router.
+    Install {
+        /// Path of `swappery_router.wasm`.
+        #[clap(long)]
+        wasm: PathBuf,
+        #[clap(long, parse(try_from_str = parse::token))]
+        wcspr: ContractHash,
+        /// Account or contract package receiving the protocol fee; defaults to the signer.
+        #[clap(long, parse(try_from_str = parse::recipient))]
+        feeto: Option<Key>,
+        /// Account or contract package allowed to manage the router; defaults to the signer.
+        #[clap(long, parse(try_from_str = parse::recipient))]
+        feeto_setter: Option<Key>,
+        /// Named key the router package is stored under in the signer's account.
+        #[clap(long, default_value = "swappery_router")]
+        contract_key_name: String,
+    },
+    /// Deploys the pair of two tokens.
+    CreatePair {
+        #[clap(flatten)]
+        router: Router,
+        #[clap(long, parse(try_from_str = parse::token))]
+        token0: ContractHash,
+        #[clap(long, parse(try_from_str = parse::token))]
+        token1: ContractHash,
+    },
+    /// Adds liquidity to the pair of two tokens, which must already exist.
+    AddLiquidity {
+        #[clap(flatten)]
+        router: Router,
+        #[clap(long, parse(try_from_str = parse::token))]
+        token0: ContractHash,
+        #[clap(long, parse(try_from_str = parse::token))]
+        token1: ContractHash,
+        #[clap(long)]
+        decimals0: u8,
+        #[clap(long)]
+        decimals1: u8,
+        #[clap(long)]
+        amount0_desired: Amount,
+        #[clap(long)]
+        amount1_desired: Amount,
+        #[clap(long)]
+        amount0_min: Amount,
+        #[clap(long)]
+        amount1_min: Amount,
+        #[clap(flatten)]
+        to: Recipient,
+    },
+    /// Adds liquidity to the pair of a token and WCSPR, paying the WCSPR side in CSPR.
+    AddLiquidityCspr {
+        #[clap(flatten)]
+        router: Router,
+        #[clap(flatten)]
+        session_wasm: SessionWasm,
+        #[clap(long, parse(try_from_str = parse::token))]
+        token: ContractHash,
+        #[clap(long)]
+        decimals: u8,
+        #[clap(long)]
+        amount_token_desired: Amount,
+        #[clap(long)]
+        amount_cspr_desired: Amount,
+        #[clap(long)]
+        amount_token_min: Amount,
+        #[clap(long)]
+        amount_cspr_min: Amount,
+        #[clap(flatten)]
+        to: Recipient,
+    },
+    /// Burns liquidity of the pair of two tokens.
+    RemoveLiquidity {
+        #[clap(flatten)]
+        router: Router,
+        #[clap(long, parse(try_from_str = parse::token))]
+        token0: ContractHash,
+        #[clap(long, parse(try_from_str = parse::token))]
+        token1: ContractHash,
+        #[clap(long)]
+        decimals0: u8,
+        #[clap(long)]
+        decimals1: u8,
+        #[clap(long)]
+        liquidity: Amount,
+        #[clap(long)]
+        amount0_min: Amount,
+        #[clap(long)]
+        amount1_min: Amount,
+        /// Use the variant for tokens taking a fee on transfer.
+        #[clap(long)]
+        supporting_fee: bool,
+        #[clap(flatten)]
+        to: Recipient,
+    },
+    /// Burns liquidity of the pair of a token and WCSPR, paying the WCSPR out as CSPR.
+    RemoveLiquidityCspr {
+        #[clap(flatten)]
+        router: Router,
+        #[clap(long, parse(try_from_str = parse::token))]
+        token: ContractHash,
+        #[clap(long)]
+        decimals: u8,
+        #[clap(long)]
+        liquidity: Amount,
+        #[clap(long)]
+        amount_token_min: Amount,
+        #[clap(long)]
+        amount_cspr_min: Amount,
+        /// Use the variant for tokens taking a fee on transfer.
+        #[clap(long)]
+        supporting_fee: bool,
+        #[clap(flatten)]
+        to: Recipient,
+    },
+    /// Swaps an exact amount along a path.
+    SwapExactTokensForTokens {
+        #[clap(flatten)]
+        router: Router,
+        #[clap(flatten)]
+        path: SwapPath,
+        #[clap(flatten)]
+        decimals: SwapDecimals,
+        #[clap(long)]
+        amount_in: Amount,
+        #[clap(long)]
+        amount_out_min: Amount,
+        /// Use the variant for tokens taking a fee on transfer.
+        #[clap(long)]
+        supporting_fee: bool,
+        #[clap(flatten)]
+        to: Recipient,
+    },
+    /// Swaps at most an amount along a path for an exact amount.
+    SwapTokensForExactTokens {
+        #[clap(flatten)]
+        router: Router,
+        #[clap(flatten)]
+        path: SwapPath,
+        #[clap(flatten)]
+        decimals: SwapDecimals,
+        #[clap(long)]
+        amount_out: Amount,
+        #[clap(long)]
+        amount_in_max: Amount,
+        #[clap(flatten)]
+        to: Recipient,
+    },
+    /// Swaps exact amounts along several paths, checking the minimum against their total.
+    SwapExactTokensForTokensSplit {
+        #[clap(flatten)]
+        router: Router,
+        /// A leg as `AMOUNT:TOKEN,TOKEN,…`; repeat for each leg.
+        #[clap(long = "leg", required = true)]
+        legs: Vec<Leg>,
+        #[clap(flatten)]
+        decimals: SwapDecimals,
+        #[clap(long)]
+        amount_out_min: Amount,
+        #[clap(flatten)]
+        to: Recipient,
+    },
+    /// Swaps an exact amount along the best path the router finds when the deploy executes.
+    SwapExactTokensForTokensBest {
+        #[clap(flatten)]
+        router: Router,
+        #[clap(long, parse(try_from_str = parse::token))]
+        token_in: ContractHash,
+        #[clap(long, parse(try_from_str = parse::token))]
+        token_out: ContractHash,
+        /// Comma-separated tokens to route through besides WCSPR and the base tokens.
+        #[clap(long, use_value_delimiter = true, parse(try_from_str = parse::token))]
+        intermediates: Vec<ContractHash>,
+        #[clap(flatten)]
+        decimals: SwapDecimals,
+        #[clap(long)]
+        amount_in: Amount,
+        #[clap(long)]
+        amount_out_min: Amount,
+        #[clap(flatten)]
+        to: Recipient,
+    },
+    /// Swaps an exact amount along a path ending in WCSPR, paid out as CSPR.
+    SwapExactTokensForCspr {
+        #[clap(flatten)]
+        router: Router,
+        #[clap(flatten)]
+        path: SwapPath,
+        #[clap(long)]
+        decimals_in: u8,
+        #[clap(long)]
+        amount_in: Amount,
+        #[clap(long)]
+        amount_out_min: Amount,
+        #[clap(flatten)]
+        to: Recipient,
+    },
+    /// Swaps at most an amount along a path ending in WCSPR for an exact amount of CSPR.
+    SwapTokensForExactCspr {
+        #[clap(flatten)]
+        router: Router,
+        #[clap(flatten)]
+        path: SwapPath,
+        #[clap(long)]
+        decimals_in: u8,
+        #[clap(long)]
+        amount_out: Amount,
+        #[clap(long)]
+        amount_in_max: Amount,
+        #[clap(flatten)]
+        to: Recipient,
+    },
+    /// Swaps an exact amount of CSPR along a path starting at WCSPR.
+    SwapExactCsprForTokens {
+        #[clap(flatten)]
+        router: Router,
+        #[clap(flatten)]
+        session_wasm: SessionWasm,
+        #[clap(flatten)]
+        path: SwapPath,
+        #[clap(long)]
+        decimals_out: u8,
+        #[clap(long)]
+        amount_in: Amount,
+        #[clap(long)]
+        amount_out_min: Amount,
+        #[clap(flatten)]
+        to: Recipient,
+    },
+    /// Swaps at most an amount of CSPR along a path starting at WCSPR for an exact amount.
+    SwapCsprForExactTokens {
+        #[clap(flatten)]
+        router: Router,
+        #[clap(flatten)]
+        session_wasm: SessionWasm,
+        #[clap(flatten)]
+        path: SwapPath,
+        #[clap(long)]
+        decimals_out: u8,
+        #[clap(long)]
+        amount_out: Amount,
+        #[clap(long)]
+        amount_in_max: Amount,
+        #[clap(flatten)]
+        to: Recipient,
+    },
+    /// Sets the recipient of the protocol fee; the feeto setter only.
+    SetFeeto {
+        #[clap(flatten)]
+        router: Router,
+        /// Account or contract package receiving the protocol fee.
+        #[clap(long, parse(try_from_str = parse::recipient))]
+        feeto: Key,
+    },
+}
+
+fn stored<C: EntryPointCall>(router: &Router, call: C) -> ExecutableDeployItem {
+    call.stored_call(router.router).into()
+}
+
+fn cspr_session<C: CsprSessionCall>(
+    router: &Router,
+    session_wasm: &SessionWasm,
+    call: C,
+) -> Result<ExecutableDeployItem, Error> {
+    let wasm = deploy::read_wasm(&session_wasm.session_wasm)?;
+    Ok(call.session(router.router, wasm))
+}
+
+fn lp(amount: &Amount) -> Result<U256, Error> {
+    amount.scale(CSPR_DECIMALS)
+}
+
+impl Command {
+    /// Returns the session of the deploy making this call.
+    pub fn session(&self, context: &Context) -> Result<ExecutableDeployItem, Error> {
+        let dead_line = context.dead_line;
+        let session = match self {
+            Command::Install {
+                wasm,
+                wcspr,
+                feeto,
+                feeto_setter,
+                contract_key_name,
+            } => {
+                let install = router::Install {
+                    wcspr_token: *wcspr,
+                    feeto: context.recipient(*feeto),
+                    feeto_setter: context.recipient(*feeto_setter),
+                    contract_key_name: contract_key_name.clone(),
+                };
+                ExecutableDeployItem::ModuleBytes {
+                    module_bytes: deploy::read_wasm(wasm)?,
+                    args: install.runtime_args(),
+                }
+            }
+            Command::CreatePair {
+                router,
+                token0,
+                token1,
+            } => stored(
+                router,
+                router::CreatePair {
+                    token0: *token0,
+                    token1: *token1,
+                },
+            ),
+            Command::AddLiquidity {
+                router,
+                token0,
+                token1,
+                decimals0,
+                decimals1,
+                amount0_desired,
+                amount1_desired,
+                amount0_min,
+                amount1_min,
+                to,
+            } => stored(
+                router,
+                router::AddLiquidity {
+                    token0: *token0,
+                    token1: *token1,
+                    amount0_desired: amount0_desired.scale(*decimals0)?,
+                    amount1_desired: amount1_desired.scale(*decimals1)?,
+                    amount0_min: amount0_min.scale(*decimals0)?,
+                    amount1_min: amount1_min.scale(*decimals1)?,
+                    to: context.recipient(to.to),
+                    dead_line,
+                },
+            ),
+            Command::AddLiquidityCspr {
+                router,
+                session_wasm,
+                token,
+                decimals,
+                amount_token_desired,
+                amount_cspr_desired,
+                amount_token_min,
+                amount_cspr_min,
+                to,
+            } => cspr_session(
+                router,
+                session_wasm,
+                cspr::AddLiquidityCspr {
+                    token: *token,
+                    amount_token_desired: amount_token_desired.scale(*decimals)?,
+                    amount_cspr_desired: amount_cspr_desired.scale(CSPR_DECIMALS)?,
+                    amount_token_min: amount_token_min.scale(*decimals)?,
+                    amount_cspr_min: amount_cspr_min.scale(CSPR_DECIMALS)?,
+                    to: context.recipient(to.to),
+                    dead_line,
+                },
+            )?,
+            Command::RemoveLiquidity {
+                router,
+                token0,
+                token1,
+                decimals0,
+                decimals1,
+                liquidity,
+                amount0_min,
+                amount1_min,
+                supporting_fee,
+                to,
+            } => {
+                let call = router::RemoveLiquidity {
+                    token0: *token0,
+                    token1: *token1,
+                    liquidity: lp(liquidity)?,
+                    amount0_min: amount0_min.scale(*decimals0)?,
+                    amount1_min: amount1_min.scale(*decimals1)?,
+                    to: context.recipient(to.to),
+                    dead_line,
+                };
+                if *supporting_fee {
+                    stored(
+                        router,
+                        router::RemoveLiquiditySupportingFee {
+                            token0: call.token0,
+                            token1: call.token1,
+                            liquidity: call.liquidity,
+                            amount0_min: call.amount0_min,
+                            amount1_min: call.amount1_min,
+                            to: call.to,
+                            dead_line: call.dead_line,
+                        },
+                    )
+                } else {
+                    stored(router, call)
+                }
+            }
+            Command::RemoveLiquidityCspr {
+                router,
+                token,
+                decimals,
+                liquidity,
+                amount_token_min,
+                amount_cspr_min,
+                supporting_fee,
+                to,
+            } => {
+                let call = router::RemoveLiquidityCspr {
+                    token: *token,
+                    liquidity: lp(liquidity)?,
+                    amount_token_min: amount_token_min.scale(*decimals)?,
+                    amount_cspr_min: amount_cspr_min.scale(CSPR_DECIMALS)?,
+                    to: context.recipient(to.to),
+                    dead_line,
+                };
+                if *supporting_fee {
+                    stored(
+                        router,
+                        router::RemoveLiquidityCsprSupportingFee {
+                            token: call.token,
+                            liquidity: call.liquidity,
+                            amount_token_min: call.amount_token_min,
+                            amount_cspr_min: call.amount_cspr_min,
+                            to: call.to,
+                            dead_line: call.dead_line,
+                        },
+                    )
+                } else {
+                    stored(router, call)
+                }
+            }
+            Command::SwapExactTokensForTokens {
+                router,
+                path,
+                decimals,
+                amount_in,
+                amount_out_min,
+                supporting_fee,
+                to,
+            } => {
+                let call = router::SwapExactTokensForTokens {
+                    amount_in: amount_in.scale(decimals.decimals_in)?,
+                    amount_out_min: amount_out_min.scale(decimals.decimals_out)?,
+                    path: path.path.clone(),
+                    to: context.recipient(to.to),
+                    dead_line,
+                };
+                if *supporting_fee {
+                    stored(
+                        router,
+                        router::SwapExactTokensForTokensSupportingFee {
+                            amount_in: call.amount_in,
+                            amount_out_min: call.amount_out_min,
+                            path: call.path,
+                            to: call.to,
+                            dead_line: call.dead_line,
+                        },
+                    )
+                } else {
+                    stored(router, call)
+                }
+            }
+            Command::SwapTokensForExactTokens {
+                router,
+                path,
+                decimals,
+                amount_out,
+                amount_in_max,
+                to,
+            } => stored(
+                router,
+                router::SwapTokensForExactTokens {
+                    amount_out: amount_out.scale(decimals.decimals_out)?,
+                    amount_in_max: amount_in_max.scale(decimals.decimals_in)?,
+                    path: path.path.clone(),
+                    to: context.recipient(to.to),
+                    dead_line,
+                },
+            ),
+            Command::SwapExactTokensForTokensSplit {
+                router,
+                legs,
+                decimals,
+                amount_out_min,
+                to,
+            } => stored(
+                router,
+                router::SwapExactTokensForTokensSplit {
+                    legs: legs
+                        .iter()
+                        .map(|leg| {
+                            let amount_in = leg.amount_in.scale(decimals.decimals_in)?;
+                            Ok((leg.path.clone(), amount_in))
+                        })
+                        .collect::<Result<_, Error>>()?,
+                    amount_out_min: amount_out_min.scale(decimals.decimals_out)?,
+                    to: context.recipient(to.to),
+                    dead_line,
+                },
+            ),
+            Command::SwapExactTokensForTokensBest {
+                router,
+                token_in,
+                token_out,
+                intermediates,
+                decimals,
+                amount_in,
+                amount_out_min,
+                to,
+            } => stored(
+                router,
+                router::SwapExactTokensForTokensBest {
+                    token_in: *token_in,
+                    token_out: *token_out,
+                    amount_in: amount_in.scale(decimals.decimals_in)?,
+                    amount_out_min: amount_out_min.scale(decimals.decimals_out)?,
+                    intermediates: intermediates.clone(),
+                    to: context.recipient(to.to),
+                    dead_line,
+                },
+            ),
+            Command::SwapExactTokensForCspr {
+                router,
+                path,
+                decimals_in,
+                amount_in,
+                amount_out_min,
+                to,
+            } => stored(
+                router,
+                router::SwapExactTokensForCspr {
+                    amount_in: amount_in.scale(*decimals_in)?,
+                    amount_out_min: amount_out_min.scale(CSPR_DECIMALS)?,
+                    path: path.path.clone(),
+                    to: context.recipient(to.to),
+                    dead_line,
+                },
+            ),
+            Command::SwapTokensForExactCspr {
+                router,
+                path,
+                decimals_in,
+                amount_out,
+                amount_in_max,
+                to,
+            } => stored(
+                router,
+                router::SwapTokensForExactCspr {
+                    amount_out: amount_out.scale(CSPR_DECIMALS)?,
+                    amount_in_max: amount_in_max.scale(*decimals_in)?,
+                    path: path.path.clone(),
+                    to: context.recipient(to.to),
+                    dead_line,
+                },
+            ),
+            Command::SwapExactCsprForTokens {
+                router,
+                session_wasm,
+                path,
+                decimals_out,
+                amount_in,
+                amount_out_min,
+                to,
+            } => cspr_session(
+                router,
+                session_wasm,
+                cspr::SwapExactCsprForTokens {
+                    amount_in: amount_in.scale(CSPR_DECIMALS)?,
+                    amount_out_min: amount_out_min.scale(*decimals_out)?,
+                    path: path.path.clone(),
+                    to: context.recipient(to.to),
+                    dead_line,
+                },
+            )?,
+            Command::SwapCsprForExactTokens {
+                router,
+                session_wasm,
+                path,
+                decimals_out,
+                amount_out,
+                amount_in_max,
+                to,
+            } => cspr_session(
+                router,
+                session_wasm,
+                cspr::SwapCsprForExactTokens {
+                    amount_out: amount_out.scale(*decimals_out)?,
+                    amount_in_max: amount_in_max.scale(CSPR_DECIMALS)?,
+                    path: path.path.clone(),
+                    to: context.recipient(to.to),
+                    dead_line,
+                },
+            )?,
+            Command::SetFeeto { router, feeto } => {
+                stored(router, router::SetFeeto { feeto: *feeto })
+            }
+        };
+        Ok(session)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use casper_node::types::{TimeDiff, Timestamp};
+    use casper_types::bytesrepr::{Bytes, FromBytes};
+    use casper_types::{CLTyped, RuntimeArgs, SecretKey};
+    use clap::Parser;
+    use swappery_sdk::names::*;
+
+    use super::*;
+
+    const ROUTER: &str = "hash-1111111111111111111111111111111111111111111111111111111111111111";
+    const TOKEN0: &str = "hash-2222222222222222222222222222222222222222222222222222222222222222";
+    const TOKEN1: &str = "hash-3333333333333333333333333333333333333333333333333333333333333333";
+    const TOKEN2: &str = "hash-4444444444444444444444444444444444444444444444444444444444444444";
+
+    #[derive(Debug, Parser)]
+    struct Cli {
+        #[clap(subcommand)]
+        command: Command,
+    }
+
+    fn context() -> Context {
+        let secret_key = SecretKey::ed25519_from_bytes([1u8; 32]).unwrap();
+        let timestamp: Timestamp = "2022-03-01T00:00:00Z".parse().unwrap();
+        Context::with_secret_key(secret_key, timestamp, "30min".parse::<TimeDiff>().unwrap())
+    }
+
+    fn command(args: &str) -> Result<Command, clap::Error> {
+        let args = std::iter::once("swappery-cli").chain(args.split_whitespace());
+        Cli::try_parse_from(args).map(|cli| cli.command)
+    }
+
+    fn session(args: &str) -> ExecutableDeployItem {
+        command(args).unwrap().session(&context()).unwrap()
+    }
+
+    /// Returns the entry point and arguments of a call against [`ROUTER`].
+    fn stored_call(args: &str) -> (String, RuntimeArgs) {
+        match session(args) {
+            ExecutableDeployItem::StoredVersionedContractByHash {
+                hash,
+                version: None,
+                entry_point,
+                args,
+            } => {
+                assert_eq!(hash, parse::package(ROUTER).unwrap());
+                (entry_point, args)
+            }
+            session => panic!("expected a stored call, got {:?}", session),
+        }
+    }
+
+    /// Returns the arguments of the session running `wasm`.
+    fn module_bytes(args: &str, wasm: &[u8]) -> RuntimeArgs {
+        match session(args) {
+            ExecutableDeployItem::ModuleBytes { module_bytes, args } => {
+                assert_eq!(module_bytes, Bytes::from(wasm.to_vec()));
+                args
+            }
+            session => panic!("expected module bytes, got {:?}", session),
+        }
+    }
+
+    fn wasm_file(name: &str, wasm: &[u8]) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("swappery-cli-{}.wasm", name));
+        std::fs::write(&path, wasm).unwrap();
+        path
+    }
+
+    fn arg<T: CLTyped + FromBytes>(args: &RuntimeArgs, name: &str) -> T {
+        let value = args.get(name).unwrap_or_else(|| panic!("missing {}", name));
+        value.clone().into_t().unwrap()
+    }
+
+    fn token(hash: &str) -> Key {
+        Key::from(parse::token(hash).unwrap())
+    }
+
+    fn signer() -> Key {
+        Key::Account(context().account)
+    }
+
+    #[test]
+    fn should_require_decimals_and_minimums() {
+        let add_liquidity = format!(
+            "add-liquidity --router {} --token0 {} --token1 {} \
+             --amount0-desired 1 --amount1-desired 2",
+            ROUTER, TOKEN0, TOKEN1
+        );
+        let complete = " --decimals0 6 --decimals1 9 --amount0-min 1 --amount1-min 2";
+        assert!(command(&format!("{}{}", add_liquidity, complete)).is_ok());
+        for missing in [
+            "--decimals0 6",
+            "--decimals1 9",
+            "--amount0-min 1",
+            "--amount1-min 2",
+        ] {
+            let args = format!("{}{}", add_liquidity, complete.replace(missing, ""));
+            assert!(command(&args).is_err(), "{}", missing);
+        }
+
+        let swap = format!(
+            "swap-exact-tokens-for-tokens --router {} --path {},{} --amount-in 1 \
+             --amount-out-min 1",
+            ROUTER, TOKEN0, TOKEN1
+        );
+        assert!(command(&swap).is_err());
+        assert!(command(&format!("{} --decimals-in 6 --decimals-out 9", swap)).is_ok());
+    }
+
+    #[test]
+    fn should_install_router() {
+        let wasm = wasm_file("install", b"router");
+        let args = module_bytes(
+            &format!("install --wasm {} --wcspr {}", wasm.display(), TOKEN2),
+            b"router",
+        );
+        assert_eq!(
+            arg::<Key>(&args, WCSPR_TOKEN_RUNTIME_ARG_NAME),
+            token(TOKEN2)
+        );
+        assert_eq!(arg::<Key>(&args, FEETO_RUNTIME_ARG_NAME), signer());
+        assert_eq!(arg::<Key>(&args, FEETO_SETTER_RUNTIME_ARG_NAME), signer());
+        assert_eq!(
+            arg::<String>(&args, CONTRACT_KEY_NAME_RUNTIME_ARG_NAME),
+            "swappery_router"
+        );
+    }
+
+    #[test]
+    fn should_create_pair() {
+        let (entry_point, args) = stored_call(&format!(
+            "create-pair --router {} --token0 {} --token1 {}",
+            ROUTER, TOKEN0, TOKEN1
+        ));
+        assert_eq!(entry_point, CREATE_PAIR_ENTRY_POINT_NAME);
+        assert_eq!(arg::<Key>(&args, TOKEN0_RUNTIME_ARG_NAME), token(TOKEN0));
+        assert_eq!(arg::<Key>(&args, TOKEN1_RUNTIME_ARG_NAME), token(TOKEN1));
+    }
+
+    #[test]
+    fn should_add_liquidity() {
+        let (entry_point, args) = stored_call(&format!(
+            "add-liquidity --router {} --token0 {} --token1 {} --decimals0 6 --decimals1 9 \
+             --amount0-desired 1.5 --amount1-desired 2 --amount0-min 1 --amount1-min 0.5 \
+             --to {}",
+            ROUTER, TOKEN0, TOKEN1, TOKEN2
+        ));
+        assert_eq!(entry_point, ADD_LIQUIDITY_ENTRY_POINT_NAME);
+        assert_eq!(arg::<Key>(&args, TOKEN0_RUNTIME_ARG_NAME), token(TOKEN0));
+        assert_eq!(arg::<Key>(&args, TOKEN1_RUNTIME_ARG_NAME), token(TOKEN1));
+        assert_eq!(
+            arg::<U256>(&args, AMOUNT0_DESIRED_RUNTIME_ARG_NAME),
+            U256::from(1_500_000)
+        );
+        assert_eq!(
+            arg::<U256>(&args, AMOUNT1_DESIRED_RUNTIME_ARG_NAME),
+            U256::from(2_000_000_000u64)
+        );
+        assert_eq!(
+            arg::<U256>(&args, AMOUNT0_MIN_RUNTIME_ARG_NAME),
+            U256::from(1_000_000)
+        );
+        assert_eq!(
+            arg::<U256>(&args, AMOUNT1_MIN_RUNTIME_ARG_NAME),
+            U256::from(500_000_000)
+        );
+        assert_eq!(arg::<Key>(&args, TO_RUNTIME_ARG_NAME), token(TOKEN2));
+        assert_eq!(
+            arg::<U256>(&args, DEAD_LINE_RUNTIME_ARG_NAME),
+            context().dead_line
+        );
+    }
+
+    #[test]
+    fn should_add_liquidity_cspr() {
+        let wasm = wasm_file("add-liquidity-cspr", b"session");
+        let args = module_bytes(
+            &format!(
+                "add-liquidity-cspr --router {} --session-wasm {} --token {} --decimals 6 \
+                 --amount-token-desired 3 --amount-cspr-desired 4 --amount-token-min 2 \
+                 --amount-cspr-min 1",
+                ROUTER,
+                wasm.display(),
+                TOKEN0
+            ),
+            b"session",
+        );
+        assert_eq!(
+            arg::<Key>(&args, ROUTER_RUNTIME_ARG_NAME),
+            Key::Hash(parse::package(ROUTER).unwrap().value())
+        );
+        assert_eq!(
+            arg::<String>(&args, ENTRY_POINT_RUNTIME_ARG_NAME),
+            ADD_LIQUIDITY_CSPR_ENTRY_POINT_NAME
+        );
+        assert_eq!(arg::<Key>(&args, TOKEN_RUNTIME_ARG_NAME), token(TOKEN0));
+        assert_eq!(
+            arg::<U256>(&args, AMOUNT_TOKEN_DESIRED_RUNTIME_ARG_NAME),
+            U256::from(3_000_000)
+        );
+        assert_eq!(
+            arg::<U256>(&args, AMOUNT_CSPR_DESIRED_RUNTIME_ARG_NAME),
+            U256::from(4_000_000_000u64)
+        );
+        assert_eq!(
+            arg::<U256>(&args, AMOUNT_TOKEN_MIN_RUNTIME_ARG_NAME),
+            U256::from(2_000_000)
+        );
+        assert_eq!(
+            arg::<U256>(&args, AMOUNT_CSPR_MIN_RUNTIME_ARG_NAME),
+            U256::from(1_000_000_000u64)
+        );
+        assert_eq!(arg::<Key>(&args, TO_RUNTIME_ARG_NAME), signer());
+    }
+
+    #[test]
+    fn should_remove_liquidity() {
+        let remove_liquidity = format!(
+            "remove-liquidity --router {} --token0 {} --token1 {} --decimals0 6 --decimals1 9 \
+             --liquidity 2 --amount0-min 1 --amount1-min 1",
+            ROUTER, TOKEN0, TOKEN1
+        );
+        let (entry_point, args) = stored_call(&remove_liquidity);
+        assert_eq!(entry_point, REMOVE_LIQUIDITY_ENTRY_POINT_NAME);
+        assert_eq!(
+            arg::<U256>(&args, LIQUIDITY_RUNTIME_ARG_NAME),
+            U256::from(2_000_000_000u64)
+        );
+        assert_eq!(
+            arg::<U256>(&args, AMOUNT0_MIN_RUNTIME_ARG_NAME),
+            U256::from(1_000_000)
+        );
+        assert_eq!(
+            arg::<U256>(&args, AMOUNT1_MIN_RUNTIME_ARG_NAME),
+            U256::from(1_000_000_000u64)
+        );
+        assert_eq!(arg::<Key>(&args, TO_RUNTIME_ARG_NAME), signer());
+
+        let (entry_point, supporting_fee_args) =
+            stored_call(&format!("{} --supporting-fee", remove_liquidity));
+        assert_eq!(
+            entry_point,
+            REMOVE_LIQUIDITY_SUPPORTING_FEE_ENTRY_POINT_NAME
+        );
+        assert_eq!(supporting_fee_args, args);
+    }
+
+    #[test]
+    fn should_remove_liquidity_cspr() {
+        let remove_liquidity_cspr = format!(
+            "remove-liquidity-cspr --router {} --token {} --decimals 6 --liquidity 2 \
+             --amount-token-min 1 --amount-cspr-min 1",
+            ROUTER, TOKEN0
+        );
+        let (entry_point, args) = stored_call(&remove_liquidity_cspr);
+        assert_eq!(entry_point, REMOVE_LIQUIDITY_CSPR_ENTRY_POINT_NAME);
+        assert_eq!(arg::<Key>(&args, TOKEN_RUNTIME_ARG_NAME), token(TOKEN0));
+        assert_eq!(
+            arg::<U256>(&args, AMOUNT_TOKEN_MIN_RUNTIME_ARG_NAME),
+            U256::from(1_000_000)
+        );
+        assert_eq!(
+            arg::<U256>(&args, AMOUNT_CSPR_MIN_RUNTIME_ARG_NAME),
+            U256::from(1_000_000_000u64)
+        );
+
+        let (entry_point, supporting_fee_args) =
+            stored_call(&format!("{} --supporting-fee", remove_liquidity_cspr));
+        assert_eq!(
+            entry_point,
+            REMOVE_LIQUIDITY_CSPR_SUPPORTING_FEE_ENTRY_POINT_NAME
+        );
+        assert_eq!(supporting_fee_args, args);
+    }
+
+    #[test]
+    fn should_swap_tokens_for_tokens() {
+        let swap = format!(
+            "swap-exact-tokens-for-tokens --router {} --path {},{} --decimals-in 6 \
+             --decimals-out 9 --amount-in 1 --amount-out-min 2",
+            ROUTER, TOKEN0, TOKEN1
+        );
+        let (entry_point, args) = stored_call(&swap);
+        assert_eq!(entry_point, SWAP_EXACT_TOKENS_FOR_TOKENS_ENTRY_POINT_NAME);
+        assert_eq!(
+            arg::<Vec<Key>>(&args, PATH_RUNTIME_ARG_NAME),
+            vec![token(TOKEN0), token(TOKEN1)]
+        );
+        assert_eq!(
+            arg::<U256>(&args, AMOUNT_IN_RUNTIME_ARG_NAME),
+            U256::from(1_000_000)
+        );
+        assert_eq!(
+            arg::<U256>(&args, AMOUNT_OUT_MIN_RUNTIME_ARG_NAME),
+            U256::from(2_000_000_000u64)
+        );
+
+        let (entry_point, supporting_fee_args) = stored_call(&format!("{} --supporting-fee", swap));
+        assert_eq!(
+            entry_point,
+            SWAP_EXACT_TOKENS_FOR_TOKENS_SUPPORTING_FEE_ENTRY_POINT_NAME
+        );
+        assert_eq!(supporting_fee_args, args);
+
+        let (entry_point, args) = stored_call(&format!(
+            "swap-tokens-for-exact-tokens --router {} --path {},{} --decimals-in 6 \
+             --decimals-out 9 --amount-out 2 --amount-in-max 1",
+            ROUTER, TOKEN0, TOKEN1
+        ));
+        assert_eq!(entry_point, SWAP_TOKENS_FOR_EXACT_TOKENS_ENTRY_POINT_NAME);
+        assert_eq!(
+            arg::<U256>(&args, AMOUNT_OUT_RUNTIME_ARG_NAME),
+            U256::from(2_000_000_000u64)
+        );
+        assert_eq!(
+            arg::<U256>(&args, AMOUNT_IN_MAX_RUNTIME_ARG_NAME),
+            U256::from(1_000_000)
+        );
+    }
+
+    #[test]
+    fn should_swap_split_and_best() {
+        let (entry_point, args) = stored_call(&format!(
+            "swap-exact-tokens-for-tokens-split --router {} --leg 1:{},{} --leg 2:{},{},{} \
+             --decimals-in 6 --decimals-out 9 --amount-out-min 3",
+            ROUTER, TOKEN0, TOKEN1, TOKEN0, TOKEN2, TOKEN1
+        ));
+        assert_eq!(
+            entry_point,
+            SWAP_EXACT_TOKENS_FOR_TOKENS_SPLIT_ENTRY_POINT_NAME
+        );
+        assert_eq!(
+            arg::<Vec<(Vec<Key>, U256)>>(&args, LEGS_RUNTIME_ARG_NAME),
+            vec![
+                (vec![token(TOKEN0), token(TOKEN1)], U256::from(1_000_000)),
+                (
+                    vec![token(TOKEN0), token(TOKEN2), token(TOKEN1)],
+                    U256::from(2_000_000)
+                ),
+            ]
+        );
+        assert_eq!(
+            arg::<U256>(&args, AMOUNT_OUT_MIN_RUNTIME_ARG_NAME),
+            U256::from(3_000_000_000u64)
+        );
+
+        let (entry_point, args) = stored_call(&format!(
+            "swap-exact-tokens-for-tokens-best --router {} --token-in {} --token-out {} \
+             --intermediates {} --decimals-in 6 --decimals-out 9 --amount-in 1 \
+             --amount-out-min 2",
+            ROUTER, TOKEN0, TOKEN1, TOKEN2
+        ));
+        assert_eq!(
+            entry_point,
+            SWAP_EXACT_TOKENS_FOR_TOKENS_BEST_ENTRY_POINT_NAME
+        );
+        assert_eq!(arg::<Key>(&args, TOKEN_IN_RUNTIME_ARG_NAME), token(TOKEN0));
+        assert_eq!(arg::<Key>(&args, TOKEN_OUT_RUNTIME_ARG_NAME), token(TOKEN1));
+        assert_eq!(
+            arg::<Vec<Key>>(&args, INTERMEDIATES_RUNTIME_ARG_NAME),
+            vec![token(TOKEN2)]
+        );
+        assert_eq!(
+            arg::<U256>(&args, AMOUNT_IN_RUNTIME_ARG_NAME),
+            U256::from(1_000_000)
+        );
+        assert_eq!(
+            arg::<U256>(&args, AMOUNT_OUT_MIN_RUNTIME_ARG_NAME),
+            U256::from(2_000_000_000u64)
+        );
+    }
+
+    #[test]
+    fn should_swap_tokens_for_cspr() {
+        let (entry_point, args) = stored_call(&format!(
+            "swap-exact-tokens-for-cspr --router {} --path {},{} --decimals-in 6 --amount-in 1 \
+             --amount-out-min 2",
+            ROUTER, TOKEN0, TOKEN2
+        ));
+        assert_eq!(entry_point, SWAP_EXACT_TOKENS_FOR_CSPR_ENTRY_POINT_NAME);
+        assert_eq!(
+            arg::<U256>(&args, AMOUNT_IN_RUNTIME_ARG_NAME),
+            U256::from(1_000_000)
+        );
+        assert_eq!(
+            arg::<U256>(&args, AMOUNT_OUT_MIN_RUNTIME_ARG_NAME),
+            U256::from(2_000_000_000u64)
+        );
+
+        let (entry_point, args) = stored_call(&format!(
+            "swap-tokens-for-exact-cspr --router {} --path {},{} --decimals-in 6 --amount-out 2 \
+             --amount-in-max 1",
+            ROUTER, TOKEN0, TOKEN2
+        ));
+        assert_eq!(entry_point, SWAP_TOKENS_FOR_EXACT_CSPR_ENTRY_POINT_NAME);
+        assert_eq!(
+            arg::<U256>(&args, AMOUNT_OUT_RUNTIME_ARG_NAME),
+            U256::from(2_000_000_000u64)
+        );
+        assert_eq!(
+            arg::<U256>(&args, AMOUNT_IN_MAX_RUNTIME_ARG_NAME),
+            U256::from(1_000_000)
+        );
+    }
+
+    #[test]
+    fn should_swap_cspr_for_tokens() {
+        let wasm = wasm_file("swap-cspr", b"session");
+        let args = module_bytes(
+            &format!(
+                "swap-exact-cspr-for-tokens --router {} --session-wasm {} --path {},{} \
+                 --decimals-out 6 --amount-in 1 --amount-out-min 2",
+                ROUTER,
+                wasm.display(),
+                TOKEN2,
+                TOKEN0
+            ),
+            b"session",
+        );
+        assert_eq!(
+            arg::<String>(&args, ENTRY_POINT_RUNTIME_ARG_NAME),
+            SWAP_EXACT_CSPR_FOR_TOKENS_ENTRY_POINT_NAME
+        );
+        assert_eq!(
+            arg::<Vec<Key>>(&args, PATH_RUNTIME_ARG_NAME),
+            vec![token(TOKEN2), token(TOKEN0)]
+        );
+        assert_eq!(
+            arg::<U256>(&args, AMOUNT_IN_RUNTIME_ARG_NAME),
+            U256::from(1_000_000_000u64)
+        );
+        assert_eq!(
+            arg::<U256>(&args, AMOUNT_OUT_MIN_RUNTIME_ARG_NAME),
+            U256::from(2_000_000)
+        );
+
+        let args = module_bytes(
+            &format!(
+                "swap-cspr-for-exact-tokens --router {} --session-wasm {} --path {},{} \
+                 --decimals-out 6 --amount-out 2 --amount-in-max 1",
+                ROUTER,
+                wasm.display(),
+                TOKEN2,
+                TOKEN0
+            ),
+            b"session",
+        );
+        assert_eq!(
+            arg::<String>(&args, ENTRY_POINT_RUNTIME_ARG_NAME),
+            SWAP_CSPR_FOR_EXACT_TOKENS_ENTRY_POINT_NAME
+        );
+        assert_eq!(
+            arg::<U256>(&args, AMOUNT_OUT_RUNTIME_ARG_NAME),
+            U256::from(2_000_000)
+        );
+        assert_eq!(
+            arg::<U256>(&args, AMOUNT_IN_MAX_RUNTIME_ARG_NAME),
+            U256::from(1_000_000_000u64)
+        );
+    }
+
+    #[test]
+    fn should_set_feeto() {
+        let feeto = "account-hash-5555555555555555555555555555555555555555555555555555555555555555";
+        let (entry_point, args) =
+            stored_call(&format!("set-feeto --router {} --feeto {}", ROUTER, feeto));
+        assert_eq!(entry_point, SET_FEETO_ENTRY_POINT_NAME);
+        assert_eq!(
+            arg::<Key>(&args, FEETO_RUNTIME_ARG_NAME),
+            Key::Account(parse::account(feeto).unwrap())
+        );
+
+        let (_, args) = stored_call(&format!("set-feeto --router {} --feeto {}", ROUTER, TOKEN2));
+        assert_eq!(arg::<Key>(&args, FEETO_RUNTIME_ARG_NAME), token(TOKEN2));
+    }
+}
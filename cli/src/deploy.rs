@@ -0,0 +1,151 @@
+//! Signing deploys and writing them to disk.
+
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use casper_execution_engine::core::engine_state::executable_deploy_item::ExecutableDeployItem;
+use casper_node::crypto::AsymmetricKeyExt;
+use casper_node::types::{Deploy, TimeDiff, Timestamp};
+use casper_types::{
+    account::AccountHash, bytesrepr::Bytes, runtime_args, Key, PublicKey, RuntimeArgs,
+    SecretKey, U256,
+};
+
+use clap::Args;
+
+use crate::error::Error;
+use crate::parse::Amount;
+
+/// Name of the standard payment's amount argument.
+const PAYMENT_AMOUNT_ARG_NAME: &str = "amount";
+
+/// Options shared by every deploy.
+#[derive(Debug, Args)]
+pub struct DeployOptions {
+    /// PEM file of the secret key signing the deploy.
+    #[clap(long, short = 'k')]
+    pub secret_key: PathBuf,
+
+    /// Name of the chain the deploy is for, e.g. `casper-test`.
+    #[clap(long)]
+    pub chain_name: String,
+
+    /// Gas payment, in CSPR.
+    #[clap(long)]
+    pub payment: Amount,
+
+    /// Time the deploy is valid for once created, e.g. `30min`.
+    #[clap(long, default_value = "30min")]
+    pub ttl: TimeDiff,
+
+    /// Creation time of the deploy, RFC 3339; defaults to now.
+    #[clap(long)]
+    pub timestamp: Option<Timestamp>,
+
+    #[clap(long, default_value = "1")]
+    pub gas_price: u64,
+
+    /// File the deploy JSON is written to.
+    #[clap(long, short = 'o')]
+    pub output: PathBuf,
+
+    /// Overwrite `--output` if it exists.
+    #[clap(long)]
+    pub force: bool,
+}
+
+/// A deploy being prepared: its signer and the time router calls are checked against.
+pub struct Context {
+    secret_key: SecretKey,
+    pub account: AccountHash,
+    timestamp: Timestamp,
+    /// Router deadline matching the deploy's expiry, in milliseconds.
+    ///
+    /// The deploy cannot execute after it expires, so a later deadline would never be checked.
+    pub dead_line: U256,
+}
+
+impl Context {
+    pub fn new(options: &DeployOptions) -> Result<Self, Error> {
+        let secret_key =
+            SecretKey::from_file(&options.secret_key).map_err(|error| Error::SecretKey {
+                path: options.secret_key.clone(),
+                reason: error.to_string(),
+            })?;
+        let timestamp = options.timestamp.unwrap_or_else(Timestamp::now);
+        Ok(Context::with_secret_key(secret_key, timestamp, options.ttl))
+    }
+
+    /// Returns the context of a deploy signed with `secret_key`, created at `timestamp` and
+    /// valid for `ttl`.
+    pub fn with_secret_key(secret_key: SecretKey, timestamp: Timestamp, ttl: TimeDiff) -> Self {
+        let account = PublicKey::from(&secret_key).to_account_hash();
+        Context {
+            secret_key,
+            account,
+            timestamp,
+            dead_line: U256::from((timestamp + ttl).millis()),
+        }
+    }
+
+    /// Returns `to`, defaulting to the signer.
+    pub fn recipient(&self, to: Option<Key>) -> Key {
+        to.unwrap_or(Key::Account(self.account))
+    }
+
+    /// Signs a deploy running `session` and paying with standard payment.
+    pub fn sign(
+        &self,
+        options: &DeployOptions,
+        session: ExecutableDeployItem,
+    ) -> Result<Deploy, Error> {
+        let payment = ExecutableDeployItem::ModuleBytes {
+            module_bytes: Bytes::new(),
+            args: runtime_args! {
+                PAYMENT_AMOUNT_ARG_NAME => options.payment.motes()?
+            },
+        };
+        Ok(Deploy::new(
+            self.timestamp,
+            options.ttl,
+            options.gas_price,
+            vec![],
+            options.chain_name.clone(),
+            payment,
+            session,
+            &self.secret_key,
+            None,
+        ))
+    }
+}
+
+/// Reads session wasm from `path`.
+pub fn read_wasm(path: &Path) -> Result<Bytes, Error> {
+    std::fs::read(path)
+        .map(Bytes::from)
+        .map_err(|error| Error::Io {
+            path: path.to_path_buf(),
+            error,
+        })
+}
+
+/// Writes `deploy` as JSON to `--output`.
+pub fn write(options: &DeployOptions, deploy: &Deploy) -> Result<(), Error> {
+    let json = serde_json::to_string_pretty(deploy)?;
+    let io_error = |error: std::io::Error| Error::Io {
+        path: options.output.clone(),
+        error,
+    };
+    let mut file = OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .create_new(!options.force)
+        .open(&options.output)
+        .map_err(|error| match error.kind() {
+            std::io::ErrorKind::AlreadyExists => Error::OutputExists(options.output.clone()),
+            _ => io_error(error),
+        })?;
+    file.write_all(json.as_bytes()).map_err(io_error)
+}
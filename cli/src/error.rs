@@ -0,0 +1,65 @@
+use std::fmt::{self, Display, Formatter};
+use std::io;
+use std::path::PathBuf;
+
+/// Failure to prepare a deploy.
+#[derive(Debug)]
+pub enum Error {
+    /// A hash is not 32 hex-encoded bytes, with or without a known prefix.
+    InvalidHash(String),
+    /// An account is neither a hex public key nor a formatted account hash.
+    InvalidAccount(String),
+    /// An amount is not a non-negative decimal number.
+    InvalidAmount(String),
+    /// An amount has more fractional digits than its token's decimals.
+    TooManyDecimals { amount: String, decimals: u8 },
+    /// An amount does not fit its type once scaled by its token's decimals.
+    AmountOverflow(String),
+    /// A split-swap leg is not `AMOUNT:PATH`.
+    InvalidLeg(String),
+    /// The secret key file cannot be loaded.
+    SecretKey { path: PathBuf, reason: String },
+    /// The output file exists and `--force` was not given.
+    OutputExists(PathBuf),
+    Io { path: PathBuf, error: io::Error },
+    Json(serde_json::Error),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::InvalidHash(hash) => write!(f, "invalid hash `{}`", hash),
+            Error::InvalidAccount(account) => write!(
+                f,
+                "invalid account `{}`: expected a hex public key or `account-hash-…`",
+                account
+            ),
+            Error::InvalidAmount(amount) => write!(f, "invalid amount `{}`", amount),
+            Error::TooManyDecimals { amount, decimals } => write!(
+                f,
+                "amount `{}` has more than {} fractional digits",
+                amount, decimals
+            ),
+            Error::AmountOverflow(amount) => write!(f, "amount `{}` is too large", amount),
+            Error::InvalidLeg(leg) => {
+                write!(f, "invalid leg `{}`: expected `AMOUNT:TOKEN,TOKEN,…`", leg)
+            }
+            Error::SecretKey { path, reason } => {
+                write!(f, "cannot load secret key {}: {}", path.display(), reason)
+            }
+            Error::OutputExists(path) => {
+                write!(f, "{} exists, pass --force to overwrite it", path.display())
+            }
+            Error::Io { path, error } => write!(f, "{}: {}", path.display(), error),
+            Error::Json(error) => write!(f, "cannot serialize deploy: {}", error),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<serde_json::Error> for Error {
+    fn from(error: serde_json::Error) -> Self {
+        Error::Json(error)
+    }
+}
@@ -0,0 +1,43 @@
+//! Prepares signed deploys of Swappery router operations without contacting a node.
+//!
+//! Each subcommand writes one deploy as JSON, ready to be carried off an air-gapped machine and
+//! sent with `casper-client send-deploy`. Router deadlines are set to the deploy's expiry.
+
+mod commands;
+mod deploy;
+mod error;
+mod parse;
+
+use std::process;
+
+use clap::Parser;
+
+use commands::Command;
+use deploy::{Context, DeployOptions};
+use error::Error;
+
+#[derive(Debug, Parser)]
+#[clap(name = "swappery-cli", version, about)]
+struct Cli {
+    #[clap(flatten)]
+    deploy: DeployOptions,
+
+    #[clap(subcommand)]
+    command: Command,
+}
+
+fn run(cli: Cli) -> Result<(), Error> {
+    let context = Context::new(&cli.deploy)?;
+    let session = cli.command.session(&context)?;
+    let deploy = context.sign(&cli.deploy, session)?;
+    deploy::write(&cli.deploy, &deploy)?;
+    println!("{} {}", deploy.id(), cli.deploy.output.display());
+    Ok(())
+}
+
+fn main() {
+    if let Err(error) = run(Cli::parse()) {
+        eprintln!("error: {}", error);
+        process::exit(1);
+    }
+}
@@ -0,0 +1,175 @@
+//! Parsing of the human-readable values commands take.
+//!
+//! Hashes are hex, optionally prefixed the way casper-client prints them. Accounts are hex public
+//! keys or formatted account hashes. Amounts are decimal numbers of whole tokens, scaled by the
+//! token's decimals once the command knows them.
+
+use std::str::FromStr;
+
+use casper_types::{
+    account::AccountHash, AsymmetricType, ContractHash, ContractPackageHash, Key, PublicKey,
+    U256, U512,
+};
+
+use crate::error::Error;
+
+const HASH_PREFIXES: [&str; 3] = ["hash-", "contract-package-wasm", "contract-"];
+const ACCOUNT_HASH_PREFIX: &str = "account-hash-";
+
+fn hash_bytes(input: &str) -> Result<[u8; 32], Error> {
+    let hex_hash = HASH_PREFIXES
+        .iter()
+        .find_map(|prefix| input.strip_prefix(prefix))
+        .unwrap_or(input);
+    let mut bytes = [0u8; 32];
+    hex::decode_to_slice(hex_hash, &mut bytes).map_err(|_| Error::InvalidHash(input.into()))?;
+    Ok(bytes)
+}
+
+/// Parses a token contract hash, `hash-…`, `contract-…` or bare hex.
+pub fn token(input: &str) -> Result<ContractHash, Error> {
+    hash_bytes(input).map(ContractHash::new)
+}
+
+/// Parses a comma-separated list of token contract hashes.
+pub fn path(input: &str) -> Result<Vec<ContractHash>, Error> {
+    input.split(',').map(str::trim).map(token).collect()
+}
+
+/// Parses a contract package hash, `hash-…`, `contract-package-wasm…` or bare hex.
+pub fn package(input: &str) -> Result<ContractPackageHash, Error> {
+    hash_bytes(input).map(ContractPackageHash::new)
+}
+
+/// Parses an account, as a hex public key or `account-hash-…`.
+pub fn account(input: &str) -> Result<AccountHash, Error> {
+    if input.starts_with(ACCOUNT_HASH_PREFIX) {
+        return AccountHash::from_formatted_str(input)
+            .map_err(|_| Error::InvalidAccount(input.into()));
+    }
+    PublicKey::from_hex(input)
+        .map(|public_key| public_key.to_account_hash())
+        .map_err(|_| Error::InvalidAccount(input.into()))
+}
+
+/// Parses a recipient: an account, or the `hash-…` of a contract package.
+pub fn recipient(input: &str) -> Result<Key, Error> {
+    if input.starts_with("hash-") {
+        return package(input).map(|package| Key::Hash(package.value()));
+    }
+    account(input).map(Key::Account)
+}
+
+/// A decimal amount of whole tokens, such as `12.5`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Amount(String);
+
+impl Amount {
+    fn digits(&self, decimals: u8) -> Result<String, Error> {
+        let (whole, fraction) = self.0.split_once('.').unwrap_or((&self.0, ""));
+        if fraction.len() > usize::from(decimals) {
+            return Err(Error::TooManyDecimals {
+                amount: self.0.clone(),
+                decimals,
+            });
+        }
+        Ok(format!("{}{:0<width$}", whole, fraction, width = usize::from(decimals)))
+    }
+
+    /// Returns the amount in the token's smallest unit.
+    pub fn scale(&self, decimals: u8) -> Result<U256, Error> {
+        U256::from_dec_str(&self.digits(decimals)?)
+            .map_err(|_| Error::AmountOverflow(self.0.clone()))
+    }
+
+    /// Returns the amount in motes, taking it as CSPR.
+    pub fn motes(&self) -> Result<U512, Error> {
+        U512::from_dec_str(&self.digits(CSPR_DECIMALS)?)
+            .map_err(|_| Error::AmountOverflow(self.0.clone()))
+    }
+}
+
+impl FromStr for Amount {
+    type Err = Error;
+
+    fn from_str(input: &str) -> Result<Self, Error> {
+        let (whole, fraction) = input.split_once('.').unwrap_or((input, ""));
+        let is_digits = |part: &str| part.bytes().all(|byte| byte.is_ascii_digit());
+        if whole.is_empty() || !is_digits(whole) || !is_digits(fraction) {
+            return Err(Error::InvalidAmount(input.into()));
+        }
+        Ok(Amount(input.into()))
+    }
+}
+
+/// Decimals of CSPR, WCSPR and the pairs' LP tokens.
+pub const CSPR_DECIMALS: u8 = 9;
+
+/// One leg of a split swap, `AMOUNT:TOKEN,TOKEN,…`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Leg {
+    pub amount_in: Amount,
+    pub path: Vec<ContractHash>,
+}
+
+impl FromStr for Leg {
+    type Err = Error;
+
+    fn from_str(input: &str) -> Result<Self, Error> {
+        let (amount_in, legs_path) = input
+            .split_once(':')
+            .ok_or_else(|| Error::InvalidLeg(input.into()))?;
+        Ok(Leg {
+            amount_in: amount_in.parse()?,
+            path: path(legs_path)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HASH: &str = "2a1b6f35c4e4bc6b2b1f2bde00ba2b4ac3eb4c0d3e8fa3a47fb2b0e8b1b8b9f0";
+
+    #[test]
+    fn should_scale_amounts() {
+        let amount: Amount = "12.5".parse().unwrap();
+        assert_eq!(amount.scale(9).unwrap(), U256::from(12_500_000_000u64));
+        assert_eq!(amount.scale(1).unwrap(), U256::from(125));
+        assert_eq!(amount.motes().unwrap(), U512::from(12_500_000_000u64));
+        let amount: Amount = "7".parse().unwrap();
+        assert_eq!(amount.scale(0).unwrap(), U256::from(7));
+    }
+
+    #[test]
+    fn should_reject_bad_amounts() {
+        for input in ["", ".5", "1.2.3", "-1", "1e9", "one"] {
+            assert!(input.parse::<Amount>().is_err(), "{}", input);
+        }
+        let amount: Amount = "0.001".parse().unwrap();
+        assert!(matches!(amount.scale(2), Err(Error::TooManyDecimals { .. })));
+        let amount: Amount = "1".repeat(80).parse().unwrap();
+        assert!(matches!(amount.scale(0), Err(Error::AmountOverflow(_))));
+    }
+
+    #[test]
+    fn should_parse_prefixed_hashes() {
+        let expected = token(HASH).unwrap();
+        assert_eq!(token(&format!("hash-{}", HASH)).unwrap(), expected);
+        assert_eq!(token(&format!("contract-{}", HASH)).unwrap(), expected);
+        assert!(token(&HASH[2..]).is_err());
+        assert_eq!(
+            recipient(&format!("hash-{}", HASH)).unwrap(),
+            Key::Hash(expected.value())
+        );
+    }
+
+    #[test]
+    fn should_parse_legs() {
+        let leg: Leg = format!("1.5:hash-{},{}", HASH, HASH).parse().unwrap();
+        assert_eq!(leg.amount_in, "1.5".parse().unwrap());
+        assert_eq!(leg.path.len(), 2);
+        assert!(HASH.parse::<Leg>().is_err());
+    }
+}
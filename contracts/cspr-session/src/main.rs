@@ -0,0 +1,117 @@
+//! Session code for router entry points that take native CSPR.
+//!
+//! Contracts cannot pull CSPR out of an account, so this session funds a fresh purse from the
+//! caller's main purse, calls `entry_point` on the `router` package with that purse added to the
+//! forwarded arguments, and refunds whatever the router did not wrap back to the main purse.
+#![no_std]
+#![no_main]
+#![feature(default_alloc_error_handler)]
+
+extern crate alloc;
+
+use alloc::{string::String, vec::Vec};
+
+use casper_contract::{
+    contract_api::{account, runtime, system},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use casper_types::{
+    runtime_args, ApiError, ContractPackageHash, Key, RuntimeArgs, URef, U256, U512,
+};
+use swappery_math::widen;
+use swappery_names::{
+    ADD_LIQUIDITY_CSPR_ENTRY_POINT_NAME, AMOUNT_CSPR_DESIRED_RUNTIME_ARG_NAME,
+    AMOUNT_CSPR_MIN_RUNTIME_ARG_NAME, AMOUNT_IN_MAX_RUNTIME_ARG_NAME, AMOUNT_IN_RUNTIME_ARG_NAME,
+    AMOUNT_OUT_MIN_RUNTIME_ARG_NAME, AMOUNT_OUT_RUNTIME_ARG_NAME,
+    AMOUNT_TOKEN_DESIRED_RUNTIME_ARG_NAME, AMOUNT_TOKEN_MIN_RUNTIME_ARG_NAME,
+    DEAD_LINE_RUNTIME_ARG_NAME, ENTRY_POINT_RUNTIME_ARG_NAME, PATH_RUNTIME_ARG_NAME,
+    PURSE_RUNTIME_ARG_NAME, ROUTER_RUNTIME_ARG_NAME, SWAP_CSPR_FOR_EXACT_TOKENS_ENTRY_POINT_NAME,
+    SWAP_EXACT_CSPR_FOR_TOKENS_ENTRY_POINT_NAME, TOKEN_RUNTIME_ARG_NAME, TO_RUNTIME_ARG_NAME,
+};
+
+/// Reverted with when `entry_point` is not a router entry point taking CSPR.
+const ERROR_UNKNOWN_ENTRY_POINT: u16 = 1;
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let router: ContractPackageHash = {
+        let router_key: Key = runtime::get_named_arg(ROUTER_RUNTIME_ARG_NAME);
+        router_key.into_hash().unwrap_or_revert().into()
+    };
+    let entry_point: String = runtime::get_named_arg(ENTRY_POINT_RUNTIME_ARG_NAME);
+    let to: Key = runtime::get_named_arg(TO_RUNTIME_ARG_NAME);
+    let dead_line: U256 = runtime::get_named_arg(DEAD_LINE_RUNTIME_ARG_NAME);
+
+    let (amount, mut args) = match entry_point.as_str() {
+        ADD_LIQUIDITY_CSPR_ENTRY_POINT_NAME => {
+            let token: Key = runtime::get_named_arg(TOKEN_RUNTIME_ARG_NAME);
+            let amount_token_desired: U256 =
+                runtime::get_named_arg(AMOUNT_TOKEN_DESIRED_RUNTIME_ARG_NAME);
+            let amount_cspr_desired: U256 =
+                runtime::get_named_arg(AMOUNT_CSPR_DESIRED_RUNTIME_ARG_NAME);
+            let amount_token_min: U256 = runtime::get_named_arg(AMOUNT_TOKEN_MIN_RUNTIME_ARG_NAME);
+            let amount_cspr_min: U256 = runtime::get_named_arg(AMOUNT_CSPR_MIN_RUNTIME_ARG_NAME);
+            (
+                amount_cspr_desired,
+                runtime_args! {
+                    TOKEN_RUNTIME_ARG_NAME => token,
+                    AMOUNT_TOKEN_DESIRED_RUNTIME_ARG_NAME => amount_token_desired,
+                    AMOUNT_CSPR_DESIRED_RUNTIME_ARG_NAME => amount_cspr_desired,
+                    AMOUNT_TOKEN_MIN_RUNTIME_ARG_NAME => amount_token_min,
+                    AMOUNT_CSPR_MIN_RUNTIME_ARG_NAME => amount_cspr_min,
+                },
+            )
+        }
+        SWAP_EXACT_CSPR_FOR_TOKENS_ENTRY_POINT_NAME => {
+            let amount_in: U256 = runtime::get_named_arg(AMOUNT_IN_RUNTIME_ARG_NAME);
+            let amount_out_min: U256 = runtime::get_named_arg(AMOUNT_OUT_MIN_RUNTIME_ARG_NAME);
+            let path: Vec<Key> = runtime::get_named_arg(PATH_RUNTIME_ARG_NAME);
+            (
+                amount_in,
+                runtime_args! {
+                    AMOUNT_IN_RUNTIME_ARG_NAME => amount_in,
+                    AMOUNT_OUT_MIN_RUNTIME_ARG_NAME => amount_out_min,
+                    PATH_RUNTIME_ARG_NAME => path,
+                },
+            )
+        }
+        SWAP_CSPR_FOR_EXACT_TOKENS_ENTRY_POINT_NAME => {
+            let amount_out: U256 = runtime::get_named_arg(AMOUNT_OUT_RUNTIME_ARG_NAME);
+            let amount_in_max: U256 = runtime::get_named_arg(AMOUNT_IN_MAX_RUNTIME_ARG_NAME);
+            let path: Vec<Key> = runtime::get_named_arg(PATH_RUNTIME_ARG_NAME);
+            (
+                amount_in_max,
+                runtime_args! {
+                    AMOUNT_OUT_RUNTIME_ARG_NAME => amount_out,
+                    AMOUNT_IN_MAX_RUNTIME_ARG_NAME => amount_in_max,
+                    PATH_RUNTIME_ARG_NAME => path,
+                },
+            )
+        }
+        _ => runtime::revert(ApiError::User(ERROR_UNKNOWN_ENTRY_POINT)),
+    };
+
+    let main_purse: URef = account::get_main_purse();
+    let purse: URef = system::create_purse();
+    system::transfer_from_purse_to_purse(main_purse, purse, widen(amount), None)
+        .unwrap_or_revert();
+
+    args.insert(TO_RUNTIME_ARG_NAME, to).unwrap_or_revert();
+    args.insert(DEAD_LINE_RUNTIME_ARG_NAME, dead_line).unwrap_or_revert();
+    args.insert(PURSE_RUNTIME_ARG_NAME, purse).unwrap_or_revert();
+    if entry_point == ADD_LIQUIDITY_CSPR_ENTRY_POINT_NAME {
+        runtime::call_versioned_contract::<U256>(router, None, &entry_point, args);
+    } else {
+        runtime::call_versioned_contract::<()>(router, None, &entry_point, args);
+    }
+
+    let refund: U512 = system::get_purse_balance(purse).unwrap_or_revert();
+    if !refund.is_zero() {
+        system::transfer_from_purse_to_purse(purse, main_purse, refund, None).unwrap_or_revert();
+    }
+}
+
+#[panic_handler]
+fn my_panic(_info: &core::panic::PanicInfo) -> ! {
+    loop {}
+}
@@ -0,0 +1,154 @@
+//! Event storage in the Casper Event Standard (CES) layout.
+//!
+//! A contract emitting events owns four named keys: `__events_schema` with the field types of
+//! every event it can emit, the `__events` dictionary holding serialized events keyed by their
+//! index, `__events_length` counting them and `__events_ces_version`. An event is stored as its
+//! `event_`-prefixed name followed by its fields in schema order.
+
+use alloc::{
+    collections::BTreeMap,
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use casper_contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use casper_types::{
+    bytesrepr::{self, Bytes, FromBytes, ToBytes},
+    contracts::NamedKeys,
+    ApiError, CLType, CLTyped, Key, URef,
+};
+
+/// Name of the dictionary holding the events.
+pub const EVENTS_DICT: &str = "__events";
+/// Name of the named key counting the events.
+pub const EVENTS_LENGTH: &str = "__events_length";
+/// Name of the named key holding the [`Schemas`].
+pub const EVENTS_SCHEMA: &str = "__events_schema";
+/// Name of the named key holding [`CES_VERSION`].
+pub const CES_VERSION_KEY: &str = "__events_ces_version";
+/// Version of the standard this layout follows.
+pub const CES_VERSION: &str = "0.1.0";
+/// Prefix of every serialized event name.
+pub const EVENT_PREFIX: &str = "event_";
+
+/// Context-local name the events dictionary is created under before it is handed to a contract,
+/// which would clash with the caller's own `__events` when one contract installs another.
+const PENDING_EVENTS_DICT: &str = "__events_pending";
+
+/// Field names and types of one event, in serialization order.
+pub type Schema = Vec<(String, CLType)>;
+
+/// Schemas of every event a contract emits, keyed by event name.
+#[derive(Default)]
+pub struct Schemas(pub BTreeMap<String, Schema>);
+
+impl Schemas {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers the event `name` with `fields`.
+    pub fn with(mut self, name: &str, fields: &[(&str, CLType)]) -> Self {
+        let schema = fields
+            .iter()
+            .map(|(field, cl_type)| (field.to_string(), cl_type.clone()))
+            .collect();
+        self.0.insert(name.to_string(), schema);
+        self
+    }
+}
+
+impl CLTyped for Schemas {
+    fn cl_type() -> CLType {
+        CLType::Any
+    }
+}
+
+impl ToBytes for Schemas {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        self.0.to_bytes()
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.0.serialized_length()
+    }
+}
+
+impl FromBytes for Schemas {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (schemas, remainder) = BTreeMap::from_bytes(bytes)?;
+        Ok((Schemas(schemas), remainder))
+    }
+}
+
+/// An event that can be stored in the CES layout.
+pub trait CesEvent {
+    /// Returns the name the event's schema is registered under.
+    fn name(&self) -> &'static str;
+
+    /// Serializes the event's fields in schema order.
+    fn fields_to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error>;
+
+    /// Returns the schemas of every event of this type.
+    fn schemas() -> Schemas;
+}
+
+/// Creates the CES named keys for events of type `T`, to be passed into the new contract's named
+/// keys. Nothing is left in the caller's named keys.
+pub fn init<T: CesEvent>() -> NamedKeys {
+    let events_uref: URef = storage::new_dictionary(PENDING_EVENTS_DICT).unwrap_or_revert();
+    runtime::remove_key(PENDING_EVENTS_DICT);
+
+    let mut named_keys = NamedKeys::new();
+    named_keys.insert(String::from(EVENTS_DICT), Key::from(events_uref));
+    named_keys.insert(
+        String::from(EVENTS_LENGTH),
+        Key::from(storage::new_uref(0u32)),
+    );
+    named_keys.insert(
+        String::from(EVENTS_SCHEMA),
+        Key::from(storage::new_uref(T::schemas())),
+    );
+    named_keys.insert(
+        String::from(CES_VERSION_KEY),
+        Key::from(storage::new_uref(String::from(CES_VERSION))),
+    );
+    named_keys
+}
+
+/// Emits `event` into the calling contract's events.
+pub fn emit<T: CesEvent>(event: &T) {
+    let events_uref = named_uref(runtime::get_key(EVENTS_DICT));
+    let length_uref = named_uref(runtime::get_key(EVENTS_LENGTH));
+    write_event(events_uref, length_uref, event);
+}
+
+/// Emits `event` into the events created by [`init`], for use before the contract can run.
+pub fn emit_into<T: CesEvent>(named_keys: &NamedKeys, event: &T) {
+    let events_uref = named_uref(named_keys.get(EVENTS_DICT).copied());
+    let length_uref = named_uref(named_keys.get(EVENTS_LENGTH).copied());
+    write_event(events_uref, length_uref, event);
+}
+
+fn named_uref(key: Option<Key>) -> URef {
+    key.and_then(Key::into_uref)
+        .ok_or(ApiError::MissingKey)
+        .unwrap_or_revert()
+}
+
+fn write_event<T: CesEvent>(events_uref: URef, length_uref: URef, event: &T) {
+    let mut bytes = format!("{}{}", EVENT_PREFIX, event.name())
+        .to_bytes()
+        .unwrap_or_revert();
+    bytes.append(&mut event.fields_to_bytes().unwrap_or_revert());
+
+    let length: u32 = storage::read(length_uref)
+        .unwrap_or_revert()
+        .unwrap_or_revert();
+    storage::dictionary_put(events_uref, &length.to_string(), Bytes::from(bytes));
+    storage::write(length_uref, length + 1);
+}
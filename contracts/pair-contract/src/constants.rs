@@ -1,7 +1,5 @@
-pub const TO_RUNTIME_ARG_NAME: &str = "to";
-pub const FEETO_RUNTIME_ARG_NAME: &str = "feeto";
-pub const AMOUNT0_RUNTIME_ARG_NAME: &str = "amount0";
-pub const AMOUNT1_RUNTIME_ARG_NAME: &str = "amount1";
+pub use swappery_names::*;
+
 pub const RESERVE0_KEY_NAME: &str = "reserve0";
 pub const RESERVE1_KEY_NAME: &str = "reserve1";
 pub const TOKEN0_KEY_NAME: &str = "token0";
@@ -9,11 +7,14 @@ pub const TOKEN1_KEY_NAME: &str = "token1";
 pub const KLAST_KEY_NAME: &str = "klast";
 pub const FACTORY_KEY_NAME: &str = "factory";
 pub const LOCKED_FLAG_KEY_NAME: &str = "locked";
-pub const MINT_ENTRY_POINT_NAME: &str = "mint";
-pub const BURN_ENTRY_POINT_NAME: &str = "burn";
-pub const SWAP_ENTRY_POINT_NAME: &str = "swap";
-pub const GET_RESERVES_ENTRY_POINT_NAME: &str = "get_reserves";
-pub const MINIMUM_LIQUIDITY: u64 = 1000;
+pub const PRICE0_CUMULATIVE_LAST_KEY_NAME: &str = "price0_cumulative_last";
+pub const PRICE1_CUMULATIVE_LAST_KEY_NAME: &str = "price1_cumulative_last";
+pub const BLOCK_TIMESTAMP_LAST_KEY_NAME: &str = "block_timestamp_last";
+pub const SWAP_FEE_BPS_KEY_NAME: &str = "swap_fee_bps";
+pub use swappery_math::MINIMUM_LIQUIDITY;
+/// Number of fractional bits of the fixed-point prices accumulated by the pair.
+pub const PRICE_RESOLUTION: usize = 112;
+pub use swappery_math::{DEFAULT_SWAP_FEE_BPS, FEE_DENOMINATOR, MAX_SWAP_FEE_BPS};
 
 /// Name of named-key for `name`.
 pub const NAME_KEY_NAME: &str = "name";
@@ -27,41 +28,3 @@ pub const BALANCES_KEY_NAME: &str = "balances";
 pub const ALLOWANCES_KEY_NAME: &str = "allowances";
 /// Name of named-key for `total_supply`
 pub const TOTAL_SUPPLY_KEY_NAME: &str = "total_supply";
-
-/// Name of `name` entry point.
-pub const NAME_ENTRY_POINT_NAME: &str = "name";
-/// Name of `symbol` entry point.
-pub const SYMBOL_ENTRY_POINT_NAME: &str = "symbol";
-/// Name of `decimals` entry point.
-pub const DECIMALS_ENTRY_POINT_NAME: &str = "decimals";
-/// Name of `balance_of` entry point.
-pub const BALANCE_OF_ENTRY_POINT_NAME: &str = "balance_of";
-/// Name of `transfer` entry point.
-pub const TRANSFER_ENTRY_POINT_NAME: &str = "transfer";
-/// Name of `approve` entry point.
-pub const APPROVE_ENTRY_POINT_NAME: &str = "approve";
-/// Name of `allowance` entry point.
-pub const ALLOWANCE_ENTRY_POINT_NAME: &str = "allowance";
-/// Name of `transfer_from` entry point.
-pub const TRANSFER_FROM_ENTRY_POINT_NAME: &str = "transfer_from";
-/// Name of `total_supply` entry point.
-pub const TOTAL_SUPPLY_ENTRY_POINT_NAME: &str = "total_supply";
-
-/// Name of `address` runtime argument.
-pub const ADDRESS_RUNTIME_ARG_NAME: &str = "address";
-/// Name of `owner` runtime argument.
-pub const OWNER_RUNTIME_ARG_NAME: &str = "owner";
-/// Name of `spender` runtime argument.
-pub const SPENDER_RUNTIME_ARG_NAME: &str = "spender";
-/// Name of `amount` runtime argument.
-pub const AMOUNT_RUNTIME_ARG_NAME: &str = "amount";
-/// Name of `recipient` runtime argument.
-pub const RECIPIENT_RUNTIME_ARG_NAME: &str = "recipient";
-/// Name of `name` runtime argument.
-pub const NAME_RUNTIME_ARG_NAME: &str = "name";
-/// Name of `symbol` runtime argument.
-pub const SYMBOL_RUNTIME_ARG_NAME: &str = "symbol";
-/// Name of `decimals` runtime argument.
-pub const DECIMALS_RUNTIME_ARG_NAME: &str = "decimals";
-/// Name of `total_supply` runtime argument.
-pub const TOTAL_SUPPLY_RUNTIME_ARG_NAME: &str = "total_supply";
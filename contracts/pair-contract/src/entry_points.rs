@@ -1,7 +1,8 @@
 use alloc::{string::String, vec, vec::Vec};
 
 use casper_types::{
-    CLType, CLTyped, EntryPoint, EntryPointAccess, EntryPointType, EntryPoints, Parameter, U256,
+    bytesrepr::Bytes, CLType, CLTyped, EntryPoint, EntryPointAccess, EntryPointType, EntryPoints,
+    Parameter, U256,
 };
 
 use crate::address::Address;
@@ -141,6 +142,8 @@ pub fn burn() -> EntryPoint {
 }
 
 /// Returns the 'swap' entry point.
+///
+/// `data` is optional; when it is non-empty the output is flash-swapped to the `to` contract.
 pub fn swap() -> EntryPoint {
     EntryPoint::new(
         String::from(consts::SWAP_ENTRY_POINT_NAME),
@@ -148,6 +151,7 @@ pub fn swap() -> EntryPoint {
             Parameter::new(consts::AMOUNT0_RUNTIME_ARG_NAME, U256::cl_type()),
             Parameter::new(consts::AMOUNT1_RUNTIME_ARG_NAME, U256::cl_type()),
             Parameter::new(consts::TO_RUNTIME_ARG_NAME, Address::cl_type()),
+            Parameter::new(consts::DATA_RUNTIME_ARG_NAME, Bytes::cl_type()),
         ],
         CLType::Unit,
         EntryPointAccess::Public,
@@ -155,6 +159,28 @@ pub fn swap() -> EntryPoint {
     )
 }
 
+/// Returns the 'skim' entry point.
+pub fn skim() -> EntryPoint {
+    EntryPoint::new(
+        String::from(consts::SKIM_ENTRY_POINT_NAME),
+        vec![Parameter::new(consts::TO_RUNTIME_ARG_NAME, Address::cl_type())],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    )
+}
+
+/// Returns the 'sync' entry point.
+pub fn sync() -> EntryPoint {
+    EntryPoint::new(
+        String::from(consts::SYNC_ENTRY_POINT_NAME),
+        vec![],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    )
+}
+
 /// Returns the 'get_reserves' entry point.
 pub fn get_reserves() -> EntryPoint {
     EntryPoint::new(
@@ -166,6 +192,39 @@ pub fn get_reserves() -> EntryPoint {
     )
 }
 
+/// Returns the 'get_cumulative_prices' entry point.
+pub fn get_cumulative_prices() -> EntryPoint {
+    EntryPoint::new(
+        String::from(consts::GET_CUMULATIVE_PRICES_ENTRY_POINT_NAME),
+        vec![],
+        <(U256, U256, u64)>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    )
+}
+
+/// Returns the 'get_swap_fee' entry point.
+pub fn get_swap_fee() -> EntryPoint {
+    EntryPoint::new(
+        String::from(consts::GET_SWAP_FEE_ENTRY_POINT_NAME),
+        vec![],
+        u32::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    )
+}
+
+/// Returns the 'set_swap_fee' entry point, callable by the pair's factory only.
+pub fn set_swap_fee() -> EntryPoint {
+    EntryPoint::new(
+        String::from(consts::SET_SWAP_FEE_ENTRY_POINT_NAME),
+        vec![Parameter::new(consts::SWAP_FEE_BPS_RUNTIME_ARG_NAME, u32::cl_type())],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    )
+}
+
 /// Returns the default entry points of LP token.
 pub fn default() -> EntryPoints {
     let mut entry_points = EntryPoints::new();
@@ -181,6 +240,11 @@ pub fn default() -> EntryPoints {
     entry_points.add_entry_point(mint());
     entry_points.add_entry_point(burn());
     entry_points.add_entry_point(swap());
+    entry_points.add_entry_point(skim());
+    entry_points.add_entry_point(sync());
     entry_points.add_entry_point(get_reserves());
+    entry_points.add_entry_point(get_cumulative_prices());
+    entry_points.add_entry_point(get_swap_fee());
+    entry_points.add_entry_point(set_swap_fee());
     entry_points
 }
@@ -1,4 +1,5 @@
 use casper_types::ApiError;
+use swappery_math::MathError;
 
 pub enum Error {
     InsufficientInputAmount,
@@ -14,6 +15,7 @@ pub enum Error {
     InvalidContext,
     InsufficientBalance,
     InsufficientAllowance,
+    InvalidSwapFee,
 }
 
 const ERROR_INSUFFICIENT_INPUT_AMOUNT: u16 = u16::MAX - 4;
@@ -29,6 +31,8 @@ const ERROR_K: u16 = u16::MAX - 13;
 const ERROR_INVALID_CONTEXT: u16 = u16::MAX - 14;
 const ERROR_INSUFFICIENT_BALANCE: u16 = u16::MAX - 15;
 const ERROR_INSUFFICIENT_ALLOWANCE: u16 = u16::MAX - 16;
+// Shared with the router's error, which rejects the same fees before they reach the pair.
+const ERROR_INVALID_SWAP_FEE: u16 = u16::MAX - 27;
 
 impl From<Error> for ApiError {
     fn from(error: Error) -> Self {
@@ -46,7 +50,22 @@ impl From<Error> for ApiError {
             Error::InvalidContext => ERROR_INVALID_CONTEXT,
             Error::InsufficientBalance => ERROR_INSUFFICIENT_BALANCE,
             Error::InsufficientAllowance => ERROR_INSUFFICIENT_ALLOWANCE,
+            Error::InvalidSwapFee => ERROR_INVALID_SWAP_FEE,
         };
         ApiError::User(user_error)
     }
 }
+
+impl From<MathError> for Error {
+    fn from(error: MathError) -> Self {
+        match error {
+            MathError::Overflow => Error::OverFlow,
+            MathError::InsufficientAmount | MathError::InsufficientInputAmount => {
+                Error::InsufficientInputAmount
+            }
+            MathError::InsufficientOutputAmount => Error::InsufficientOutputAmount,
+            MathError::InsufficientLiquidity => Error::InsufficientLiquidity,
+            MathError::InvalidFee => Error::InvalidSwapFee,
+        }
+    }
+}
@@ -0,0 +1,152 @@
+//! Events emitted by the pair.
+use alloc::vec::Vec;
+use casper_types::{
+    bytesrepr::{self, ToBytes},
+    CLTyped, U256,
+};
+
+use crate::address::Address;
+use crate::ces::{CesEvent, Schemas};
+
+pub enum PairEvent {
+    Mint {
+        sender: Address,
+        amount0: U256,
+        amount1: U256,
+        to: Address,
+        liquidity: U256,
+    },
+    Burn {
+        sender: Address,
+        amount0: U256,
+        amount1: U256,
+        to: Address,
+        liquidity: U256,
+    },
+    Swap {
+        sender: Address,
+        amount0_in: U256,
+        amount1_in: U256,
+        amount0_out: U256,
+        amount1_out: U256,
+        to: Address,
+    },
+    Sync {
+        reserve0: U256,
+        reserve1: U256,
+    },
+    /// LP minted to the protocol fee recipient out of the growth in `sqrt(k)`.
+    MintFee {
+        fee_to: Address,
+        liquidity: U256,
+    },
+    /// Swap fee changed by the factory.
+    SetSwapFee {
+        swap_fee_bps: u32,
+    },
+}
+
+impl CesEvent for PairEvent {
+    fn name(&self) -> &'static str {
+        match self {
+            PairEvent::Mint { .. } => "mint",
+            PairEvent::Burn { .. } => "burn",
+            PairEvent::Swap { .. } => "swap",
+            PairEvent::Sync { .. } => "sync",
+            PairEvent::MintFee { .. } => "mint_fee",
+            PairEvent::SetSwapFee { .. } => "set_swap_fee",
+        }
+    }
+
+    fn fields_to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut bytes = Vec::new();
+        match self {
+            PairEvent::Mint {
+                sender,
+                amount0,
+                amount1,
+                to,
+                liquidity,
+            }
+            | PairEvent::Burn {
+                sender,
+                amount0,
+                amount1,
+                to,
+                liquidity,
+            } => {
+                bytes.append(&mut sender.to_bytes()?);
+                bytes.append(&mut amount0.to_bytes()?);
+                bytes.append(&mut amount1.to_bytes()?);
+                bytes.append(&mut to.to_bytes()?);
+                bytes.append(&mut liquidity.to_bytes()?);
+            }
+            PairEvent::Swap {
+                sender,
+                amount0_in,
+                amount1_in,
+                amount0_out,
+                amount1_out,
+                to,
+            } => {
+                bytes.append(&mut sender.to_bytes()?);
+                bytes.append(&mut amount0_in.to_bytes()?);
+                bytes.append(&mut amount1_in.to_bytes()?);
+                bytes.append(&mut amount0_out.to_bytes()?);
+                bytes.append(&mut amount1_out.to_bytes()?);
+                bytes.append(&mut to.to_bytes()?);
+            }
+            PairEvent::Sync { reserve0, reserve1 } => {
+                bytes.append(&mut reserve0.to_bytes()?);
+                bytes.append(&mut reserve1.to_bytes()?);
+            }
+            PairEvent::MintFee { fee_to, liquidity } => {
+                bytes.append(&mut fee_to.to_bytes()?);
+                bytes.append(&mut liquidity.to_bytes()?);
+            }
+            PairEvent::SetSwapFee { swap_fee_bps } => {
+                bytes.append(&mut swap_fee_bps.to_bytes()?);
+            }
+        }
+        Ok(bytes)
+    }
+
+    fn schemas() -> Schemas {
+        let liquidity_fields = [
+            ("sender", Address::cl_type()),
+            ("amount0", U256::cl_type()),
+            ("amount1", U256::cl_type()),
+            ("to", Address::cl_type()),
+            ("liquidity", U256::cl_type()),
+        ];
+        Schemas::new()
+            .with("mint", &liquidity_fields)
+            .with("burn", &liquidity_fields)
+            .with(
+                "swap",
+                &[
+                    ("sender", Address::cl_type()),
+                    ("amount0_in", U256::cl_type()),
+                    ("amount1_in", U256::cl_type()),
+                    ("amount0_out", U256::cl_type()),
+                    ("amount1_out", U256::cl_type()),
+                    ("to", Address::cl_type()),
+                ],
+            )
+            .with(
+                "sync",
+                &[
+                    ("reserve0", U256::cl_type()),
+                    ("reserve1", U256::cl_type()),
+                ],
+            )
+            .with(
+                "mint_fee",
+                &[
+                    ("fee_to", Address::cl_type()),
+                    ("liquidity", U256::cl_type()),
+                ],
+            )
+            .with("set_swap_fee", &[("swap_fee_bps", u32::cl_type())])
+    }
+}
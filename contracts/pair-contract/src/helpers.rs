@@ -1,13 +1,24 @@
+use alloc::vec::Vec;
 use core::convert::TryInto;
 
 use casper_contract::{
-    contract_api::{runtime, storage},
+    contract_api::{self, runtime, storage},
+    ext_ffi,
     unwrap_or_revert::UnwrapOrRevert,
 };
-use casper_types::{bytesrepr::{ FromBytes }, system::CallStackElement, ApiError, CLTyped, URef};
+use casper_types::{
+    api_error,
+    bytesrepr::{self, FromBytes},
+    system::CallStackElement,
+    ApiError, CLTyped, URef, U256, U512,
+};
+use swappery_math as math;
 
+use crate::ces;
 use crate::error::Error;
+use crate::event::PairEvent;
 use crate::address::Address;
+use crate::constants::PRICE_RESOLUTION;
 
 /// Gets [`URef`] under a name.
 pub(crate) fn get_uref(name: &str) -> URef {
@@ -27,6 +38,48 @@ where
     value
 }
 
+/// Returns the serialized size of the runtime argument `name`, or `None` if it was not passed.
+fn get_named_arg_size(name: &str) -> Option<usize> {
+    let mut arg_size: usize = 0;
+    let ret = unsafe {
+        ext_ffi::casper_get_named_arg_size(
+            name.as_bytes().as_ptr(),
+            name.len(),
+            &mut arg_size as *mut usize,
+        )
+    };
+    match api_error::result_from(ret) {
+        Ok(_) => Some(arg_size),
+        Err(ApiError::MissingArgument) => None,
+        Err(e) => runtime::revert(e),
+    }
+}
+
+/// Reads the runtime argument `name` if the caller passed it.
+///
+/// Unlike [`runtime::get_named_arg`] this does not revert on a missing argument, which lets entry
+/// points accept optional arguments without breaking existing callers.
+pub fn get_optional_named_arg<T: FromBytes>(name: &str) -> Option<T> {
+    let arg_size = get_named_arg_size(name)?;
+    let arg_bytes = if arg_size > 0 {
+        let data_non_null_ptr = contract_api::alloc_bytes(arg_size);
+        let ret = unsafe {
+            ext_ffi::casper_get_named_arg(
+                name.as_bytes().as_ptr(),
+                name.len(),
+                data_non_null_ptr.as_ptr(),
+                arg_size,
+            )
+        };
+        let data =
+            unsafe { Vec::from_raw_parts(data_non_null_ptr.as_ptr(), arg_size, arg_size) };
+        api_error::result_from(ret).map(|_| data).unwrap_or_revert()
+    } else {
+        Vec::new()
+    };
+    Some(bytesrepr::deserialize(arg_bytes).unwrap_or_revert())
+}
+
 /// Gets the immediate call stack element of the current execution.
 fn get_immediate_call_stack_item() -> Option<CallStackElement> {
     let call_stack = runtime::get_call_stack();
@@ -65,7 +118,7 @@ pub(crate) fn get_immediate_caller_address() -> Result<Address, Error> {
 /// Gets the caller address which is stored on the top of the call stack.
 ///
 /// This is similar to what [`runtime::get_caller`] does but it also supports stored contracts.
-pub(crate) fn get_caller_address() -> Result<Address, Error> {
+pub fn get_caller_address() -> Result<Address, Error> {
     let call_stack = runtime::get_call_stack();
     let top_of_the_stack = call_stack
         .into_iter()
@@ -88,6 +141,21 @@ fn get_last_call_stack_item() -> Option<CallStackElement> {
     call_stack.into_iter().rev().nth(0)
 }
 
+/// Encodes `numerator / denominator` as a fixed-point number with [`PRICE_RESOLUTION`] fractional
+/// bits.
+///
+/// The division is carried out in [`U512`] so large reserves do not lose their high bits; the
+/// result keeps the low 256 bits, matching the wrapping arithmetic of the price accumulators.
+pub(crate) fn encode_price(numerator: U256, denominator: U256) -> U256 {
+    let price: U512 = (math::widen(numerator) << PRICE_RESOLUTION) / math::widen(denominator);
+    math::truncate(price)
+}
+
+/// Emits `event` into the pair's CES events.
+pub(crate) fn emit(event: &PairEvent) {
+    ces::emit(event);
+}
+
 // pub(crate) fn get_key<T: FromBytes + CLTyped>(name: &str) -> Option<T> {
 //     match runtime::get_key(name) {
 //         None => None,
@@ -110,4 +178,4 @@ fn get_last_call_stack_item() -> Option<CallStackElement> {
 //             runtime::put_key(name, key);
 //         }
 //     }
-// }
\ No newline at end of file
+// }
@@ -0,0 +1,1116 @@
+#![no_std]
+
+extern crate alloc;
+
+pub mod address;
+mod allowances;
+mod balances;
+pub mod ces;
+pub mod constants;
+pub mod entry_points;
+pub mod error;
+pub mod event;
+pub mod helpers;
+mod variables;
+
+use alloc::string::String;
+
+use once_cell::unsync::OnceCell;
+
+use casper_types::{
+    account::AccountHash, bytesrepr::Bytes, contracts::NamedKeys, runtime_args, CLValue, Key,
+    RuntimeArgs, URef, U256, U512, ContractHash, ContractPackageHash,
+};
+
+use casper_contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+
+use swappery_math as math;
+
+use constants as consts;
+pub use error::Error;
+pub use address::Address;
+use event::PairEvent;
+
+#[derive(Default)]
+pub struct SwapperyPair {
+    balances_uref: OnceCell<URef>,
+    allowances_uref: OnceCell<URef>,
+    total_supply_uref: OnceCell<URef>,
+    reserve0_uref: OnceCell<URef>,
+    reserve1_uref: OnceCell<URef>,
+    locked_uref: OnceCell<URef>,
+    klast_uref: OnceCell<URef>,
+    price0_cumulative_last_uref: OnceCell<URef>,
+    price1_cumulative_last_uref: OnceCell<URef>,
+    block_timestamp_last_uref: OnceCell<URef>,
+    swap_fee_bps_uref: OnceCell<URef>,
+}
+
+impl SwapperyPair {
+    fn total_supply_uref(&self) -> URef {
+        *self
+            .total_supply_uref
+            .get_or_init(variables::total_supply_uref)
+    }
+
+    fn read_total_supply(&self) -> U256 {
+        variables::read_total_supply_from(self.total_supply_uref())
+    }
+
+    fn write_total_supply(&self, total_supply: U256) {
+        variables::write_total_supply_to(self.total_supply_uref(), total_supply)
+    }
+
+    fn balances_uref(&self) -> URef {
+        *self.balances_uref.get_or_init(balances::get_balances_uref)
+    }
+
+    fn read_balance(&self, owner: Address) -> U256 {
+        balances::read_balance_from(self.balances_uref(), owner)
+    }
+
+    fn write_balance(&mut self, owner: Address, amount: U256) {
+        balances::write_balance_to(self.balances_uref(), owner, amount)
+    }
+
+    fn allowances_uref(&self) -> URef {
+        *self
+            .allowances_uref
+            .get_or_init(allowances::allowances_uref)
+    }
+
+    fn read_allowance(&self, owner: Address, spender: Address) -> U256 {
+        allowances::read_allowance_from(self.allowances_uref(), owner, spender)
+    }
+
+    fn write_allowance(&mut self, owner: Address, spender: Address, amount: U256) {
+        allowances::write_allowance_to(self.allowances_uref(), owner, spender, amount)
+    }
+
+    fn transfer_balance(
+        &mut self,
+        sender: Address,
+        recipient: Address,
+        amount: U256,
+    ) -> Result<(), Error> {
+        balances::transfer_balance(self.balances_uref(), sender, recipient, amount)
+    }
+
+    /// Returns the name of the token.
+    pub fn name(&self) -> String {
+        helpers::read_from(consts::NAME_KEY_NAME)
+    }
+
+    /// Returns the symbol of the token.
+    pub fn symbol(&self) -> String {
+        helpers::read_from(consts::SYMBOL_KEY_NAME)
+    }
+
+    /// Returns the decimals of the token.
+    pub fn decimals(&self) -> u8 {
+        helpers::read_from(consts::DECIMALS_KEY_NAME)
+    }
+
+    /// Returns the total supply of the token.
+    pub fn total_supply(&self) -> U256 {
+        self.read_total_supply()
+    }
+
+    /// Returns the balance of `owner`.
+    pub fn balance_of(&self, owner: Address) -> U256 {
+        self.read_balance(owner)
+    }
+
+    /// Transfers `amount` of tokens from the direct caller to `recipient`.
+    pub fn transfer(&mut self, recipient: Address, amount: U256) -> Result<(), Error> {
+        let sender = helpers::get_immediate_caller_address()?;
+        self.transfer_balance(sender, recipient, amount)
+    }
+
+    /// Transfers `amount` of tokens from `owner` to `recipient` if the direct caller has been
+    /// previously approved to spend the specified amount on behalf of the owner.
+    pub fn transfer_from(
+        &mut self,
+        owner: Address,
+        recipient: Address,
+        amount: U256,
+    ) -> Result<(), Error> {
+        let spender = helpers::get_immediate_caller_address()?;
+        if amount.is_zero() {
+            return Ok(());
+        }
+        let spender_allowance = self.read_allowance(owner, spender);
+        let new_spender_allowance = spender_allowance
+            .checked_sub(amount)
+            .ok_or(Error::InsufficientAllowance)?;
+        self.transfer_balance(owner, recipient, amount)?;
+        self.write_allowance(owner, spender, new_spender_allowance);
+        Ok(())
+    }
+
+    /// Allows `spender` to transfer up to `amount` of the direct caller's tokens.
+    pub fn approve(&mut self, spender: Address, amount: U256) -> Result<(), Error> {
+        let owner = helpers::get_immediate_caller_address()?;
+        self.write_allowance(owner, spender, amount);
+        Ok(())
+    }
+
+    /// Returns the amount of `owner`'s tokens allowed to be spent by `spender`.
+    pub fn allowance(&self, owner: Address, spender: Address) -> U256 {
+        self.read_allowance(owner, spender)
+    }
+
+    /// Mints `amount` new tokens and adds them to `owner`'s balance and to the token total supply.
+    ///
+    /// # Security
+    ///
+    /// This offers no security whatsoever, hence it is advised to NOT expose this method through a
+    /// public entry point.
+    pub fn mint(&mut self, owner: Address, amount: U256) -> Result<(), Error> {
+        let new_balance = {
+            let balance = self.read_balance(owner);
+            balance.checked_add(amount).ok_or(Error::OverFlow)?
+        };
+        let new_total_supply = {
+            let total_supply: U256 = self.read_total_supply();
+            total_supply.checked_add(amount).ok_or(Error::OverFlow)?
+        };
+        self.write_balance(owner, new_balance);
+        self.write_total_supply(new_total_supply);
+        Ok(())
+    }
+
+    /// Burns (i.e. subtracts) `amount` of tokens from `owner`'s balance and from the token total
+    /// supply.
+    ///
+    /// # Security
+    ///
+    /// This offers no security whatsoever, hence it is advised to NOT expose this method through a
+    /// public entry point.
+    pub fn burn(&mut self, owner: Address, amount: U256) -> Result<(), Error> {
+        let new_balance = {
+            let balance = self.read_balance(owner);
+            balance
+                .checked_sub(amount)
+                .ok_or(Error::InsufficientBalance)?
+        };
+        let new_total_supply = {
+            let total_supply = self.read_total_supply();
+            total_supply.checked_sub(amount).ok_or(Error::OverFlow)?
+        };
+        self.write_balance(owner, new_balance);
+        self.write_total_supply(new_total_supply);
+        Ok(())
+    }
+
+    fn reserve0_uref(&self) -> URef {
+        *self.reserve0_uref.get_or_init(variables::reserve0_uref)
+    }
+
+    fn read_reserve0(&self) -> U256 {
+        variables::read_reserve_from(self.reserve0_uref())
+    }
+
+    fn write_reserve0(&self, reserve0: U256) {
+        variables::write_reserve_to(self.reserve0_uref(), reserve0)
+    }
+
+    fn reserve1_uref(&self) -> URef {
+        *self.reserve1_uref.get_or_init(variables::reserve1_uref)
+    }
+
+    fn read_reserve1(&self) -> U256 {
+        variables::read_reserve_from(self.reserve1_uref())
+    }
+
+    fn write_reserve1(&self, reserve1: U256) {
+        variables::write_reserve_to(self.reserve1_uref(), reserve1)
+    }
+
+    fn locked_uref(&self) -> URef {
+        *self.locked_uref.get_or_init(variables::locked_uref)
+    }
+
+    fn read_locked(&self) -> bool {
+        variables::read_locked_from(self.locked_uref())
+    }
+
+    fn write_locked(&self, locked: bool) {
+        variables::write_locked_to(self.locked_uref(), locked)
+    }
+
+    fn klast_uref(&self) -> URef {
+        *self.klast_uref.get_or_init(variables::klast_uref)
+    }
+
+    fn read_klast(&self) -> U512 {
+        variables::read_klast_from(self.klast_uref())
+    }
+
+    fn write_klast(&self, klast: U512) {
+        variables::write_klast_to(self.klast_uref(), klast)
+    }
+
+    fn swap_fee_bps_uref(&self) -> URef {
+        *self.swap_fee_bps_uref.get_or_init(variables::swap_fee_bps_uref)
+    }
+
+    fn read_swap_fee_bps(&self) -> u32 {
+        variables::read_swap_fee_bps_from(self.swap_fee_bps_uref())
+    }
+
+    fn write_swap_fee_bps(&self, swap_fee_bps: u32) {
+        variables::write_swap_fee_bps_to(self.swap_fee_bps_uref(), swap_fee_bps)
+    }
+
+    fn price0_cumulative_last_uref(&self) -> URef {
+        *self
+            .price0_cumulative_last_uref
+            .get_or_init(variables::price0_cumulative_last_uref)
+    }
+
+    fn read_price0_cumulative_last(&self) -> U256 {
+        variables::read_price_cumulative_from(self.price0_cumulative_last_uref())
+    }
+
+    fn write_price0_cumulative_last(&self, price0_cumulative_last: U256) {
+        variables::write_price_cumulative_to(
+            self.price0_cumulative_last_uref(),
+            price0_cumulative_last,
+        )
+    }
+
+    fn price1_cumulative_last_uref(&self) -> URef {
+        *self
+            .price1_cumulative_last_uref
+            .get_or_init(variables::price1_cumulative_last_uref)
+    }
+
+    fn read_price1_cumulative_last(&self) -> U256 {
+        variables::read_price_cumulative_from(self.price1_cumulative_last_uref())
+    }
+
+    fn write_price1_cumulative_last(&self, price1_cumulative_last: U256) {
+        variables::write_price_cumulative_to(
+            self.price1_cumulative_last_uref(),
+            price1_cumulative_last,
+        )
+    }
+
+    fn block_timestamp_last_uref(&self) -> URef {
+        *self
+            .block_timestamp_last_uref
+            .get_or_init(variables::block_timestamp_last_uref)
+    }
+
+    fn read_block_timestamp_last(&self) -> u64 {
+        variables::read_block_timestamp_from(self.block_timestamp_last_uref())
+    }
+
+    fn write_block_timestamp_last(&self, block_timestamp_last: u64) {
+        variables::write_block_timestamp_to(self.block_timestamp_last_uref(), block_timestamp_last)
+    }
+
+    pub fn reserve0(&self) -> U256 {
+        self.read_reserve0()
+    }
+
+    pub fn reserve1(&self) -> U256 {
+        self.read_reserve1()
+    }
+
+    pub fn locked(&self) -> bool {
+        self.read_locked()
+    }
+
+    pub fn token0(&self) -> ContractHash {
+        helpers::read_from(consts::TOKEN0_KEY_NAME)
+    }
+
+    pub fn token1(&self) -> ContractHash {
+        helpers::read_from(consts::TOKEN1_KEY_NAME)
+    }
+
+    pub fn factory(&self) -> Address {
+        helpers::read_from(consts::FACTORY_KEY_NAME)
+    }
+
+    /// Returns the protocol fee recipient configured on the factory.
+    ///
+    /// A pair installed directly by an account has no factory to ask, so its protocol fee is off.
+    pub fn feeto(&self) -> Address {
+        match self.factory() {
+            Address::Contract(factory) => runtime::call_versioned_contract(
+                factory,
+                None,
+                consts::GET_FEETO_ENTRY_POINT_NAME,
+                runtime_args! {},
+            ),
+            Address::Account(_) => Address::from(AccountHash::new([0u8; 32])),
+        }
+    }
+
+    /// Returns the swap fee charged on inputs, in basis points of `FEE_DENOMINATOR`.
+    pub fn swap_fee_bps(&self) -> u32 {
+        self.read_swap_fee_bps()
+    }
+
+    /// Returns the protocol's share of fee growth in basis points of `FEE_DENOMINATOR`, as
+    /// governed by the factory.
+    pub fn protocol_fee_share_bps(&self) -> u32 {
+        match self.factory() {
+            Address::Contract(factory) => runtime::call_versioned_contract(
+                factory,
+                None,
+                consts::GET_PROTOCOL_FEE_SHARE_ENTRY_POINT_NAME,
+                runtime_args! {},
+            ),
+            Address::Account(_) => 0,
+        }
+    }
+
+    pub fn klast(&self) -> U512 {
+        self.read_klast()
+    }
+
+    pub fn price0_cumulative_last(&self) -> U256 {
+        self.read_price0_cumulative_last()
+    }
+
+    pub fn price1_cumulative_last(&self) -> U256 {
+        self.read_price1_cumulative_last()
+    }
+
+    pub fn block_timestamp_last(&self) -> u64 {
+        self.read_block_timestamp_last()
+    }
+
+    /// Writes the new reserves and, on the first call of a block, accumulates the time-weighted
+    /// prices of the previous reserves.
+    ///
+    /// Accumulators are meant to overflow: oracles only ever use the difference between two
+    /// observations, which stays correct under wrapping arithmetic.
+    pub fn _update(&mut self, balance0: U256, balance1: U256) {
+        let _reserve0: U256 = self.read_reserve0();
+        let _reserve1: U256 = self.read_reserve1();
+        let block_timestamp: u64 = u64::from(runtime::get_blocktime());
+        let time_elapsed: u64 = block_timestamp.wrapping_sub(self.read_block_timestamp_last());
+        if time_elapsed > 0 && !_reserve0.is_zero() && !_reserve1.is_zero() {
+            let price0: U256 = helpers::encode_price(_reserve1, _reserve0);
+            let price1: U256 = helpers::encode_price(_reserve0, _reserve1);
+            let (price0_weighted, _) = price0.overflowing_mul(U256::from(time_elapsed));
+            let (price1_weighted, _) = price1.overflowing_mul(U256::from(time_elapsed));
+            let (price0_cumulative_last, _) = self
+                .read_price0_cumulative_last()
+                .overflowing_add(price0_weighted);
+            let (price1_cumulative_last, _) = self
+                .read_price1_cumulative_last()
+                .overflowing_add(price1_weighted);
+            self.write_price0_cumulative_last(price0_cumulative_last);
+            self.write_price1_cumulative_last(price1_cumulative_last);
+        }
+        self.write_reserve0(balance0);
+        self.write_reserve1(balance1);
+        self.write_block_timestamp_last(block_timestamp);
+        helpers::emit(&PairEvent::Sync {
+            reserve0: balance0,
+            reserve1: balance1,
+        });
+    }
+
+    pub fn _mint_fee(&mut self, _reserve0: U256, _reserve1: U256, feeto: Address) -> bool {
+        let fee_on = feeto != Address::from(AccountHash::new([0u8; 32]));
+        let _klast: U512 = self.klast();
+        if fee_on {
+            // Mints the share of the growth in sqrt(k) since the last liquidity event that goes
+            // to the protocol.
+            let liquidity: U256 = math::fee_liquidity(
+                self.read_total_supply(),
+                _reserve0,
+                _reserve1,
+                _klast,
+                self.protocol_fee_share_bps(),
+            )
+            .map_err(Error::from)
+            .unwrap_or_revert();
+            if liquidity > U256::zero() {
+                SwapperyPair::default()
+                    .mint(feeto, liquidity)
+                    .unwrap_or_revert();
+                helpers::emit(&PairEvent::MintFee {
+                    fee_to: feeto,
+                    liquidity,
+                });
+            }
+        } else if !(_klast.is_zero()) {
+            self.write_klast(U512::zero());
+        }
+        return fee_on;
+    }
+
+    /// Installs a pair, returning its package and contract hashes and its events dictionary.
+    pub fn create(
+        name: String,
+        symbol: String,
+        decimals: u8,
+        initial_supply: U256,
+        contract_key_name: &str,
+        token0: ContractHash,
+        token1: ContractHash,
+        factory: Address,
+        swap_fee_bps: u32,
+    ) -> Result<(ContractPackageHash, ContractHash, URef), Error> {
+        let balances_uref = storage::new_dictionary(consts::BALANCES_KEY_NAME).unwrap_or_revert();
+        let allowances_uref = storage::new_dictionary(consts::ALLOWANCES_KEY_NAME).unwrap_or_revert();
+        let total_supply_uref = storage::new_uref(initial_supply).into_read_write();
+        let reserve0_uref = storage::new_uref(U256::zero()).into_read_write();
+        let reserve1_uref = storage::new_uref(U256::zero()).into_read_write();
+        let locked_uref = storage::new_uref(false).into_read_write();
+        let klast_uref = storage::new_uref(U512::zero()).into_read_write();
+        let price0_cumulative_last_uref = storage::new_uref(U256::zero()).into_read_write();
+        let price1_cumulative_last_uref = storage::new_uref(U256::zero()).into_read_write();
+        let block_timestamp_last_uref = storage::new_uref(0u64).into_read_write();
+        let swap_fee_bps_uref = storage::new_uref(swap_fee_bps).into_read_write();
+
+        let name_key = {
+            let name_uref = storage::new_uref(name).into_read();
+            Key::from(name_uref)
+        };
+
+        let symbol_key = {
+            let symbol_uref = storage::new_uref(symbol).into_read();
+            Key::from(symbol_uref)
+        };
+
+        let decimals_key = {
+            let decimals_uref = storage::new_uref(decimals).into_read();
+            Key::from(decimals_uref)
+        };
+
+        let total_supply_key = Key::from(total_supply_uref);
+
+        let balances_dictionary_key = {
+            // Sets up initial balance for the caller - either an account, or a contract.
+            let caller = helpers::get_caller_address()?;
+            balances::write_balance_to(balances_uref, caller, initial_supply);
+
+            runtime::remove_key(consts::BALANCES_KEY_NAME);
+
+            Key::from(balances_uref)
+        };
+
+        let allowances_dictionary_key = {
+            runtime::remove_key(consts::ALLOWANCES_KEY_NAME);
+
+            Key::from(allowances_uref)
+        };
+
+        let token0_key = {
+            let token0_uref = storage::new_uref(token0).into_read();
+            Key::from(token0_uref)
+        };
+
+        let token1_key = {
+            let token1_uref = storage::new_uref(token1).into_read();
+            Key::from(token1_uref)
+        };
+
+        let factory_key = {
+            let factory_uref = storage::new_uref(factory).into_read();
+            Key::from(factory_uref)
+        };
+
+        let mut named_keys = NamedKeys::new();
+
+        named_keys.insert(String::from(consts::NAME_KEY_NAME), name_key);
+        named_keys.insert(String::from(consts::SYMBOL_KEY_NAME), symbol_key);
+        named_keys.insert(String::from(consts::DECIMALS_KEY_NAME), decimals_key);
+        named_keys.insert(String::from(consts::BALANCES_KEY_NAME), balances_dictionary_key);
+        named_keys.insert(String::from(consts::ALLOWANCES_KEY_NAME), allowances_dictionary_key);
+        named_keys.insert(String::from(consts::TOTAL_SUPPLY_KEY_NAME), total_supply_key);
+        named_keys.insert(String::from(consts::RESERVE0_KEY_NAME), Key::from(reserve0_uref));
+        named_keys.insert(String::from(consts::RESERVE1_KEY_NAME), Key::from(reserve1_uref));
+        named_keys.insert(String::from(consts::TOKEN0_KEY_NAME), token0_key);
+        named_keys.insert(String::from(consts::TOKEN1_KEY_NAME), token1_key);
+        named_keys.insert(String::from(consts::LOCKED_FLAG_KEY_NAME), Key::from(locked_uref));
+        named_keys.insert(String::from(consts::KLAST_KEY_NAME), Key::from(klast_uref));
+        named_keys.insert(String::from(consts::FACTORY_KEY_NAME), factory_key);
+        named_keys.insert(
+            String::from(consts::PRICE0_CUMULATIVE_LAST_KEY_NAME),
+            Key::from(price0_cumulative_last_uref),
+        );
+        named_keys.insert(
+            String::from(consts::PRICE1_CUMULATIVE_LAST_KEY_NAME),
+            Key::from(price1_cumulative_last_uref),
+        );
+        named_keys.insert(
+            String::from(consts::BLOCK_TIMESTAMP_LAST_KEY_NAME),
+            Key::from(block_timestamp_last_uref),
+        );
+        named_keys.insert(
+            String::from(consts::SWAP_FEE_BPS_KEY_NAME),
+            Key::from(swap_fee_bps_uref),
+        );
+        let ces_named_keys = ces::init::<PairEvent>();
+        let events_uref: URef = ces_named_keys
+            .get(ces::EVENTS_DICT)
+            .copied()
+            .and_then(Key::into_uref)
+            .unwrap_or_revert();
+        named_keys.extend(ces_named_keys);
+
+        let (contract_hash, _version) = storage::new_contract(
+            entry_points::default(),
+            Some(named_keys),
+            Some(String::from(contract_key_name)),
+            None,
+        );
+        let contract_package_hash: ContractPackageHash = runtime::get_key(contract_key_name)
+            .and_then(Key::into_hash)
+            .map(ContractPackageHash::new)
+            .unwrap_or_revert();
+
+        let mut contract_hash_key_name: String = String::from(contract_key_name);
+        contract_hash_key_name.push_str("_contract_hash");
+        // Hash of the installed contract will be reachable through named keys.
+        runtime::put_key(contract_hash_key_name.as_str(), Key::from(contract_hash));
+        Ok((contract_package_hash, contract_hash, events_uref))
+    }
+}
+
+/// Exports the pair entry points from the wasm module that links this crate.
+///
+/// `storage::new_contract` can only install entry points found in the calling module, so the
+/// router invokes this alongside the pair's own binary to be able to deploy pairs itself.
+#[macro_export]
+macro_rules! export_pair_entry_points {
+    () => {
+        $crate::export_pair_entry_points!(
+            name,
+            symbol,
+            decimals,
+            total_supply,
+            balance_of,
+            transfer,
+            approve,
+            allowance,
+            transfer_from,
+            get_reserves,
+            get_cumulative_prices,
+            get_swap_fee,
+            set_swap_fee,
+            mint,
+            burn,
+            swap,
+            skim,
+            sync,
+        );
+    };
+    ($($entry_point:ident),* $(,)?) => {
+        $(
+            #[no_mangle]
+            pub extern "C" fn $entry_point() {
+                $crate::$entry_point()
+            }
+        )*
+    };
+}
+
+// Entry point bodies, exported as `extern "C"` symbols by `export_pair_entry_points!`.
+
+pub fn name() {
+    let name = SwapperyPair::default().name();
+    runtime::ret(CLValue::from_t(name).unwrap_or_revert());
+}
+
+pub fn symbol() {
+    let symbol = SwapperyPair::default().symbol();
+    runtime::ret(CLValue::from_t(symbol).unwrap_or_revert());
+}
+
+pub fn decimals() {
+    let decimals = SwapperyPair::default().decimals();
+    runtime::ret(CLValue::from_t(decimals).unwrap_or_revert());
+}
+
+pub fn total_supply() {
+    let total_supply = SwapperyPair::default().total_supply();
+    runtime::ret(CLValue::from_t(total_supply).unwrap_or_revert());
+}
+
+pub fn balance_of() {
+    let address: Address = runtime::get_named_arg(consts::ADDRESS_RUNTIME_ARG_NAME);
+    let balance = SwapperyPair::default().balance_of(address);
+    runtime::ret(CLValue::from_t(balance).unwrap_or_revert());
+}
+
+pub fn transfer() {
+    let recipient: Address = runtime::get_named_arg(consts::RECIPIENT_RUNTIME_ARG_NAME);
+    let amount: U256 = runtime::get_named_arg(consts::AMOUNT_RUNTIME_ARG_NAME);
+
+    SwapperyPair::default()
+        .transfer(recipient, amount)
+        .unwrap_or_revert();
+}
+
+pub fn approve() {
+    let spender: Address = runtime::get_named_arg(consts::SPENDER_RUNTIME_ARG_NAME);
+    let amount: U256 = runtime::get_named_arg(consts::AMOUNT_RUNTIME_ARG_NAME);
+
+    SwapperyPair::default()
+        .approve(spender, amount)
+        .unwrap_or_revert();
+}
+
+pub fn allowance() {
+    let owner: Address = runtime::get_named_arg(consts::OWNER_RUNTIME_ARG_NAME);
+    let spender: Address = runtime::get_named_arg(consts::SPENDER_RUNTIME_ARG_NAME);
+    let val = SwapperyPair::default().allowance(owner, spender);
+    runtime::ret(CLValue::from_t(val).unwrap_or_revert());
+}
+
+pub fn transfer_from() {
+    let owner: Address = runtime::get_named_arg(consts::OWNER_RUNTIME_ARG_NAME);
+    let recipient: Address = runtime::get_named_arg(consts::RECIPIENT_RUNTIME_ARG_NAME);
+    let amount: U256 = runtime::get_named_arg(consts::AMOUNT_RUNTIME_ARG_NAME);
+    SwapperyPair::default()
+        .transfer_from(owner, recipient, amount)
+        .unwrap_or_revert();
+}
+
+pub fn get_reserves() {
+    let reserves: (U256, U256) = (
+        SwapperyPair::default().reserve0(),
+        SwapperyPair::default().reserve1(),
+    );
+    runtime::ret(CLValue::from_t(reserves).unwrap_or_revert());
+}
+
+pub fn get_cumulative_prices() {
+    let cumulative_prices: (U256, U256, u64) = (
+        SwapperyPair::default().price0_cumulative_last(),
+        SwapperyPair::default().price1_cumulative_last(),
+        SwapperyPair::default().block_timestamp_last(),
+    );
+    runtime::ret(CLValue::from_t(cumulative_prices).unwrap_or_revert());
+}
+
+pub fn mint() {
+    let locked = SwapperyPair::default().locked();
+    if locked {
+        runtime::revert(Error::Locked);
+    }
+    SwapperyPair::default().write_locked(true);
+
+    let to: Address = runtime::get_named_arg(consts::TO_RUNTIME_ARG_NAME);
+    let fee_to: Address = SwapperyPair::default().feeto();
+
+    let _reserve0: U256 = SwapperyPair::default().reserve0();
+    let _reserve1: U256 = SwapperyPair::default().reserve1();
+
+    let token0: ContractHash = SwapperyPair::default().token0();
+    let token1: ContractHash = SwapperyPair::default().token1();
+
+    let self_addr = helpers::get_self_address().unwrap_or_revert();
+
+    let balance0: U256 = runtime::call_contract(
+        token0,
+        consts::BALANCE_OF_ENTRY_POINT_NAME,
+        runtime_args! {
+            consts::ADDRESS_RUNTIME_ARG_NAME => self_addr
+        },
+    );
+    let balance1: U256 = runtime::call_contract(
+        token1,
+        consts::BALANCE_OF_ENTRY_POINT_NAME,
+        runtime_args! {
+            consts::ADDRESS_RUNTIME_ARG_NAME => self_addr
+        },
+    );
+    // Balances fall below the reserves only if a token took from the pair, which deposits nothing.
+    let amount0: U256 = balance0
+        .checked_sub(_reserve0)
+        .unwrap_or_revert_with(Error::InsufficientLiquidityMinted);
+    let amount1: U256 = balance1
+        .checked_sub(_reserve1)
+        .unwrap_or_revert_with(Error::InsufficientLiquidityMinted);
+
+    let fee_on: bool = SwapperyPair::default()._mint_fee(_reserve0, _reserve1, fee_to);
+    let _total_supply: U256 = SwapperyPair::default().total_supply();
+    let liquidity: U256 =
+        math::mint_liquidity(amount0, amount1, _reserve0, _reserve1, _total_supply)
+            .map_err(Error::from)
+            .unwrap_or_revert();
+    if _total_supply.is_zero() {
+        SwapperyPair::default()
+            .mint(
+                Address::from(AccountHash::new([0u8; 32])),
+                U256::from(consts::MINIMUM_LIQUIDITY),
+            )
+            .unwrap_or_revert();
+    }
+    if !(liquidity > U256::zero()) {
+        runtime::revert(Error::InsufficientLiquidityMinted);
+    }
+    SwapperyPair::default()
+        .mint(to, liquidity)
+        .unwrap_or_revert();
+
+    SwapperyPair::default()._update(balance0, balance1);
+    if fee_on {
+        SwapperyPair::default().write_klast(math::mul(
+            SwapperyPair::default().reserve0(),
+            SwapperyPair::default().reserve1(),
+        ));
+    }
+
+    helpers::emit(&PairEvent::Mint {
+        sender: helpers::get_immediate_caller_address().unwrap_or_revert(),
+        amount0,
+        amount1,
+        to,
+        liquidity,
+    });
+
+    SwapperyPair::default().write_locked(false);
+
+    runtime::ret(CLValue::from_t(liquidity).unwrap_or_revert());
+}
+
+pub fn burn() {
+    let locked = SwapperyPair::default().locked();
+    if locked {
+        runtime::revert(Error::Locked);
+    }
+    SwapperyPair::default().write_locked(true);
+
+    let to: Address = runtime::get_named_arg(consts::TO_RUNTIME_ARG_NAME);
+    let fee_to: Address = SwapperyPair::default().feeto();
+
+    let _reserve0: U256 = SwapperyPair::default().reserve0();
+    let _reserve1: U256 = SwapperyPair::default().reserve1();
+
+    let token0: ContractHash = SwapperyPair::default().token0();
+    let token1: ContractHash = SwapperyPair::default().token1();
+
+    let self_addr = helpers::get_self_address().unwrap_or_revert();
+
+    let mut balance0: U256 = runtime::call_contract(
+        token0,
+        consts::BALANCE_OF_ENTRY_POINT_NAME,
+        runtime_args! {
+            consts::ADDRESS_RUNTIME_ARG_NAME => self_addr
+        },
+    );
+    let mut balance1: U256 = runtime::call_contract(
+        token1,
+        consts::BALANCE_OF_ENTRY_POINT_NAME,
+        runtime_args! {
+            consts::ADDRESS_RUNTIME_ARG_NAME => self_addr
+        },
+    );
+    let liquidity: U256 = SwapperyPair::default().balance_of(self_addr);
+
+    let fee_on: bool = SwapperyPair::default()._mint_fee(_reserve0, _reserve1, fee_to);
+    let _total_supply: U256 = SwapperyPair::default().total_supply();
+    let (amount0, amount1) = math::burn_amounts(liquidity, balance0, balance1, _total_supply)
+        .map_err(Error::from)
+        .unwrap_or_revert();
+    if !(amount0 > U256::zero() && amount1 > U256::zero()) {
+        runtime::revert(Error::InsufficientLiquidityBurned);
+    }
+
+    SwapperyPair::default()
+        .burn(self_addr, liquidity)
+        .unwrap_or_revert();
+    runtime::call_contract::<()>(
+        token0,
+        consts::TRANSFER_ENTRY_POINT_NAME,
+        runtime_args! {
+            consts::RECIPIENT_RUNTIME_ARG_NAME => to,
+            consts::AMOUNT_RUNTIME_ARG_NAME => amount0
+        },
+    );
+    runtime::call_contract::<()>(
+        token1,
+        consts::TRANSFER_ENTRY_POINT_NAME,
+        runtime_args! {
+            consts::RECIPIENT_RUNTIME_ARG_NAME => to,
+            consts::AMOUNT_RUNTIME_ARG_NAME => amount1
+        },
+    );
+
+    balance0 = runtime::call_contract(
+        token0,
+        consts::BALANCE_OF_ENTRY_POINT_NAME,
+        runtime_args! {
+            consts::ADDRESS_RUNTIME_ARG_NAME => self_addr
+        },
+    );
+    balance1 = runtime::call_contract(
+        token1,
+        consts::BALANCE_OF_ENTRY_POINT_NAME,
+        runtime_args! {
+            consts::ADDRESS_RUNTIME_ARG_NAME => self_addr
+        },
+    );
+
+    SwapperyPair::default()._update(balance0, balance1);
+    if fee_on {
+        SwapperyPair::default().write_klast(math::mul(
+            SwapperyPair::default().reserve0(),
+            SwapperyPair::default().reserve1(),
+        ));
+    }
+
+    helpers::emit(&PairEvent::Burn {
+        sender: helpers::get_immediate_caller_address().unwrap_or_revert(),
+        amount0,
+        amount1,
+        to,
+        liquidity,
+    });
+
+    SwapperyPair::default().write_locked(false);
+
+    runtime::ret(CLValue::from_t((amount0, amount1)).unwrap_or_revert());
+}
+
+pub fn swap() {
+    let locked = SwapperyPair::default().locked();
+    if locked {
+        runtime::revert(Error::Locked);
+    }
+    SwapperyPair::default().write_locked(true);
+
+    let amount0_out: U256 = runtime::get_named_arg(consts::AMOUNT0_RUNTIME_ARG_NAME);
+    let amount1_out: U256 = runtime::get_named_arg(consts::AMOUNT1_RUNTIME_ARG_NAME);
+    let to: Address = runtime::get_named_arg(consts::TO_RUNTIME_ARG_NAME);
+    let data: Bytes =
+        helpers::get_optional_named_arg(consts::DATA_RUNTIME_ARG_NAME).unwrap_or_default();
+
+    if !(amount0_out > U256::zero() || amount1_out > U256::zero()) {
+        runtime::revert(Error::InsufficientOutputAmount);
+    }
+
+    let _reserve0: U256 = SwapperyPair::default().reserve0();
+    let _reserve1: U256 = SwapperyPair::default().reserve1();
+
+    if !(amount0_out < _reserve0 && amount1_out < _reserve1) {
+        runtime::revert(Error::InsufficientLiquidity);
+    }
+
+    let balance0: U256;
+    let balance1: U256;
+
+    let self_addr = helpers::get_self_address().unwrap_or_revert();
+
+    let token0: ContractHash = SwapperyPair::default().token0();
+    let token1: ContractHash = SwapperyPair::default().token1();
+
+    if amount0_out > U256::zero() {
+        runtime::call_contract::<()>(
+            token0,
+            consts::TRANSFER_ENTRY_POINT_NAME,
+            runtime_args! {
+                consts::RECIPIENT_RUNTIME_ARG_NAME => to,
+                consts::AMOUNT_RUNTIME_ARG_NAME => amount0_out
+            },
+        );
+    }
+    if amount1_out > U256::zero() {
+        runtime::call_contract::<()>(
+            token1,
+            consts::TRANSFER_ENTRY_POINT_NAME,
+            runtime_args! {
+                consts::RECIPIENT_RUNTIME_ARG_NAME => to,
+                consts::AMOUNT_RUNTIME_ARG_NAME => amount1_out
+            },
+        );
+    }
+
+    if !data.is_empty() {
+        // Flash swap: the receiver gets the outputs first and must pay for them before the
+        // balance and K checks below.
+        let receiver = *to
+            .as_contract_package_hash()
+            .unwrap_or_revert_with(Error::InvalidTo);
+        let sender = helpers::get_immediate_caller_address().unwrap_or_revert();
+        runtime::call_versioned_contract::<()>(
+            receiver,
+            None,
+            consts::SWAPPERY_CALL_ENTRY_POINT_NAME,
+            runtime_args! {
+                consts::SENDER_RUNTIME_ARG_NAME => sender,
+                consts::AMOUNT0_RUNTIME_ARG_NAME => amount0_out,
+                consts::AMOUNT1_RUNTIME_ARG_NAME => amount1_out,
+                consts::DATA_RUNTIME_ARG_NAME => data
+            },
+        );
+    }
+
+    balance0 = runtime::call_contract(
+        token0,
+        consts::BALANCE_OF_ENTRY_POINT_NAME,
+        runtime_args! {
+            consts::ADDRESS_RUNTIME_ARG_NAME => self_addr
+        },
+    );
+    balance1 = runtime::call_contract(
+        token1,
+        consts::BALANCE_OF_ENTRY_POINT_NAME,
+        runtime_args! {
+            consts::ADDRESS_RUNTIME_ARG_NAME => self_addr
+        },
+    );
+
+    let amount0_in: U256 = math::amount_in(balance0, _reserve0, amount0_out)
+        .map_err(Error::from)
+        .unwrap_or_revert();
+    let amount1_in: U256 = math::amount_in(balance1, _reserve1, amount1_out)
+        .map_err(Error::from)
+        .unwrap_or_revert();
+
+    if !(amount0_in > U256::zero() || amount1_in > U256::zero()) {
+        runtime::revert(Error::InsufficientInputAmount);
+    }
+
+    let k_holds: bool = math::k_holds(
+        balance0,
+        balance1,
+        amount0_in,
+        amount1_in,
+        _reserve0,
+        _reserve1,
+        SwapperyPair::default().swap_fee_bps(),
+    )
+    .map_err(Error::from)
+    .unwrap_or_revert();
+    if !k_holds {
+        runtime::revert(Error::K);
+    }
+
+    SwapperyPair::default()._update(balance0, balance1);
+    helpers::emit(&PairEvent::Swap {
+        sender: helpers::get_immediate_caller_address().unwrap_or_revert(),
+        amount0_in,
+        amount1_in,
+        amount0_out,
+        amount1_out,
+        to,
+    });
+
+    SwapperyPair::default().write_locked(false);
+}
+
+pub fn get_swap_fee() {
+    let swap_fee_bps: u32 = SwapperyPair::default().swap_fee_bps();
+    runtime::ret(CLValue::from_t(swap_fee_bps).unwrap_or_revert());
+}
+
+/// Changes the swap fee, up to `MAX_SWAP_FEE_BPS`. Only the factory may call it.
+pub fn set_swap_fee() {
+    let swap_fee_bps: u32 = runtime::get_named_arg(consts::SWAP_FEE_BPS_RUNTIME_ARG_NAME);
+    let caller: Address = helpers::get_immediate_caller_address().unwrap_or_revert();
+    if caller != SwapperyPair::default().factory() {
+        runtime::revert(Error::Forbidden);
+    }
+    if swap_fee_bps > consts::MAX_SWAP_FEE_BPS {
+        runtime::revert(Error::InvalidSwapFee);
+    }
+    SwapperyPair::default().write_swap_fee_bps(swap_fee_bps);
+    helpers::emit(&PairEvent::SetSwapFee { swap_fee_bps });
+}
+
+/// Sends the tokens held by the pair above its reserves to `to`.
+pub fn skim() {
+    let locked = SwapperyPair::default().locked();
+    if locked {
+        runtime::revert(Error::Locked);
+    }
+    SwapperyPair::default().write_locked(true);
+
+    let to: Address = runtime::get_named_arg(consts::TO_RUNTIME_ARG_NAME);
+
+    let token0: ContractHash = SwapperyPair::default().token0();
+    let token1: ContractHash = SwapperyPair::default().token1();
+
+    let self_addr = helpers::get_self_address().unwrap_or_revert();
+
+    let balance0: U256 = runtime::call_contract(
+        token0,
+        consts::BALANCE_OF_ENTRY_POINT_NAME,
+        runtime_args! {
+            consts::ADDRESS_RUNTIME_ARG_NAME => self_addr
+        },
+    );
+    let balance1: U256 = runtime::call_contract(
+        token1,
+        consts::BALANCE_OF_ENTRY_POINT_NAME,
+        runtime_args! {
+            consts::ADDRESS_RUNTIME_ARG_NAME => self_addr
+        },
+    );
+
+    let excess0: U256 = balance0.saturating_sub(SwapperyPair::default().reserve0());
+    let excess1: U256 = balance1.saturating_sub(SwapperyPair::default().reserve1());
+
+    if excess0 > U256::zero() {
+        runtime::call_contract::<()>(
+            token0,
+            consts::TRANSFER_ENTRY_POINT_NAME,
+            runtime_args! {
+                consts::RECIPIENT_RUNTIME_ARG_NAME => to,
+                consts::AMOUNT_RUNTIME_ARG_NAME => excess0
+            },
+        );
+    }
+    if excess1 > U256::zero() {
+        runtime::call_contract::<()>(
+            token1,
+            consts::TRANSFER_ENTRY_POINT_NAME,
+            runtime_args! {
+                consts::RECIPIENT_RUNTIME_ARG_NAME => to,
+                consts::AMOUNT_RUNTIME_ARG_NAME => excess1
+            },
+        );
+    }
+
+    SwapperyPair::default().write_locked(false);
+}
+
+/// Forces the reserves to match the token balances held by the pair.
+pub fn sync() {
+    let locked = SwapperyPair::default().locked();
+    if locked {
+        runtime::revert(Error::Locked);
+    }
+    SwapperyPair::default().write_locked(true);
+
+    let token0: ContractHash = SwapperyPair::default().token0();
+    let token1: ContractHash = SwapperyPair::default().token1();
+
+    let self_addr = helpers::get_self_address().unwrap_or_revert();
+
+    let balance0: U256 = runtime::call_contract(
+        token0,
+        consts::BALANCE_OF_ENTRY_POINT_NAME,
+        runtime_args! {
+            consts::ADDRESS_RUNTIME_ARG_NAME => self_addr
+        },
+    );
+    let balance1: U256 = runtime::call_contract(
+        token1,
+        consts::BALANCE_OF_ENTRY_POINT_NAME,
+        runtime_args! {
+            consts::ADDRESS_RUNTIME_ARG_NAME => self_addr
+        },
+    );
+
+    SwapperyPair::default()._update(balance0, balance1);
+
+    SwapperyPair::default().write_locked(false);
+}
@@ -1,11 +1,12 @@
 //! Implementation of variables.
 
 use casper_contract::{contract_api::storage, unwrap_or_revert::UnwrapOrRevert};
-use casper_types::{URef, U256};
+use casper_types::{URef, U256, U512};
 
 use crate::{constants::{
     RESERVE0_KEY_NAME, RESERVE1_KEY_NAME, LOCKED_FLAG_KEY_NAME,
-    KLAST_KEY_NAME, TOTAL_SUPPLY_KEY_NAME
+    KLAST_KEY_NAME, TOTAL_SUPPLY_KEY_NAME, PRICE0_CUMULATIVE_LAST_KEY_NAME,
+    PRICE1_CUMULATIVE_LAST_KEY_NAME, BLOCK_TIMESTAMP_LAST_KEY_NAME, SWAP_FEE_BPS_KEY_NAME,
 }, helpers};
 
 #[inline]
@@ -33,6 +34,26 @@ pub(crate) fn klast_uref() -> URef {
     helpers::get_uref(KLAST_KEY_NAME)
 }
 
+#[inline]
+pub(crate) fn price0_cumulative_last_uref() -> URef {
+    helpers::get_uref(PRICE0_CUMULATIVE_LAST_KEY_NAME)
+}
+
+#[inline]
+pub(crate) fn price1_cumulative_last_uref() -> URef {
+    helpers::get_uref(PRICE1_CUMULATIVE_LAST_KEY_NAME)
+}
+
+#[inline]
+pub(crate) fn block_timestamp_last_uref() -> URef {
+    helpers::get_uref(BLOCK_TIMESTAMP_LAST_KEY_NAME)
+}
+
+#[inline]
+pub(crate) fn swap_fee_bps_uref() -> URef {
+    helpers::get_uref(SWAP_FEE_BPS_KEY_NAME)
+}
+
 pub(crate) fn read_total_supply_from(uref: URef) -> U256 {
     storage::read(uref).unwrap_or_revert().unwrap_or_revert()
 }
@@ -57,10 +78,34 @@ pub(crate) fn write_locked_to(uref: URef, value: bool) {
     storage::write(uref, value);
 }
 
-pub(crate) fn read_klast_from(uref: URef) -> U256 {
+pub(crate) fn read_klast_from(uref: URef) -> U512 {
     storage::read(uref).unwrap_or_revert().unwrap_or_revert()
 }
 
-pub(crate) fn write_klast_to(uref: URef, value: U256) {
+pub(crate) fn write_klast_to(uref: URef, value: U512) {
     storage::write(uref, value);
-}
\ No newline at end of file
+}
+
+pub(crate) fn read_price_cumulative_from(uref: URef) -> U256 {
+    storage::read(uref).unwrap_or_revert().unwrap_or_revert()
+}
+
+pub(crate) fn write_price_cumulative_to(uref: URef, value: U256) {
+    storage::write(uref, value);
+}
+
+pub(crate) fn read_block_timestamp_from(uref: URef) -> u64 {
+    storage::read(uref).unwrap_or_revert().unwrap_or_revert()
+}
+
+pub(crate) fn write_block_timestamp_to(uref: URef, value: u64) {
+    storage::write(uref, value);
+}
+
+pub(crate) fn read_swap_fee_bps_from(uref: URef) -> u32 {
+    storage::read(uref).unwrap_or_revert().unwrap_or_revert()
+}
+
+pub(crate) fn write_swap_fee_bps_to(uref: URef, value: u32) {
+    storage::write(uref, value);
+}
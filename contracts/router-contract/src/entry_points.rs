@@ -3,19 +3,18 @@ use alloc::{boxed::Box, string::String, vec, vec::Vec};
 use casper_erc20::Address;
 
 use casper_types::{
-    account::AccountHash, CLType, CLTyped, ContractHash, EntryPoint, EntryPointAccess,
-    EntryPointType, EntryPoints, Key, Parameter, U256,
+    CLType, CLTyped, ContractHash, EntryPoint, EntryPointAccess, EntryPointType, EntryPoints, Key,
+    Parameter, URef, U256,
 };
 
 use crate::constants as consts;
 
 pub fn create_pair() -> EntryPoint {
     EntryPoint::new(
-        String::from(consts::CREATE_PAIR_ENTRY_POINT),
+        String::from(consts::CREATE_PAIR_ENTRY_POINT_NAME),
         vec![
             Parameter::new(consts::TOKEN0_RUNTIME_ARG_NAME, Key::cl_type()),
             Parameter::new(consts::TOKEN1_RUNTIME_ARG_NAME, Key::cl_type()),
-            Parameter::new(consts::PAIR_RUNTIME_ARG_NAME, Key::cl_type()),
         ],
         CLType::Unit,
         EntryPointAccess::Public,
@@ -25,7 +24,7 @@ pub fn create_pair() -> EntryPoint {
 
 pub fn get_pair() -> EntryPoint {
     EntryPoint::new(
-        String::from(consts::GET_PAIR_ENTRY_POINT),
+        String::from(consts::GET_PAIR_ENTRY_POINT_NAME),
         vec![
             Parameter::new(consts::TOKEN0_RUNTIME_ARG_NAME, Key::cl_type()),
             Parameter::new(consts::TOKEN1_RUNTIME_ARG_NAME, Key::cl_type()),
@@ -36,12 +35,97 @@ pub fn get_pair() -> EntryPoint {
     )
 }
 
+pub fn all_pairs_length() -> EntryPoint {
+    EntryPoint::new(
+        String::from(consts::ALL_PAIRS_LENGTH_ENTRY_POINT_NAME),
+        vec![],
+        u64::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    )
+}
+
+pub fn pair_at() -> EntryPoint {
+    EntryPoint::new(
+        String::from(consts::PAIR_AT_ENTRY_POINT_NAME),
+        vec![Parameter::new(consts::INDEX_RUNTIME_ARG_NAME, u64::cl_type())],
+        Address::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    )
+}
+
+pub fn pairs_for_token() -> EntryPoint {
+    EntryPoint::new(
+        String::from(consts::PAIRS_FOR_TOKEN_ENTRY_POINT_NAME),
+        vec![
+            Parameter::new(consts::TOKEN_RUNTIME_ARG_NAME, Key::cl_type()),
+            Parameter::new(consts::OFFSET_RUNTIME_ARG_NAME, u64::cl_type()),
+            Parameter::new(consts::LIMIT_RUNTIME_ARG_NAME, u64::cl_type()),
+        ],
+        CLType::List(Box::new(Address::cl_type())),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    )
+}
+
+pub fn get_base_tokens() -> EntryPoint {
+    EntryPoint::new(
+        String::from(consts::GET_BASE_TOKENS_ENTRY_POINT_NAME),
+        vec![],
+        CLType::List(Box::new(ContractHash::cl_type())),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    )
+}
+
+pub fn set_base_tokens() -> EntryPoint {
+    EntryPoint::new(
+        String::from(consts::SET_BASE_TOKENS_ENTRY_POINT_NAME),
+        vec![Parameter::new(
+            consts::BASE_TOKENS_RUNTIME_ARG_NAME,
+            CLType::List(Box::new(CLType::Key)),
+        )],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    )
+}
+
+pub fn get_best_amount_out() -> EntryPoint {
+    EntryPoint::new(
+        String::from(consts::GET_BEST_AMOUNT_OUT_ENTRY_POINT_NAME),
+        vec![
+            Parameter::new(consts::TOKEN_IN_RUNTIME_ARG_NAME, Key::cl_type()),
+            Parameter::new(consts::TOKEN_OUT_RUNTIME_ARG_NAME, Key::cl_type()),
+            Parameter::new(consts::AMOUNT_IN_RUNTIME_ARG_NAME, U256::cl_type()),
+            Parameter::new(
+                consts::INTERMEDIATES_RUNTIME_ARG_NAME,
+                CLType::List(Box::new(CLType::Key)),
+            ),
+        ],
+        <(U256, Vec<Key>)>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    )
+}
+
+pub fn get_feeto() -> EntryPoint {
+    EntryPoint::new(
+        String::from(consts::GET_FEETO_ENTRY_POINT_NAME),
+        vec![],
+        Address::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    )
+}
+
 pub fn set_feeto() -> EntryPoint {
     EntryPoint::new(
-        String::from(consts::SET_FEETO_ENTRY_POINT),
+        String::from(consts::SET_FEETO_ENTRY_POINT_NAME),
         vec![Parameter::new(
-            consts::FEETO_KEY_NAME,
-            AccountHash::cl_type(),
+            consts::FEETO_RUNTIME_ARG_NAME,
+            Key::cl_type(),
         )],
         CLType::Unit,
         EntryPointAccess::Public,
@@ -51,10 +135,10 @@ pub fn set_feeto() -> EntryPoint {
 
 pub fn set_feeto_setter() -> EntryPoint {
     EntryPoint::new(
-        String::from(consts::SET_FEETO_SETTER_ENTRY_POINT),
+        String::from(consts::SET_FEETO_SETTER_ENTRY_POINT_NAME),
         vec![Parameter::new(
-            consts::FEETO_SETTER_KEY_NAME,
-            AccountHash::cl_type(),
+            consts::FEETO_SETTER_RUNTIME_ARG_NAME,
+            Key::cl_type(),
         )],
         CLType::Unit,
         EntryPointAccess::Public,
@@ -62,6 +146,89 @@ pub fn set_feeto_setter() -> EntryPoint {
     )
 }
 
+pub fn get_protocol_fee_share() -> EntryPoint {
+    EntryPoint::new(
+        String::from(consts::GET_PROTOCOL_FEE_SHARE_ENTRY_POINT_NAME),
+        vec![],
+        u32::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    )
+}
+
+pub fn set_protocol_fee_share() -> EntryPoint {
+    EntryPoint::new(
+        String::from(consts::SET_PROTOCOL_FEE_SHARE_ENTRY_POINT_NAME),
+        vec![Parameter::new(
+            consts::PROTOCOL_FEE_SHARE_BPS_RUNTIME_ARG_NAME,
+            u32::cl_type(),
+        )],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    )
+}
+
+pub fn set_pair_swap_fee() -> EntryPoint {
+    EntryPoint::new(
+        String::from(consts::SET_PAIR_SWAP_FEE_ENTRY_POINT_NAME),
+        vec![
+            Parameter::new(consts::TOKEN0_RUNTIME_ARG_NAME, Key::cl_type()),
+            Parameter::new(consts::TOKEN1_RUNTIME_ARG_NAME, Key::cl_type()),
+            Parameter::new(consts::SWAP_FEE_BPS_RUNTIME_ARG_NAME, u32::cl_type()),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    )
+}
+
+pub fn get_amounts_out() -> EntryPoint {
+    EntryPoint::new(
+        String::from(consts::GET_AMOUNTS_OUT_ENTRY_POINT_NAME),
+        vec![
+            Parameter::new(consts::AMOUNT_IN_RUNTIME_ARG_NAME, U256::cl_type()),
+            Parameter::new(
+                consts::PATH_RUNTIME_ARG_NAME,
+                CLType::List(Box::new(CLType::Key)),
+            ),
+        ],
+        CLType::List(Box::new(U256::cl_type())),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    )
+}
+
+pub fn get_amounts_in() -> EntryPoint {
+    EntryPoint::new(
+        String::from(consts::GET_AMOUNTS_IN_ENTRY_POINT_NAME),
+        vec![
+            Parameter::new(consts::AMOUNT_OUT_RUNTIME_ARG_NAME, U256::cl_type()),
+            Parameter::new(
+                consts::PATH_RUNTIME_ARG_NAME,
+                CLType::List(Box::new(CLType::Key)),
+            ),
+        ],
+        CLType::List(Box::new(U256::cl_type())),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    )
+}
+
+pub fn quote() -> EntryPoint {
+    EntryPoint::new(
+        String::from(consts::QUOTE_ENTRY_POINT_NAME),
+        vec![
+            Parameter::new(consts::AMOUNT_A_RUNTIME_ARG_NAME, U256::cl_type()),
+            Parameter::new(consts::RESERVE_A_RUNTIME_ARG_NAME, U256::cl_type()),
+            Parameter::new(consts::RESERVE_B_RUNTIME_ARG_NAME, U256::cl_type()),
+        ],
+        U256::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    )
+}
+
 pub fn add_liquidity() -> EntryPoint {
     EntryPoint::new(
         String::from(consts::ADD_LIQUIDITY_ENTRY_POINT_NAME),
@@ -72,7 +239,7 @@ pub fn add_liquidity() -> EntryPoint {
             Parameter::new(consts::AMOUNT1_DESIRED_RUNTIME_ARG_NAME, U256::cl_type()),
             Parameter::new(consts::AMOUNT0_MIN_RUNTIME_ARG_NAME, U256::cl_type()),
             Parameter::new(consts::AMOUNT1_MIN_RUNTIME_ARG_NAME, U256::cl_type()),
-            Parameter::new(consts::TO_RUNTIME_ARG_NAME, AccountHash::cl_type()),
+            Parameter::new(consts::TO_RUNTIME_ARG_NAME, Key::cl_type()),
             Parameter::new(consts::DEAD_LINE_RUNTIME_ARG_NAME, U256::cl_type()),
         ],
         CLType::Unit,
@@ -90,7 +257,78 @@ pub fn remove_liquidity() -> EntryPoint {
             Parameter::new(consts::LIQUIDITY_RUNTIME_ARG_NAME, U256::cl_type()),
             Parameter::new(consts::AMOUNT0_MIN_RUNTIME_ARG_NAME, U256::cl_type()),
             Parameter::new(consts::AMOUNT1_MIN_RUNTIME_ARG_NAME, U256::cl_type()),
-            Parameter::new(consts::TO_RUNTIME_ARG_NAME, AccountHash::cl_type()),
+            Parameter::new(consts::TO_RUNTIME_ARG_NAME, Key::cl_type()),
+            Parameter::new(consts::DEAD_LINE_RUNTIME_ARG_NAME, U256::cl_type()),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    )
+}
+
+pub fn add_liquidity_cspr() -> EntryPoint {
+    EntryPoint::new(
+        String::from(consts::ADD_LIQUIDITY_CSPR_ENTRY_POINT_NAME),
+        vec![
+            Parameter::new(consts::TOKEN_RUNTIME_ARG_NAME, Key::cl_type()),
+            Parameter::new(consts::AMOUNT_TOKEN_DESIRED_RUNTIME_ARG_NAME, U256::cl_type()),
+            Parameter::new(consts::AMOUNT_CSPR_DESIRED_RUNTIME_ARG_NAME, U256::cl_type()),
+            Parameter::new(consts::AMOUNT_TOKEN_MIN_RUNTIME_ARG_NAME, U256::cl_type()),
+            Parameter::new(consts::AMOUNT_CSPR_MIN_RUNTIME_ARG_NAME, U256::cl_type()),
+            Parameter::new(consts::TO_RUNTIME_ARG_NAME, Key::cl_type()),
+            Parameter::new(consts::DEAD_LINE_RUNTIME_ARG_NAME, U256::cl_type()),
+            Parameter::new(consts::PURSE_RUNTIME_ARG_NAME, URef::cl_type()),
+        ],
+        U256::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    )
+}
+
+pub fn remove_liquidity_cspr() -> EntryPoint {
+    EntryPoint::new(
+        String::from(consts::REMOVE_LIQUIDITY_CSPR_ENTRY_POINT_NAME),
+        vec![
+            Parameter::new(consts::TOKEN_RUNTIME_ARG_NAME, Key::cl_type()),
+            Parameter::new(consts::LIQUIDITY_RUNTIME_ARG_NAME, U256::cl_type()),
+            Parameter::new(consts::AMOUNT_TOKEN_MIN_RUNTIME_ARG_NAME, U256::cl_type()),
+            Parameter::new(consts::AMOUNT_CSPR_MIN_RUNTIME_ARG_NAME, U256::cl_type()),
+            Parameter::new(consts::TO_RUNTIME_ARG_NAME, Key::cl_type()),
+            Parameter::new(consts::DEAD_LINE_RUNTIME_ARG_NAME, U256::cl_type()),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    )
+}
+
+pub fn remove_liquidity_supporting_fee() -> EntryPoint {
+    EntryPoint::new(
+        String::from(consts::REMOVE_LIQUIDITY_SUPPORTING_FEE_ENTRY_POINT_NAME),
+        vec![
+            Parameter::new(consts::TOKEN0_RUNTIME_ARG_NAME, Key::cl_type()),
+            Parameter::new(consts::TOKEN1_RUNTIME_ARG_NAME, Key::cl_type()),
+            Parameter::new(consts::LIQUIDITY_RUNTIME_ARG_NAME, U256::cl_type()),
+            Parameter::new(consts::AMOUNT0_MIN_RUNTIME_ARG_NAME, U256::cl_type()),
+            Parameter::new(consts::AMOUNT1_MIN_RUNTIME_ARG_NAME, U256::cl_type()),
+            Parameter::new(consts::TO_RUNTIME_ARG_NAME, Key::cl_type()),
+            Parameter::new(consts::DEAD_LINE_RUNTIME_ARG_NAME, U256::cl_type()),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    )
+}
+
+pub fn remove_liquidity_cspr_supporting_fee() -> EntryPoint {
+    EntryPoint::new(
+        String::from(consts::REMOVE_LIQUIDITY_CSPR_SUPPORTING_FEE_ENTRY_POINT_NAME),
+        vec![
+            Parameter::new(consts::TOKEN_RUNTIME_ARG_NAME, Key::cl_type()),
+            Parameter::new(consts::LIQUIDITY_RUNTIME_ARG_NAME, U256::cl_type()),
+            Parameter::new(consts::AMOUNT_TOKEN_MIN_RUNTIME_ARG_NAME, U256::cl_type()),
+            Parameter::new(consts::AMOUNT_CSPR_MIN_RUNTIME_ARG_NAME, U256::cl_type()),
+            Parameter::new(consts::TO_RUNTIME_ARG_NAME, Key::cl_type()),
             Parameter::new(consts::DEAD_LINE_RUNTIME_ARG_NAME, U256::cl_type()),
         ],
         CLType::Unit,
@@ -109,7 +347,7 @@ pub fn swap_exact_tokens_for_tokens() -> EntryPoint {
                 consts::PATH_RUNTIME_ARG_NAME,
                 CLType::List(Box::new(CLType::Key)),
             ),
-            Parameter::new(consts::TO_RUNTIME_ARG_NAME, AccountHash::cl_type()),
+            Parameter::new(consts::TO_RUNTIME_ARG_NAME, Key::cl_type()),
             Parameter::new(consts::DEAD_LINE_RUNTIME_ARG_NAME, U256::cl_type()),
         ],
         CLType::Unit,
@@ -128,7 +366,43 @@ pub fn swap_exact_tokens_for_tokens_supporting_fee() -> EntryPoint {
                 consts::PATH_RUNTIME_ARG_NAME,
                 CLType::List(Box::new(CLType::Key)),
             ),
-            Parameter::new(consts::TO_RUNTIME_ARG_NAME, AccountHash::cl_type()),
+            Parameter::new(consts::TO_RUNTIME_ARG_NAME, Key::cl_type()),
+            Parameter::new(consts::DEAD_LINE_RUNTIME_ARG_NAME, U256::cl_type()),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    )
+}
+
+pub fn swap_exact_tokens_for_tokens_split() -> EntryPoint {
+    EntryPoint::new(
+        String::from(consts::SWAP_EXACT_TOKENS_FOR_TOKENS_SPLIT_ENTRY_POINT_NAME),
+        vec![
+            Parameter::new(consts::LEGS_RUNTIME_ARG_NAME, <Vec<(Vec<Key>, U256)>>::cl_type()),
+            Parameter::new(consts::AMOUNT_OUT_MIN_RUNTIME_ARG_NAME, U256::cl_type()),
+            Parameter::new(consts::TO_RUNTIME_ARG_NAME, Key::cl_type()),
+            Parameter::new(consts::DEAD_LINE_RUNTIME_ARG_NAME, U256::cl_type()),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    )
+}
+
+pub fn swap_exact_tokens_for_tokens_best() -> EntryPoint {
+    EntryPoint::new(
+        String::from(consts::SWAP_EXACT_TOKENS_FOR_TOKENS_BEST_ENTRY_POINT_NAME),
+        vec![
+            Parameter::new(consts::TOKEN_IN_RUNTIME_ARG_NAME, Key::cl_type()),
+            Parameter::new(consts::TOKEN_OUT_RUNTIME_ARG_NAME, Key::cl_type()),
+            Parameter::new(consts::AMOUNT_IN_RUNTIME_ARG_NAME, U256::cl_type()),
+            Parameter::new(consts::AMOUNT_OUT_MIN_RUNTIME_ARG_NAME, U256::cl_type()),
+            Parameter::new(
+                consts::INTERMEDIATES_RUNTIME_ARG_NAME,
+                CLType::List(Box::new(CLType::Key)),
+            ),
+            Parameter::new(consts::TO_RUNTIME_ARG_NAME, Key::cl_type()),
             Parameter::new(consts::DEAD_LINE_RUNTIME_ARG_NAME, U256::cl_type()),
         ],
         CLType::Unit,
@@ -147,7 +421,85 @@ pub fn swap_tokens_for_exact_tokens() -> EntryPoint {
                 consts::PATH_RUNTIME_ARG_NAME,
                 CLType::List(Box::new(CLType::Key)),
             ),
-            Parameter::new(consts::TO_RUNTIME_ARG_NAME, AccountHash::cl_type()),
+            Parameter::new(consts::TO_RUNTIME_ARG_NAME, Key::cl_type()),
+            Parameter::new(consts::DEAD_LINE_RUNTIME_ARG_NAME, U256::cl_type()),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    )
+}
+
+pub fn swap_exact_cspr_for_tokens() -> EntryPoint {
+    EntryPoint::new(
+        String::from(consts::SWAP_EXACT_CSPR_FOR_TOKENS_ENTRY_POINT_NAME),
+        vec![
+            Parameter::new(consts::AMOUNT_IN_RUNTIME_ARG_NAME, U256::cl_type()),
+            Parameter::new(consts::AMOUNT_OUT_MIN_RUNTIME_ARG_NAME, U256::cl_type()),
+            Parameter::new(
+                consts::PATH_RUNTIME_ARG_NAME,
+                CLType::List(Box::new(CLType::Key)),
+            ),
+            Parameter::new(consts::TO_RUNTIME_ARG_NAME, Key::cl_type()),
+            Parameter::new(consts::DEAD_LINE_RUNTIME_ARG_NAME, U256::cl_type()),
+            Parameter::new(consts::PURSE_RUNTIME_ARG_NAME, URef::cl_type()),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    )
+}
+
+pub fn swap_cspr_for_exact_tokens() -> EntryPoint {
+    EntryPoint::new(
+        String::from(consts::SWAP_CSPR_FOR_EXACT_TOKENS_ENTRY_POINT_NAME),
+        vec![
+            Parameter::new(consts::AMOUNT_OUT_RUNTIME_ARG_NAME, U256::cl_type()),
+            Parameter::new(consts::AMOUNT_IN_MAX_RUNTIME_ARG_NAME, U256::cl_type()),
+            Parameter::new(
+                consts::PATH_RUNTIME_ARG_NAME,
+                CLType::List(Box::new(CLType::Key)),
+            ),
+            Parameter::new(consts::TO_RUNTIME_ARG_NAME, Key::cl_type()),
+            Parameter::new(consts::DEAD_LINE_RUNTIME_ARG_NAME, U256::cl_type()),
+            Parameter::new(consts::PURSE_RUNTIME_ARG_NAME, URef::cl_type()),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    )
+}
+
+pub fn swap_exact_tokens_for_cspr() -> EntryPoint {
+    EntryPoint::new(
+        String::from(consts::SWAP_EXACT_TOKENS_FOR_CSPR_ENTRY_POINT_NAME),
+        vec![
+            Parameter::new(consts::AMOUNT_IN_RUNTIME_ARG_NAME, U256::cl_type()),
+            Parameter::new(consts::AMOUNT_OUT_MIN_RUNTIME_ARG_NAME, U256::cl_type()),
+            Parameter::new(
+                consts::PATH_RUNTIME_ARG_NAME,
+                CLType::List(Box::new(CLType::Key)),
+            ),
+            Parameter::new(consts::TO_RUNTIME_ARG_NAME, Key::cl_type()),
+            Parameter::new(consts::DEAD_LINE_RUNTIME_ARG_NAME, U256::cl_type()),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    )
+}
+
+pub fn swap_tokens_for_exact_cspr() -> EntryPoint {
+    EntryPoint::new(
+        String::from(consts::SWAP_TOKENS_FOR_EXACT_CSPR_ENTRY_POINT_NAME),
+        vec![
+            Parameter::new(consts::AMOUNT_OUT_RUNTIME_ARG_NAME, U256::cl_type()),
+            Parameter::new(consts::AMOUNT_IN_MAX_RUNTIME_ARG_NAME, U256::cl_type()),
+            Parameter::new(
+                consts::PATH_RUNTIME_ARG_NAME,
+                CLType::List(Box::new(CLType::Key)),
+            ),
+            Parameter::new(consts::TO_RUNTIME_ARG_NAME, Key::cl_type()),
             Parameter::new(consts::DEAD_LINE_RUNTIME_ARG_NAME, U256::cl_type()),
         ],
         CLType::Unit,
@@ -160,12 +512,35 @@ pub fn default() -> EntryPoints {
     let mut contract_entry_points = EntryPoints::new();
     contract_entry_points.add_entry_point(create_pair());
     contract_entry_points.add_entry_point(get_pair());
+    contract_entry_points.add_entry_point(all_pairs_length());
+    contract_entry_points.add_entry_point(pair_at());
+    contract_entry_points.add_entry_point(pairs_for_token());
+    contract_entry_points.add_entry_point(get_base_tokens());
+    contract_entry_points.add_entry_point(set_base_tokens());
+    contract_entry_points.add_entry_point(get_best_amount_out());
+    contract_entry_points.add_entry_point(get_feeto());
     contract_entry_points.add_entry_point(set_feeto());
     contract_entry_points.add_entry_point(set_feeto_setter());
+    contract_entry_points.add_entry_point(get_protocol_fee_share());
+    contract_entry_points.add_entry_point(set_protocol_fee_share());
+    contract_entry_points.add_entry_point(set_pair_swap_fee());
+    contract_entry_points.add_entry_point(get_amounts_out());
+    contract_entry_points.add_entry_point(get_amounts_in());
+    contract_entry_points.add_entry_point(quote());
     contract_entry_points.add_entry_point(add_liquidity());
     contract_entry_points.add_entry_point(remove_liquidity());
+    contract_entry_points.add_entry_point(add_liquidity_cspr());
+    contract_entry_points.add_entry_point(remove_liquidity_cspr());
+    contract_entry_points.add_entry_point(remove_liquidity_supporting_fee());
+    contract_entry_points.add_entry_point(remove_liquidity_cspr_supporting_fee());
     contract_entry_points.add_entry_point(swap_exact_tokens_for_tokens());
     contract_entry_points.add_entry_point(swap_tokens_for_exact_tokens());
     contract_entry_points.add_entry_point(swap_exact_tokens_for_tokens_supporting_fee());
+    contract_entry_points.add_entry_point(swap_exact_tokens_for_tokens_split());
+    contract_entry_points.add_entry_point(swap_exact_tokens_for_tokens_best());
+    contract_entry_points.add_entry_point(swap_exact_cspr_for_tokens());
+    contract_entry_points.add_entry_point(swap_cspr_for_exact_tokens());
+    contract_entry_points.add_entry_point(swap_exact_tokens_for_cspr());
+    contract_entry_points.add_entry_point(swap_tokens_for_exact_cspr());
     contract_entry_points
 }
@@ -1,4 +1,5 @@
 use casper_types::ApiError;
+use swappery_math::MathError;
 
 pub enum Error {
     ExcessiveInputAmount,
@@ -10,6 +11,15 @@ pub enum Error {
     InvalidPath,
     Expired,
     Permission,
+    PairExists,
+    InvalidSwapFee,
+    InvalidProtocolFeeShare,
+    PairNotFound,
+    IdenticalAddresses,
+    NoRoute,
+    TooManyIntermediates,
+    Overflow,
+    InvalidRecipient,
 }
 
 const ERROR_EXCESSIVE_INPUT_AMOUNT: u16 = u16::MAX - 17;
@@ -21,6 +31,15 @@ const ERROR_INSUFFICIENT_LIQUIDITY: u16 = u16::MAX - 22;
 const ERROR_INVALID_PATH: u16 = u16::MAX - 23;
 const ERROR_EXPIRED: u16 = u16::MAX - 24;
 const ERROR_PERMISSION: u16 = u16::MAX - 25;
+const ERROR_PAIR_EXISTS: u16 = u16::MAX - 26;
+const ERROR_INVALID_SWAP_FEE: u16 = u16::MAX - 27;
+const ERROR_INVALID_PROTOCOL_FEE_SHARE: u16 = u16::MAX - 28;
+const ERROR_PAIR_NOT_FOUND: u16 = u16::MAX - 29;
+const ERROR_IDENTICAL_ADDRESSES: u16 = u16::MAX - 30;
+const ERROR_NO_ROUTE: u16 = u16::MAX - 31;
+const ERROR_TOO_MANY_INTERMEDIATES: u16 = u16::MAX - 32;
+const ERROR_OVERFLOW: u16 = u16::MAX - 33;
+const ERROR_INVALID_RECIPIENT: u16 = u16::MAX - 34;
 
 impl From<Error> for ApiError {
     fn from(error: Error) -> Self {
@@ -34,7 +53,29 @@ impl From<Error> for ApiError {
             Error::InvalidPath => ERROR_INVALID_PATH,
             Error::Expired => ERROR_EXPIRED,
             Error::Permission => ERROR_PERMISSION,
+            Error::PairExists => ERROR_PAIR_EXISTS,
+            Error::InvalidSwapFee => ERROR_INVALID_SWAP_FEE,
+            Error::InvalidProtocolFeeShare => ERROR_INVALID_PROTOCOL_FEE_SHARE,
+            Error::PairNotFound => ERROR_PAIR_NOT_FOUND,
+            Error::IdenticalAddresses => ERROR_IDENTICAL_ADDRESSES,
+            Error::NoRoute => ERROR_NO_ROUTE,
+            Error::TooManyIntermediates => ERROR_TOO_MANY_INTERMEDIATES,
+            Error::Overflow => ERROR_OVERFLOW,
+            Error::InvalidRecipient => ERROR_INVALID_RECIPIENT,
         };
         ApiError::User(user_error)
     }
 }
+
+impl From<MathError> for Error {
+    fn from(error: MathError) -> Self {
+        match error {
+            MathError::Overflow => Error::Overflow,
+            MathError::InsufficientAmount => Error::InsufficientAAmount,
+            MathError::InsufficientInputAmount => Error::InsufficientInputAmount,
+            MathError::InsufficientOutputAmount => Error::InsufficientOutputAmount,
+            MathError::InsufficientLiquidity => Error::InsufficientLiquidity,
+            MathError::InvalidFee => Error::InvalidSwapFee,
+        }
+    }
+}
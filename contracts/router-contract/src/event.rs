@@ -1,38 +1,189 @@
-use alloc::{string::String, vec::Vec};
-use casper_types::{ContractHash, U256};
+use alloc::vec::Vec;
+use casper_erc20::Address;
+use casper_types::{
+    bytesrepr::{self, ToBytes},
+    CLTyped, ContractHash, URef, U256,
+};
+use swappery_pair::ces::{CesEvent, Schemas};
 
 pub enum RouterEvent {
+    /// Creation of `pair`, whose events are written to the `events` dictionary.
     CreatePair {
-        token0: String,
-        token1: String,
-        pair: String,
+        token0: ContractHash,
+        token1: ContractHash,
+        pair: Address,
+        events: URef,
     },
     AddLiquidity {
-        token0: String,
-        token1: String,
+        token0: ContractHash,
+        token1: ContractHash,
         amount0: U256,
         amount1: U256,
-        recipient: String,
+        recipient: Address,
     },
     RemoveLiquidity {
-        token0: String,
-        token1: String,
+        token0: ContractHash,
+        token1: ContractHash,
         liquidity: U256,
-        recipient: String,
+        recipient: Address,
     },
     SwapExactIn {
         amount_in: U256,
         amount_out: U256,
         path: Vec<ContractHash>,
-        recipient: String,
+        recipient: Address,
     },
     SwapExactOut {
         amount_in: U256,
         amount_out: U256,
         path: Vec<ContractHash>,
-        recipient: String,
+        recipient: Address,
+    },
+    /// A swap split across `legs`, each reported as its path, amount in and amount out.
+    SwapSplit {
+        legs: Vec<(Vec<ContractHash>, U256, U256)>,
+        amount_in: U256,
+        amount_out: U256,
+        recipient: Address,
     },
     Installed {
         contract_hash: ContractHash,
     },
 }
+
+impl CesEvent for RouterEvent {
+    fn name(&self) -> &'static str {
+        match self {
+            RouterEvent::CreatePair { .. } => "create_pair",
+            RouterEvent::AddLiquidity { .. } => "add_liquidity",
+            RouterEvent::RemoveLiquidity { .. } => "remove_liquidity",
+            RouterEvent::SwapExactIn { .. } => "swap_exact_in",
+            RouterEvent::SwapExactOut { .. } => "swap_exact_out",
+            RouterEvent::SwapSplit { .. } => "swap_split",
+            RouterEvent::Installed { .. } => "installed",
+        }
+    }
+
+    fn fields_to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut bytes = Vec::new();
+        match self {
+            RouterEvent::CreatePair {
+                token0,
+                token1,
+                pair,
+                events,
+            } => {
+                bytes.append(&mut token0.to_bytes()?);
+                bytes.append(&mut token1.to_bytes()?);
+                bytes.append(&mut pair.to_bytes()?);
+                bytes.append(&mut events.to_bytes()?);
+            }
+            RouterEvent::AddLiquidity {
+                token0,
+                token1,
+                amount0,
+                amount1,
+                recipient,
+            } => {
+                bytes.append(&mut token0.to_bytes()?);
+                bytes.append(&mut token1.to_bytes()?);
+                bytes.append(&mut amount0.to_bytes()?);
+                bytes.append(&mut amount1.to_bytes()?);
+                bytes.append(&mut recipient.to_bytes()?);
+            }
+            RouterEvent::RemoveLiquidity {
+                token0,
+                token1,
+                liquidity,
+                recipient,
+            } => {
+                bytes.append(&mut token0.to_bytes()?);
+                bytes.append(&mut token1.to_bytes()?);
+                bytes.append(&mut liquidity.to_bytes()?);
+                bytes.append(&mut recipient.to_bytes()?);
+            }
+            RouterEvent::SwapExactIn {
+                amount_in,
+                amount_out,
+                path,
+                recipient,
+            }
+            | RouterEvent::SwapExactOut {
+                amount_in,
+                amount_out,
+                path,
+                recipient,
+            } => {
+                bytes.append(&mut amount_in.to_bytes()?);
+                bytes.append(&mut amount_out.to_bytes()?);
+                bytes.append(&mut path.to_bytes()?);
+                bytes.append(&mut recipient.to_bytes()?);
+            }
+            RouterEvent::SwapSplit {
+                legs,
+                amount_in,
+                amount_out,
+                recipient,
+            } => {
+                bytes.append(&mut legs.to_bytes()?);
+                bytes.append(&mut amount_in.to_bytes()?);
+                bytes.append(&mut amount_out.to_bytes()?);
+                bytes.append(&mut recipient.to_bytes()?);
+            }
+            RouterEvent::Installed { contract_hash } => {
+                bytes.append(&mut contract_hash.to_bytes()?);
+            }
+        }
+        Ok(bytes)
+    }
+
+    fn schemas() -> Schemas {
+        let swap_fields = [
+            ("amount_in", U256::cl_type()),
+            ("amount_out", U256::cl_type()),
+            ("path", Vec::<ContractHash>::cl_type()),
+            ("recipient", Address::cl_type()),
+        ];
+        Schemas::new()
+            .with(
+                "create_pair",
+                &[
+                    ("token0", ContractHash::cl_type()),
+                    ("token1", ContractHash::cl_type()),
+                    ("pair", Address::cl_type()),
+                    ("events", URef::cl_type()),
+                ],
+            )
+            .with(
+                "add_liquidity",
+                &[
+                    ("token0", ContractHash::cl_type()),
+                    ("token1", ContractHash::cl_type()),
+                    ("amount0", U256::cl_type()),
+                    ("amount1", U256::cl_type()),
+                    ("recipient", Address::cl_type()),
+                ],
+            )
+            .with(
+                "remove_liquidity",
+                &[
+                    ("token0", ContractHash::cl_type()),
+                    ("token1", ContractHash::cl_type()),
+                    ("liquidity", U256::cl_type()),
+                    ("recipient", Address::cl_type()),
+                ],
+            )
+            .with("swap_exact_in", &swap_fields)
+            .with("swap_exact_out", &swap_fields)
+            .with(
+                "swap_split",
+                &[
+                    ("legs", Vec::<(Vec<ContractHash>, U256, U256)>::cl_type()),
+                    ("amount_in", U256::cl_type()),
+                    ("amount_out", U256::cl_type()),
+                    ("recipient", Address::cl_type()),
+                ],
+            )
+            .with("installed", &[("contract_hash", ContractHash::cl_type())])
+    }
+}
@@ -5,7 +5,7 @@ use casper_types::{URef};
 use casper_erc20::Address;
 
 use crate::{constants::{
-    FEETO_KEY_NAME, FEETO_SETTER_KEY_NAME,
+    FEETO_KEY_NAME, FEETO_SETTER_KEY_NAME, PROTOCOL_FEE_SHARE_KEY_NAME,
 }, helpers};
 
 #[inline]
@@ -18,6 +18,11 @@ pub(crate) fn feeto_setter_uref() -> URef {
     helpers::get_uref(FEETO_SETTER_KEY_NAME)
 }
 
+#[inline]
+pub(crate) fn protocol_fee_share_uref() -> URef {
+    helpers::get_uref(PROTOCOL_FEE_SHARE_KEY_NAME)
+}
+
 pub(crate) fn read_feeto_from(uref: URef) -> Address {
     storage::read(uref).unwrap_or_revert().unwrap_or_revert()
 }
@@ -32,4 +37,12 @@ pub(crate) fn read_feeto_setter_from(uref: URef) -> Address {
 
 pub(crate) fn write_feeto_setter_to(uref: URef, value: Address) {
     storage::write(uref, value);
-}
\ No newline at end of file
+}
+
+pub(crate) fn read_protocol_fee_share_from(uref: URef) -> u32 {
+    storage::read(uref).unwrap_or_revert().unwrap_or_revert()
+}
+
+pub(crate) fn write_protocol_fee_share_to(uref: URef, value: u32) {
+    storage::write(uref, value);
+}
@@ -3,23 +3,25 @@ use core::convert::TryInto;
 
 extern crate alloc;
 
-use alloc::{collections::BTreeMap, format, string::ToString, vec::Vec};
-
 use casper_contract::{
     contract_api::{runtime, storage},
     unwrap_or_revert::UnwrapOrRevert,
 };
 use casper_types::{
     bytesrepr::FromBytes, runtime_args, system::CallStackElement, ApiError, CLTyped, ContractHash,
-    ContractPackageHash, RuntimeArgs, URef, U256,
+    ContractPackageHash, Key, RuntimeArgs, URef, U256,
 };
 
+use swappery_pair::ces;
+
 use casper_erc20::{Address, Error};
 
 use crate::error::Error as RouterError;
 use crate::event::RouterEvent;
 
-use crate::constants::GET_RESERVES_ENTRY_POINT_NAME;
+use crate::constants::{
+    GET_RESERVES_ENTRY_POINT_NAME, GET_SWAP_FEE_ENTRY_POINT_NAME,
+};
 
 pub(crate) fn get_uref(name: &str) -> URef {
     let key = runtime::get_key(name)
@@ -59,6 +61,25 @@ pub(crate) fn get_immediate_caller_address() -> Result<Address, Error> {
         .ok_or(Error::InvalidContext)
 }
 
+/// Converts a recipient key into an [`Address`]: `Key::Account` is an account and `Key::Hash` a
+/// contract package.
+pub(crate) fn key_to_address(key: Key) -> Address {
+    match key {
+        Key::Account(account_hash) => Address::from(account_hash),
+        Key::Hash(hash) => Address::from(ContractPackageHash::new(hash)),
+        _ => runtime::revert(ApiError::UnexpectedKeyVariant),
+    }
+}
+
+/// Converts a recipient key of CSPR into an [`Address`]. Only accounts have a main purse to pay
+/// into, so anything but `Key::Account` reverts up front rather than after the swap or burn.
+pub(crate) fn key_to_account(key: Key) -> Address {
+    match key {
+        Key::Account(account_hash) => Address::from(account_hash),
+        _ => runtime::revert(RouterError::InvalidRecipient),
+    }
+}
+
 pub(crate) fn get_caller_address() -> Result<Address, Error> {
     let call_stack = runtime::get_call_stack();
     let top_of_the_stack = call_stack
@@ -71,26 +92,16 @@ pub(crate) fn get_caller_address() -> Result<Address, Error> {
 }
 
 pub(crate) fn quote(amount0: U256, reserve0: U256, reserve1: U256) -> U256 {
-    if !(amount0 > U256::zero()) {
-        // require(amountA > 0, 'PancakeLibrary: INSUFFICIENT_AMOUNT');
-    }
-    if !(reserve0 > U256::zero() && reserve1 > U256::zero()) {
-        // require(reserveA > 0 && reserveB > 0, 'PancakeLibrary: INSUFFICIENT_LIQUIDITY');
-    }
-    amount0 * reserve1 / reserve0
+    swappery_math::quote(amount0, reserve0, reserve1)
+        .map_err(RouterError::from)
+        .unwrap_or_revert()
 }
 
 pub(crate) fn sort_tokens(
     token0: ContractHash,
     token1: ContractHash,
 ) -> (ContractHash, ContractHash) {
-    let tokens: (ContractHash, ContractHash);
-    if token0.lt(&token1) {
-        tokens = (token0, token1);
-    } else {
-        tokens = (token1, token0);
-    }
-    tokens
+    swappery_math::sort_tokens(token0, token1)
 }
 
 pub(crate) fn get_reserves(
@@ -113,31 +124,36 @@ pub(crate) fn get_reserves(
     reserves
 }
 
-pub(crate) fn get_amount_out(amount_in: U256, reserve_in: U256, reserve_out: U256) -> U256 {
-    if !(amount_in > U256::zero()) {
-        runtime::revert(RouterError::InsufficientInputAmount);
-    }
-    if !(reserve_in > U256::zero() && reserve_out > U256::zero()) {
-        runtime::revert(RouterError::InsufficientLiquidity);
-    }
-
-    let amount_with_fee: U256 = amount_in * U256::from(998u64);
-    let nume: U256 = amount_with_fee * reserve_out;
-    let deno: U256 = reserve_in * U256::from(1000u64) + amount_with_fee;
-    nume / deno
+/// Returns the swap fee of `pair` in basis points, so quotes agree with the pair's K check.
+pub(crate) fn get_swap_fee(pair: Address) -> u32 {
+    runtime::call_versioned_contract(
+        *pair.as_contract_package_hash().unwrap_or_revert(),
+        None,
+        GET_SWAP_FEE_ENTRY_POINT_NAME,
+        runtime_args! {},
+    )
 }
 
-pub(crate) fn get_amount_in(amount_out: U256, reserve_in: U256, reserve_out: U256) -> U256 {
-    if !(amount_out > U256::zero()) {
-        runtime::revert(RouterError::InsufficientOutputAmount);
-    }
-    if !(reserve_in > U256::zero() && reserve_out > U256::zero()) {
-        runtime::revert(RouterError::InsufficientLiquidity);
-    }
+pub(crate) fn get_amount_out(
+    amount_in: U256,
+    reserve_in: U256,
+    reserve_out: U256,
+    swap_fee_bps: u32,
+) -> U256 {
+    swappery_math::get_amount_out(amount_in, reserve_in, reserve_out, swap_fee_bps)
+        .map_err(RouterError::from)
+        .unwrap_or_revert()
+}
 
-    let nume: U256 = reserve_in * amount_out * U256::from(1000u64);
-    let deno: U256 = (reserve_out - amount_out) * U256::from(998u64);
-    (nume / deno) + U256::one()
+pub(crate) fn get_amount_in(
+    amount_out: U256,
+    reserve_in: U256,
+    reserve_out: U256,
+    swap_fee_bps: u32,
+) -> U256 {
+    swappery_math::get_amount_in(amount_out, reserve_in, reserve_out, swap_fee_bps)
+        .map_err(RouterError::from)
+        .unwrap_or_revert()
 }
 
 pub fn contract_package_hash() -> ContractPackageHash {
@@ -153,107 +169,7 @@ pub fn contract_package_hash() -> ContractPackageHash {
     package_hash.unwrap_or_revert()
 }
 
+/// Emits `event` into the router's CES events.
 pub fn emit(event: &RouterEvent) {
-    let mut events = Vec::new();
-    match event {
-        RouterEvent::CreatePair {
-            token0,
-            token1,
-            pair,
-        } => {
-            let mut param = BTreeMap::new();
-            param.insert(
-                "contract_package_hash",
-                contract_package_hash().to_formatted_string(),
-            );
-            param.insert("event_type", "create_pair".to_string());
-            param.insert("token0", token0.to_string());
-            param.insert("token1", token1.to_string());
-            param.insert("pair", pair.to_string());
-            events.push(param);
-        }
-        RouterEvent::AddLiquidity {
-            token0,
-            token1,
-            amount0,
-            amount1,
-            recipient,
-        } => {
-            let mut param = BTreeMap::new();
-            param.insert(
-                "contract_package_hash",
-                contract_package_hash().to_formatted_string(),
-            );
-            param.insert("event_type", "add_liquidity".to_string());
-            param.insert("token0", token0.to_string());
-            param.insert("token1", token1.to_string());
-            param.insert("amount0", amount0.to_string());
-            param.insert("amount1", amount1.to_string());
-            param.insert("recipient", recipient.to_string());
-            events.push(param);
-        }
-        RouterEvent::RemoveLiquidity {
-            token0,
-            token1,
-            liquidity,
-            recipient,
-        } => {
-            let mut param = BTreeMap::new();
-            param.insert(
-                "contract_package_hash",
-                contract_package_hash().to_formatted_string(),
-            );
-            param.insert("event_type", "remove_liquidity".to_string());
-            param.insert("token0", token0.to_string());
-            param.insert("token1", token1.to_string());
-            param.insert("liquidity", liquidity.to_string());
-            param.insert("recipient", recipient.to_string());
-            events.push(param);
-        }
-        RouterEvent::SwapExactIn {
-            amount_in,
-            amount_out,
-            path,
-            recipient,
-        } => {
-            let mut param = BTreeMap::new();
-            param.insert(
-                "contract_package_hash",
-                contract_package_hash().to_formatted_string(),
-            );
-            param.insert("event_type", "swap_exact_in".to_string());
-            param.insert("amount_in", amount_in.to_string());
-            param.insert("amount_out", amount_out.to_string());
-            param.insert("path", format!("{:?}", path));
-            param.insert("recipient", recipient.to_string());
-            events.push(param);
-        }
-        RouterEvent::SwapExactOut {
-            amount_in,
-            amount_out,
-            path,
-            recipient,
-        } => {
-            let mut param = BTreeMap::new();
-            param.insert(
-                "contract_package_hash",
-                contract_package_hash().to_formatted_string(),
-            );
-            param.insert("event_type", "swap_exact_out".to_string());
-            param.insert("amount_in", amount_in.to_string());
-            param.insert("amount_out", amount_out.to_string());
-            param.insert("path", format!("{:?}", path));
-            param.insert("recipient", recipient.to_string());
-            events.push(param);
-        }
-        RouterEvent::Installed { contract_hash } => {
-            let mut param = BTreeMap::new();
-            param.insert("event_type", "installed".to_string());
-            param.insert("contract_hash", contract_hash.to_string());
-            events.push(param);
-        }
-    };
-    for param in events {
-        let _: URef = storage::new_uref(param);
-    }
+    ces::emit(event);
 }
@@ -14,7 +14,7 @@ mod feeto;
 mod helpers;
 mod pair_list;
 
-use alloc::{string::String, vec::Vec};
+use alloc::{collections::BTreeMap, format, string::String, vec, vec::Vec};
 
 use casper_erc20::{
     constants::{
@@ -27,30 +27,56 @@ use casper_erc20::{
 use constants as consts;
 
 use casper_types::{
-    account::AccountHash, contracts::NamedKeys, runtime_args, CLValue, ContractHash,
-    ContractPackageHash, Error, HashAddr, Key, RuntimeArgs, URef, U256,
+    contracts::NamedKeys, runtime_args, CLValue, ContractHash, Error, HashAddr, Key, RuntimeArgs,
+    URef, U256,
 };
 
 use casper_contract::{
-    contract_api::{runtime, storage},
+    contract_api::{runtime, storage, system},
     unwrap_or_revert::UnwrapOrRevert,
 };
 
 use once_cell::unsync::OnceCell;
 
+use swappery_pair::{ces, SwapperyPair};
+
+swappery_pair::export_pair_entry_points!();
+
+/// Reserves, in sorted token order, and swap fee of a pair; `None` when there is no such pair.
+type PairQuote = Option<(U256, U256, u32)>;
+
 #[derive(Default)]
 pub struct SwapperyRouter {
     pair_list_uref: OnceCell<URef>,
+    all_pairs_uref: OnceCell<URef>,
+    all_pairs_length_uref: OnceCell<URef>,
+    token_pairs_uref: OnceCell<URef>,
+    token_pairs_length_uref: OnceCell<URef>,
     feeto_uref: OnceCell<URef>,
     feeto_setter_uref: OnceCell<URef>,
+    protocol_fee_share_uref: OnceCell<URef>,
 }
 
 impl SwapperyRouter {
-    fn new(pair_list_uref: URef, feeto_uref: URef, feeto_setter_uref: URef) -> Self {
+    fn new(
+        pair_list_uref: URef,
+        all_pairs_uref: URef,
+        all_pairs_length_uref: URef,
+        token_pairs_uref: URef,
+        token_pairs_length_uref: URef,
+        feeto_uref: URef,
+        feeto_setter_uref: URef,
+        protocol_fee_share_uref: URef,
+    ) -> Self {
         Self {
             pair_list_uref: pair_list_uref.into(),
+            all_pairs_uref: all_pairs_uref.into(),
+            all_pairs_length_uref: all_pairs_length_uref.into(),
+            token_pairs_uref: token_pairs_uref.into(),
+            token_pairs_length_uref: token_pairs_length_uref.into(),
             feeto_uref: feeto_uref.into(),
             feeto_setter_uref: feeto_setter_uref.into(),
+            protocol_fee_share_uref: protocol_fee_share_uref.into(),
         }
     }
     fn pair_list_uref(&self) -> URef {
@@ -58,11 +84,59 @@ impl SwapperyRouter {
             .pair_list_uref
             .get_or_init(pair_list::get_pair_list_uref)
     }
+    fn try_get_pair_for(&self, token0: ContractHash, token1: ContractHash) -> Option<Address> {
+        pair_list::try_get_pair_for(self.pair_list_uref(), token0, token1)
+    }
     fn get_pair_for(&self, token0: ContractHash, token1: ContractHash) -> Address {
         pair_list::get_pair_for(self.pair_list_uref(), token0, token1)
     }
     fn add_pair_for(&self, token0: ContractHash, token1: ContractHash, pair: Address) {
-        pair_list::add_pair_for(self.pair_list_uref(), token0, token1, pair)
+        pair_list::add_pair_for(self.pair_list_uref(), token0, token1, pair);
+        pair_list::push_pair(
+            self.all_pairs_uref(),
+            self.all_pairs_length_uref(),
+            self.token_pairs_uref(),
+            self.token_pairs_length_uref(),
+            token0,
+            token1,
+            pair,
+        );
+    }
+
+    fn all_pairs_uref(&self) -> URef {
+        *self
+            .all_pairs_uref
+            .get_or_init(pair_list::get_all_pairs_uref)
+    }
+    fn all_pairs_length_uref(&self) -> URef {
+        *self
+            .all_pairs_length_uref
+            .get_or_init(pair_list::get_all_pairs_length_uref)
+    }
+    fn token_pairs_uref(&self) -> URef {
+        *self
+            .token_pairs_uref
+            .get_or_init(pair_list::get_token_pairs_uref)
+    }
+    fn token_pairs_length_uref(&self) -> URef {
+        *self
+            .token_pairs_length_uref
+            .get_or_init(pair_list::get_token_pairs_length_uref)
+    }
+    fn all_pairs_length(&self) -> u64 {
+        pair_list::read_all_pairs_length_from(self.all_pairs_length_uref())
+    }
+    fn pair_at(&self, index: u64) -> Option<Address> {
+        pair_list::get_pair_at(self.all_pairs_uref(), index)
+    }
+    fn pairs_for_token(&self, token: ContractHash, offset: u64, limit: u64) -> Vec<Address> {
+        pair_list::get_pairs_for_token(
+            self.token_pairs_uref(),
+            self.token_pairs_length_uref(),
+            token,
+            offset,
+            limit,
+        )
     }
 
     fn feeto_uref(&self) -> URef {
@@ -89,10 +163,145 @@ impl SwapperyRouter {
         feeto::write_feeto_setter_to(self.feeto_setter_uref(), feeto_setter)
     }
 
+    fn protocol_fee_share_uref(&self) -> URef {
+        *self
+            .protocol_fee_share_uref
+            .get_or_init(feeto::protocol_fee_share_uref)
+    }
+
+    fn read_protocol_fee_share(&self) -> u32 {
+        feeto::read_protocol_fee_share_from(self.protocol_fee_share_uref())
+    }
+
+    fn write_protocol_fee_share(&self, protocol_fee_share_bps: u32) {
+        feeto::write_protocol_fee_share_to(self.protocol_fee_share_uref(), protocol_fee_share_bps)
+    }
+
     pub fn wcspr_token(&self) -> ContractHash {
         helpers::read_from(consts::WCSPR_CONTRACT_KEY_NAME)
     }
 
+    fn read_base_tokens(&self) -> Vec<ContractHash> {
+        helpers::read_from(consts::BASE_TOKENS_KEY_NAME)
+    }
+
+    fn write_base_tokens(&self, base_tokens: Vec<ContractHash>) {
+        storage::write(helpers::get_uref(consts::BASE_TOKENS_KEY_NAME), base_tokens);
+    }
+
+    /// Returns the router's purse, through which unwrapped CSPR is paid out.
+    fn purse(&self) -> URef {
+        helpers::get_uref(consts::PURSE_KEY_NAME)
+    }
+
+    /// Wraps `amount` motes taken from `purse` into WCSPR held by the router.
+    fn deposit_cspr(&self, purse: URef, amount: U256) {
+        runtime::call_contract::<()>(
+            self.wcspr_token(),
+            consts::DEPOSIT_ENTRY_POINT_NAME,
+            runtime_args! {
+                consts::AMOUNT_RUNTIME_ARG_NAME => swappery_math::widen(amount),
+                consts::PURSE_RUNTIME_ARG_NAME => purse
+            },
+        );
+    }
+
+    /// Unwraps `amount` of the router's WCSPR and sends the CSPR to the `to` account, which callers
+    /// check with `helpers::key_to_account` on entry.
+    fn withdraw_cspr(&self, amount: U256, to: Address) {
+        let cspr_amount = swappery_math::widen(amount);
+        runtime::call_contract::<()>(
+            self.wcspr_token(),
+            consts::WITHDRAW_ENTRY_POINT_NAME,
+            runtime_args! {
+                consts::AMOUNT_RUNTIME_ARG_NAME => cspr_amount,
+                consts::TO_PURSE_RUNTIME_ARG_NAME => self.purse().into_add()
+            },
+        );
+        system::transfer_from_purse_to_account(
+            self.purse(),
+            *to.as_account_hash().unwrap_or_revert(),
+            cspr_amount,
+            None,
+        )
+        .unwrap_or_revert();
+    }
+
+    fn balance_of(&self, token: ContractHash, owner: Address) -> U256 {
+        runtime::call_contract(
+            token,
+            consts::BALANCE_OF_ENTRY_POINT_NAME,
+            runtime_args! {
+                consts::ADDRESS_RUNTIME_ARG_NAME => owner,
+            },
+        )
+    }
+
+    /// Returns how much of `token` the router gained over `balance_before`, capped at the `sent`
+    /// amount so balances the router held before are never swept along.
+    fn received_since(&self, token: ContractHash, balance_before: U256, sent: U256) -> U256 {
+        let router = Address::from(helpers::contract_package_hash());
+        let received: U256 = self
+            .balance_of(token, router)
+            .checked_sub(balance_before)
+            .unwrap_or_revert_with(error::Error::Overflow);
+        received.min(sent)
+    }
+
+    /// Sends `amount` of the router's `token` to `to` and returns what `to` actually received,
+    /// which is less than was sent for fee-on-transfer tokens.
+    fn forward(&self, token: ContractHash, amount: U256, to: Address) -> U256 {
+        let balance_before: U256 = self.balance_of(token, to);
+        runtime::call_contract::<()>(
+            token,
+            consts::TRANSFER_ENTRY_POINT_NAME,
+            runtime_args! {
+                RECIPIENT_RUNTIME_ARG_NAME => to,
+                AMOUNT_RUNTIME_ARG_NAME => amount
+            },
+        );
+        self.balance_of(token, to)
+            .checked_sub(balance_before)
+            .unwrap_or_revert_with(error::Error::Overflow)
+    }
+
+    /// Pulls `liquidity` of the `token0`/`token1` pair from the caller and burns it to the router,
+    /// returning the burnt amounts in `token0`, `token1` order.
+    fn burn_to_router(
+        &self,
+        token0: ContractHash,
+        token1: ContractHash,
+        liquidity: U256,
+    ) -> (U256, U256) {
+        let pair: Address = self.get_pair_for(token0, token1);
+        let caller: Address = helpers::get_immediate_caller_address().unwrap_or_revert();
+        runtime::call_versioned_contract::<()>(
+            *pair.as_contract_package_hash().unwrap_or_revert(),
+            None,
+            TRANSFER_FROM_ENTRY_POINT_NAME,
+            runtime_args! {
+                OWNER_RUNTIME_ARG_NAME => caller,
+                RECIPIENT_RUNTIME_ARG_NAME => pair,
+                AMOUNT_RUNTIME_ARG_NAME => liquidity
+            },
+        );
+        let amounts: (U256, U256) = runtime::call_versioned_contract(
+            *pair.as_contract_package_hash().unwrap_or_revert(),
+            None,
+            consts::BURN_ENTRY_POINT_NAME,
+            runtime_args! {
+                consts::TO_RUNTIME_ARG_NAME => Address::from(helpers::contract_package_hash()),
+            },
+        );
+        // The pair returns its amounts in sorted token order.
+        let (sorted_token0, _) = helpers::sort_tokens(token0, token1);
+        if sorted_token0 == token0 {
+            amounts
+        } else {
+            (amounts.1, amounts.0)
+        }
+    }
+
     pub fn create(
         feeto: Address,
         feeto_setter: Address,
@@ -101,8 +310,20 @@ impl SwapperyRouter {
     ) -> Result<SwapperyRouter, Error> {
         let pair_list_uref: URef =
             storage::new_dictionary(consts::PAIR_LIST_KEY_NAME).unwrap_or_revert();
+        let all_pairs_uref: URef =
+            storage::new_dictionary(consts::ALL_PAIRS_KEY_NAME).unwrap_or_revert();
+        let all_pairs_length_uref: URef = storage::new_uref(0u64).into_read_write();
+        let token_pairs_uref: URef =
+            storage::new_dictionary(consts::TOKEN_PAIRS_KEY_NAME).unwrap_or_revert();
+        let token_pairs_length_uref: URef =
+            storage::new_dictionary(consts::TOKEN_PAIRS_LENGTH_KEY_NAME).unwrap_or_revert();
         let feeto_uref: URef = storage::new_uref(feeto).into_read_write();
         let feeto_setter_uref: URef = storage::new_uref(feeto_setter).into_read_write();
+        let protocol_fee_share_uref: URef =
+            storage::new_uref(consts::DEFAULT_PROTOCOL_FEE_SHARE_BPS).into_read_write();
+        let purse: URef = system::create_purse();
+        let base_tokens_uref: URef =
+            storage::new_uref(Vec::<ContractHash>::new()).into_read_write();
         let wcspr_token_key: Key = {
             let wcspr_token_uref = storage::new_uref(wcspr_token).into_read();
             Key::from(wcspr_token_uref)
@@ -111,20 +332,53 @@ impl SwapperyRouter {
             runtime::remove_key(consts::PAIR_LIST_KEY_NAME);
             Key::from(pair_list_uref)
         };
+        let all_pairs_key = {
+            runtime::remove_key(consts::ALL_PAIRS_KEY_NAME);
+            Key::from(all_pairs_uref)
+        };
+        let token_pairs_key = {
+            runtime::remove_key(consts::TOKEN_PAIRS_KEY_NAME);
+            Key::from(token_pairs_uref)
+        };
+        let token_pairs_length_key = {
+            runtime::remove_key(consts::TOKEN_PAIRS_LENGTH_KEY_NAME);
+            Key::from(token_pairs_length_uref)
+        };
         let feeto_key = Key::from(feeto_uref);
         let feeto_setter_key = Key::from(feeto_setter_uref);
 
         let mut named_keys = NamedKeys::new();
         named_keys.insert(String::from(consts::PAIR_LIST_KEY_NAME), pair_list_key);
+        named_keys.insert(String::from(consts::ALL_PAIRS_KEY_NAME), all_pairs_key);
+        named_keys.insert(
+            String::from(consts::ALL_PAIRS_LENGTH_KEY_NAME),
+            Key::from(all_pairs_length_uref),
+        );
+        named_keys.insert(String::from(consts::TOKEN_PAIRS_KEY_NAME), token_pairs_key);
+        named_keys.insert(
+            String::from(consts::TOKEN_PAIRS_LENGTH_KEY_NAME),
+            token_pairs_length_key,
+        );
         named_keys.insert(String::from(consts::FEETO_KEY_NAME), feeto_key);
         named_keys.insert(
             String::from(consts::FEETO_SETTER_KEY_NAME),
             feeto_setter_key,
         );
+        named_keys.insert(
+            String::from(consts::PROTOCOL_FEE_SHARE_KEY_NAME),
+            Key::from(protocol_fee_share_uref),
+        );
+        named_keys.insert(String::from(consts::PURSE_KEY_NAME), Key::from(purse));
+        named_keys.insert(
+            String::from(consts::BASE_TOKENS_KEY_NAME),
+            Key::from(base_tokens_uref),
+        );
         named_keys.insert(
             String::from(consts::WCSPR_CONTRACT_KEY_NAME),
             wcspr_token_key,
         );
+        let events_named_keys: NamedKeys = ces::init::<event::RouterEvent>();
+        named_keys.extend(events_named_keys.clone());
 
         let (contract_hash, version) = storage::new_contract(
             entry_points::default(),
@@ -133,11 +387,16 @@ impl SwapperyRouter {
             None,
         );
         let event = event::RouterEvent::Installed { contract_hash };
-        helpers::emit(&event);
+        ces::emit_into(&events_named_keys, &event);
         Ok(SwapperyRouter::new(
             pair_list_uref,
+            all_pairs_uref,
+            all_pairs_length_uref,
+            token_pairs_uref,
+            token_pairs_length_uref,
             feeto_uref,
             feeto_setter_uref,
+            protocol_fee_share_uref,
         ))
     }
 
@@ -156,11 +415,103 @@ impl SwapperyRouter {
                 *amounts.get(i).unwrap_or_revert(),
                 reserves.0,
                 reserves.1,
+                helpers::get_swap_fee(pair),
             ));
         }
         amounts
     }
 
+    /// Returns the amounts swapping `amount_in` along `path` yields at every hop, or `None` when a
+    /// pair is missing or too shallow to return anything. Each pair is read once into `quotes`,
+    /// which all candidate paths of one search share.
+    fn try_get_amounts_out(
+        &self,
+        amount_in: U256,
+        path: &[ContractHash],
+        quotes: &mut BTreeMap<(ContractHash, ContractHash), PairQuote>,
+    ) -> Option<Vec<U256>> {
+        let mut amounts: Vec<U256> = Vec::with_capacity(path.len());
+        amounts.push(amount_in);
+        for hop in path.windows(2) {
+            let (token0, token1) = helpers::sort_tokens(hop[0], hop[1]);
+            let (reserve0, reserve1, swap_fee_bps) =
+                (*quotes.entry((token0, token1)).or_insert_with(|| {
+                    let pair: Address = self.try_get_pair_for(token0, token1)?;
+                    let (reserve0, reserve1) = helpers::get_reserves(token0, token1, pair);
+                    Some((reserve0, reserve1, helpers::get_swap_fee(pair)))
+                }))?;
+            let (reserve_in, reserve_out) = if hop[0] == token0 {
+                (reserve0, reserve1)
+            } else {
+                (reserve1, reserve0)
+            };
+            if reserve_in.is_zero() || reserve_out.is_zero() {
+                return None;
+            }
+            let amount_out =
+                helpers::get_amount_out(*amounts.last()?, reserve_in, reserve_out, swap_fee_bps);
+            if amount_out.is_zero() {
+                return None;
+            }
+            amounts.push(amount_out);
+        }
+        Some(amounts)
+    }
+
+    /// Finds the path from `token_in` to `token_out` returning the most for `amount_in`, among the
+    /// direct pair and the two- and three-hop routes through `intermediates`, WCSPR and the base
+    /// tokens. Returns the amounts at every hop of that path along with it.
+    pub fn get_best_amount_out(
+        &self,
+        token_in: ContractHash,
+        token_out: ContractHash,
+        amount_in: U256,
+        intermediates: Vec<ContractHash>,
+    ) -> Option<(Vec<U256>, Vec<ContractHash>)> {
+        if token_in == token_out {
+            runtime::revert(error::Error::InvalidPath);
+        }
+        if intermediates.len() > consts::MAX_INTERMEDIATES {
+            runtime::revert(error::Error::TooManyIntermediates);
+        }
+        if amount_in.is_zero() {
+            runtime::revert(error::Error::InsufficientInputAmount);
+        }
+
+        let mut hops: Vec<ContractHash> = Vec::new();
+        for token in intermediates
+            .into_iter()
+            .chain(Some(self.wcspr_token()))
+            .chain(self.read_base_tokens())
+        {
+            if token != token_in && token != token_out && !hops.contains(&token) {
+                hops.push(token);
+            }
+        }
+
+        let mut paths: Vec<Vec<ContractHash>> = vec![vec![token_in, token_out]];
+        for first in hops.iter() {
+            paths.push(vec![token_in, *first, token_out]);
+            for second in hops.iter().filter(|second| *second != first) {
+                paths.push(vec![token_in, *first, *second, token_out]);
+            }
+        }
+
+        let mut quotes: BTreeMap<(ContractHash, ContractHash), PairQuote> = BTreeMap::new();
+        let mut best: Option<(Vec<U256>, Vec<ContractHash>)> = None;
+        for path in paths {
+            if let Some(amounts) = self.try_get_amounts_out(amount_in, &path, &mut quotes) {
+                if best
+                    .as_ref()
+                    .map_or(true, |(best_amounts, _)| amounts.last() > best_amounts.last())
+                {
+                    best = Some((amounts, path));
+                }
+            }
+        }
+        best
+    }
+
     pub fn get_amounts_in(&self, amount_out: U256, path: Vec<ContractHash>) -> Vec<U256> {
         if !(path.len() >= 1) {
             runtime::revert(error::Error::InvalidPath);
@@ -182,6 +533,7 @@ impl SwapperyRouter {
                 *amounts.get(i - 1).unwrap_or_revert(),
                 reserves.0,
                 reserves.1,
+                helpers::get_swap_fee(pair),
             ));
         }
         amounts.reverse();
@@ -254,16 +606,6 @@ impl SwapperyRouter {
                 },
             );
         }
-        if path.last().unwrap_or_revert().eq(&self.wcspr_token()) {
-            runtime::call_contract::<()>(
-                self.wcspr_token(),
-                consts::WITHDRAW_ENTRY_POINT_NAME,
-                runtime_args! {
-                    consts::AMOUNT_RUNTIME_ARG_NAME => *amounts.get(amounts.len() - 1).unwrap_or_revert(),
-                    consts::TO_RUNTIME_ARG_NAME => _to
-                },
-            );
-        }
     }
 
     pub fn _swap_supporting_fee(&self, path: Vec<ContractHash>, _to: Address) {
@@ -276,15 +618,22 @@ impl SwapperyRouter {
             let pair = self.get_pair_for(*input, *output);
             let reserves = helpers::get_reserves(*input, *output, pair);
 
-            let mut amount_in = runtime::call_contract(
+            let balance: U256 = runtime::call_contract(
                 *input,
                 consts::BALANCE_OF_ENTRY_POINT_NAME,
                 runtime_args! {
                     consts::ADDRESS_RUNTIME_ARG_NAME => pair
                 },
             );
-            amount_in = amount_in - reserves.0;
-            let amount_out = helpers::get_amount_out(amount_in, reserves.0, reserves.1);
+            let amount_in = balance
+                .checked_sub(reserves.0)
+                .unwrap_or_revert_with(error::Error::InsufficientInputAmount);
+            let amount_out = helpers::get_amount_out(
+                amount_in,
+                reserves.0,
+                reserves.1,
+                helpers::get_swap_fee(pair),
+            );
 
             let amounts_out: (U256, U256);
             if input.eq(&token0) {
@@ -321,18 +670,49 @@ pub extern "C" fn create_pair() {
     let token0: ContractHash = ContractHash::new(_token0_hash);
     let _token1_hash: HashAddr = token1_key.into_hash().unwrap_or_revert();
     let token1: ContractHash = ContractHash::new(_token1_hash);
-    let pair_key: Key = runtime::get_named_arg(consts::PAIR_RUNTIME_ARG_NAME);
-    let _pair_hash: HashAddr = pair_key.into_hash().unwrap_or_revert();
-    let pair: Address = Address::from(ContractPackageHash::from(_pair_hash));
+    if token0 == token1 {
+        runtime::revert(error::Error::IdenticalAddresses);
+    }
+    let (token0, token1) = helpers::sort_tokens(token0, token1);
+
+    if SwapperyRouter::default()
+        .try_get_pair_for(token0, token1)
+        .is_some()
+    {
+        runtime::revert(error::Error::PairExists);
+    }
+
+    // Names come from the token hashes rather than the tokens' own `symbol`, which a hostile token
+    // could make revert or return unbounded strings from.
+    let tokens_tag = format!(
+        "{}-{}",
+        hex::encode(&token0.value()[..consts::PAIR_NAME_HASH_BYTES]),
+        hex::encode(&token1.value()[..consts::PAIR_NAME_HASH_BYTES])
+    );
+    let contract_key_name = format!("{}_{}", consts::PAIR_KEY_NAME_PREFIX, tokens_tag);
+    let (pair_package_hash, _, pair_events) = SwapperyPair::create(
+        format!("{} {}", consts::PAIR_NAME, tokens_tag),
+        String::from(consts::PAIR_SYMBOL),
+        consts::PAIR_DECIMALS,
+        U256::zero(),
+        contract_key_name.as_str(),
+        token0,
+        token1,
+        swappery_pair::Address::from(helpers::contract_package_hash()),
+        consts::DEFAULT_SWAP_FEE_BPS,
+    )
+    .unwrap_or_revert();
+    // Named keys of a contract are loaded on every call: the pair is only kept in `pair_list`.
+    runtime::remove_key(&contract_key_name);
+    runtime::remove_key(&format!("{}_contract_hash", contract_key_name));
+    let pair: Address = Address::from(pair_package_hash);
 
     SwapperyRouter::default().add_pair_for(token0, token1, pair);
     let event = event::RouterEvent::CreatePair {
-        token0: token0.to_formatted_string(),
-        token1: token1.to_formatted_string(),
-        pair: pair
-            .as_contract_package_hash()
-            .unwrap()
-            .to_formatted_string(),
+        token0,
+        token1,
+        pair,
+        events: pair_events.remove_access_rights(),
     };
     helpers::emit(&event);
 }
@@ -350,10 +730,66 @@ pub extern "C" fn get_pair() {
     runtime::ret(CLValue::from_t(pair).unwrap_or_revert());
 }
 
+#[no_mangle]
+pub extern "C" fn all_pairs_length() {
+    let length: u64 = SwapperyRouter::default().all_pairs_length();
+    runtime::ret(CLValue::from_t(length).unwrap_or_revert());
+}
+
+#[no_mangle]
+pub extern "C" fn pair_at() {
+    let index: u64 = runtime::get_named_arg(consts::INDEX_RUNTIME_ARG_NAME);
+    let pair: Address = SwapperyRouter::default()
+        .pair_at(index)
+        .unwrap_or_revert_with(error::Error::PairNotFound);
+    runtime::ret(CLValue::from_t(pair).unwrap_or_revert());
+}
+
+#[no_mangle]
+pub extern "C" fn pairs_for_token() {
+    let token_key: Key = runtime::get_named_arg(consts::TOKEN_RUNTIME_ARG_NAME);
+    let token: ContractHash = ContractHash::new(token_key.into_hash().unwrap_or_revert());
+    let offset: u64 = runtime::get_named_arg(consts::OFFSET_RUNTIME_ARG_NAME);
+    let limit: u64 = runtime::get_named_arg(consts::LIMIT_RUNTIME_ARG_NAME);
+    let pairs: Vec<Address> = SwapperyRouter::default().pairs_for_token(token, offset, limit);
+    runtime::ret(CLValue::from_t(pairs).unwrap_or_revert());
+}
+
+#[no_mangle]
+pub extern "C" fn get_base_tokens() {
+    let base_tokens: Vec<ContractHash> = SwapperyRouter::default().read_base_tokens();
+    runtime::ret(CLValue::from_t(base_tokens).unwrap_or_revert());
+}
+
+/// Sets the tokens tried as intermediates of every best-path search, on top of WCSPR.
+#[no_mangle]
+pub extern "C" fn set_base_tokens() {
+    let base_tokens_key: Vec<Key> = runtime::get_named_arg(consts::BASE_TOKENS_RUNTIME_ARG_NAME);
+    let caller: Address = helpers::get_immediate_caller_address().unwrap_or_revert();
+    let feeto_setter = SwapperyRouter::default().read_feeto_setter();
+    if caller != feeto_setter {
+        runtime::revert(error::Error::Permission);
+    }
+    if base_tokens_key.len() > consts::MAX_BASE_TOKENS {
+        runtime::revert(error::Error::TooManyIntermediates);
+    }
+    let base_tokens: Vec<ContractHash> = base_tokens_key
+        .iter()
+        .map(|key| ContractHash::new(key.into_hash().unwrap_or_revert()))
+        .collect();
+    SwapperyRouter::default().write_base_tokens(base_tokens);
+}
+
+#[no_mangle]
+pub extern "C" fn get_feeto() {
+    let feeto: Address = SwapperyRouter::default().read_feeto();
+    runtime::ret(CLValue::from_t(feeto).unwrap_or_revert());
+}
+
 #[no_mangle]
 pub extern "C" fn set_feeto() {
-    let feeto_key: Key = runtime::get_named_arg(consts::FEETO_KEY_NAME);
-    let feeto = Address::from(AccountHash::new(feeto_key.into_hash().unwrap_or_revert()));
+    let feeto_key: Key = runtime::get_named_arg(consts::FEETO_RUNTIME_ARG_NAME);
+    let feeto: Address = helpers::key_to_address(feeto_key);
     let caller: Address = helpers::get_immediate_caller_address().unwrap_or_revert();
     let feeto_setter = SwapperyRouter::default().read_feeto_setter();
     if caller != feeto_setter {
@@ -364,8 +800,8 @@ pub extern "C" fn set_feeto() {
 
 #[no_mangle]
 pub extern "C" fn set_feeto_setter() {
-    let feeto_key: Key = runtime::get_named_arg(consts::FEETO_SETTER_KEY_NAME);
-    let feeto = Address::from(AccountHash::new(feeto_key.into_hash().unwrap_or_revert()));
+    let feeto_key: Key = runtime::get_named_arg(consts::FEETO_SETTER_RUNTIME_ARG_NAME);
+    let feeto: Address = helpers::key_to_address(feeto_key);
     let caller: Address = helpers::get_immediate_caller_address().unwrap_or_revert();
     let feeto_setter = SwapperyRouter::default().read_feeto_setter();
     if caller != feeto_setter {
@@ -375,75 +811,63 @@ pub extern "C" fn set_feeto_setter() {
 }
 
 #[no_mangle]
-pub extern "C" fn add_liquidity() {
+pub extern "C" fn get_protocol_fee_share() {
+    let protocol_fee_share_bps: u32 = SwapperyRouter::default().read_protocol_fee_share();
+    runtime::ret(CLValue::from_t(protocol_fee_share_bps).unwrap_or_revert());
+}
+
+#[no_mangle]
+pub extern "C" fn set_protocol_fee_share() {
+    let protocol_fee_share_bps: u32 =
+        runtime::get_named_arg(consts::PROTOCOL_FEE_SHARE_BPS_RUNTIME_ARG_NAME);
+    let caller: Address = helpers::get_immediate_caller_address().unwrap_or_revert();
+    let feeto_setter = SwapperyRouter::default().read_feeto_setter();
+    if caller != feeto_setter {
+        runtime::revert(error::Error::Permission);
+    }
+    if protocol_fee_share_bps > consts::MAX_PROTOCOL_FEE_SHARE_BPS {
+        runtime::revert(error::Error::InvalidProtocolFeeShare);
+    }
+    SwapperyRouter::default().write_protocol_fee_share(protocol_fee_share_bps);
+}
+
+/// Sets a pair's swap fee; `feeto_setter` only. Anything from 0 (fee-free swaps) up to
+/// `MAX_SWAP_FEE_BPS` is accepted, which keeps the fee below `FEE_DENOMINATOR` so exact-output
+/// quotes stay defined. The pair emits `set_swap_fee` with the new value.
+#[no_mangle]
+pub extern "C" fn set_pair_swap_fee() {
     let token0_key: Key = runtime::get_named_arg(consts::TOKEN0_RUNTIME_ARG_NAME);
     let token1_key: Key = runtime::get_named_arg(consts::TOKEN1_RUNTIME_ARG_NAME);
     let _token0_hash: HashAddr = token0_key.into_hash().unwrap_or_revert();
     let token0: ContractHash = ContractHash::new(_token0_hash);
     let _token1_hash: HashAddr = token1_key.into_hash().unwrap_or_revert();
     let token1: ContractHash = ContractHash::new(_token1_hash);
-    let amount0_desired: U256 = runtime::get_named_arg(consts::AMOUNT0_DESIRED_RUNTIME_ARG_NAME);
-    let amount1_desired: U256 = runtime::get_named_arg(consts::AMOUNT1_DESIRED_RUNTIME_ARG_NAME);
-    let amount0_min: U256 = runtime::get_named_arg(consts::AMOUNT0_MIN_RUNTIME_ARG_NAME);
-    let amount1_min: U256 = runtime::get_named_arg(consts::AMOUNT1_MIN_RUNTIME_ARG_NAME);
-    let to_key: Key = runtime::get_named_arg(consts::TO_RUNTIME_ARG_NAME);
-    let to = Address::from(AccountHash::new(to_key.into_hash().unwrap_or_revert()));
-    // let dead_line: U256 = runtime::get_named_arg(consts::DEAD_LINE_RUNTIME_ARG_NAME);
+    let swap_fee_bps: u32 = runtime::get_named_arg(consts::SWAP_FEE_BPS_RUNTIME_ARG_NAME);
 
-    // if dead_line < U256::from(u64::from(runtime::get_blocktime())) {
-    //     runtime::revert(error::Error::Expired);
-    // }
+    let caller: Address = helpers::get_immediate_caller_address().unwrap_or_revert();
+    let feeto_setter = SwapperyRouter::default().read_feeto_setter();
+    if caller != feeto_setter {
+        runtime::revert(error::Error::Permission);
+    }
+    if swap_fee_bps > consts::MAX_SWAP_FEE_BPS {
+        runtime::revert(error::Error::InvalidSwapFee);
+    }
 
-    let amounts: (U256, U256) = SwapperyRouter::default()._add_liquidity(
-        token0,
-        token1,
-        amount0_desired,
-        amount1_desired,
-        amount0_min,
-        amount1_min,
-    );
     let pair: Address = SwapperyRouter::default().get_pair_for(token0, token1);
-    let caller: Address = helpers::get_immediate_caller_address().unwrap_or_revert();
-    runtime::call_contract::<()>(
-        token0,
-        TRANSFER_FROM_ENTRY_POINT_NAME,
-        runtime_args! {
-            OWNER_RUNTIME_ARG_NAME => caller,
-            RECIPIENT_RUNTIME_ARG_NAME => pair,
-            AMOUNT_RUNTIME_ARG_NAME => amounts.0
-        },
-    );
-    runtime::call_contract::<()>(
-        token1,
-        TRANSFER_FROM_ENTRY_POINT_NAME,
-        runtime_args! {
-            OWNER_RUNTIME_ARG_NAME => caller,
-            RECIPIENT_RUNTIME_ARG_NAME => pair,
-            AMOUNT_RUNTIME_ARG_NAME => amounts.1
-        },
-    );
-    let liquidity: U256 = runtime::call_versioned_contract(
+    runtime::call_versioned_contract::<()>(
         *pair.as_contract_package_hash().unwrap_or_revert(),
         None,
-        consts::MINT_ENTRY_POINT_NAME,
+        consts::SET_SWAP_FEE_ENTRY_POINT_NAME,
         runtime_args! {
-            consts::TO_RUNTIME_ARG_NAME => to,
-            consts::FEETO_KEY_NAME => SwapperyRouter::default().read_feeto(),
+            consts::SWAP_FEE_BPS_RUNTIME_ARG_NAME => swap_fee_bps
         },
     );
-    let event = event::RouterEvent::AddLiquidity {
-        token0: token0.to_formatted_string(),
-        token1: token1.to_formatted_string(),
-        amount0: amounts.0,
-        amount1: amounts.1,
-        recipient: to.as_account_hash().unwrap().to_formatted_string(),
-    };
-    helpers::emit(&event);
-    runtime::ret(CLValue::from_t(liquidity).unwrap_or_revert());
 }
 
+/// Like `remove_liquidity`, but checks the minimums against what `to` receives, so either token
+/// may take a fee on transfer.
 #[no_mangle]
-pub extern "C" fn remove_liquidity() {
+pub extern "C" fn remove_liquidity_supporting_fee() {
     let token0_key: Key = runtime::get_named_arg(consts::TOKEN0_RUNTIME_ARG_NAME);
     let token1_key: Key = runtime::get_named_arg(consts::TOKEN1_RUNTIME_ARG_NAME);
     let _token0_hash: HashAddr = token0_key.into_hash().unwrap_or_revert();
@@ -454,82 +878,394 @@ pub extern "C" fn remove_liquidity() {
     let amount0_min: U256 = runtime::get_named_arg(consts::AMOUNT0_MIN_RUNTIME_ARG_NAME);
     let amount1_min: U256 = runtime::get_named_arg(consts::AMOUNT1_MIN_RUNTIME_ARG_NAME);
     let to_key: Key = runtime::get_named_arg(consts::TO_RUNTIME_ARG_NAME);
-    let to = Address::from(AccountHash::new(to_key.into_hash().unwrap_or_revert()));
-    // let dead_line: U256 = runtime::get_named_arg(consts::DEAD_LINE_RUNTIME_ARG_NAME);
-
-    // if dead_line < U256::from(u64::from(runtime::get_blocktime())) {
-    //     runtime::revert(error::Error::Expired);
-    // }
+    let to: Address = helpers::key_to_address(to_key);
+    let dead_line: U256 = runtime::get_named_arg(consts::DEAD_LINE_RUNTIME_ARG_NAME);
 
-    let pair: Address = SwapperyRouter::default().get_pair_for(token0, token1);
-    let caller: Address = helpers::get_immediate_caller_address().unwrap_or_revert();
+    if dead_line < U256::from(u64::from(runtime::get_blocktime())) {
+        runtime::revert(error::Error::Expired);
+    }
 
-    runtime::call_versioned_contract::<()>(
-        *pair.as_contract_package_hash().unwrap_or_revert(),
-        None,
-        TRANSFER_FROM_ENTRY_POINT_NAME,
-        runtime_args! {
-            OWNER_RUNTIME_ARG_NAME => caller,
-            RECIPIENT_RUNTIME_ARG_NAME => pair,
-            AMOUNT_RUNTIME_ARG_NAME => liquidity
-        },
-    );
-    let amounts: (U256, U256) = runtime::call_versioned_contract(
-        *pair.as_contract_package_hash().unwrap_or_revert(),
-        None,
-        consts::BURN_ENTRY_POINT_NAME,
-        runtime_args! {
-            consts::TO_RUNTIME_ARG_NAME => to,
-            consts::FEETO_KEY_NAME => SwapperyRouter::default().read_feeto(),
-        },
-    );
-    if amounts.0 < amount0_min {
+    let router = Address::from(helpers::contract_package_hash());
+    let balance0_before: U256 = SwapperyRouter::default().balance_of(token0, router);
+    let balance1_before: U256 = SwapperyRouter::default().balance_of(token1, router);
+    let (burnt0, burnt1) = SwapperyRouter::default().burn_to_router(token0, token1, liquidity);
+    let received0: U256 = SwapperyRouter::default().received_since(token0, balance0_before, burnt0);
+    let received1: U256 = SwapperyRouter::default().received_since(token1, balance1_before, burnt1);
+    let amount0: U256 = SwapperyRouter::default().forward(token0, received0, to);
+    let amount1: U256 = SwapperyRouter::default().forward(token1, received1, to);
+    if amount0 < amount0_min {
         runtime::revert(error::Error::InsufficientAAmount);
     }
-    if token0.eq(&SwapperyRouter::default().wcspr_token()) {
-        runtime::call_contract::<()>(
-            SwapperyRouter::default().wcspr_token(),
-            consts::WITHDRAW_ENTRY_POINT_NAME,
-            runtime_args! {
-                consts::AMOUNT_RUNTIME_ARG_NAME => amounts.0,
-                consts::TO_RUNTIME_ARG_NAME => to
-            },
-        );
-    }
-    if amounts.1 < amount1_min {
+    if amount1 < amount1_min {
         runtime::revert(error::Error::InsufficientBAmount);
     }
-    if token1.eq(&SwapperyRouter::default().wcspr_token()) {
-        runtime::call_contract::<()>(
-            SwapperyRouter::default().wcspr_token(),
-            consts::WITHDRAW_ENTRY_POINT_NAME,
-            runtime_args! {
-                consts::AMOUNT_RUNTIME_ARG_NAME => amounts.1,
-                consts::TO_RUNTIME_ARG_NAME => to
-            },
-        );
-    }
     let event = event::RouterEvent::RemoveLiquidity {
-        token0: token0.to_formatted_string(),
-        token1: token1.to_formatted_string(),
+        token0,
+        token1,
         liquidity: liquidity,
-        recipient: to.as_account_hash().unwrap().to_formatted_string(),
+        recipient: to,
     };
     helpers::emit(&event);
 }
 
+/// Like `remove_liquidity_cspr`, but checks `amount_token_min` against what `to` receives, so
+/// `token` may take a fee on transfer. `to` must be an account.
 #[no_mangle]
-pub extern "C" fn swap_exact_tokens_for_tokens() {
-    let amount_in: U256 = runtime::get_named_arg(consts::AMOUNT_IN_RUNTIME_ARG_NAME);
-    let amount_out_min: U256 = runtime::get_named_arg(consts::AMOUNT_OUT_MIN_RUNTIME_ARG_NAME);
-    let path_key: Vec<Key> = runtime::get_named_arg(consts::PATH_RUNTIME_ARG_NAME);
+pub extern "C" fn remove_liquidity_cspr_supporting_fee() {
+    let token_key: Key = runtime::get_named_arg(consts::TOKEN_RUNTIME_ARG_NAME);
+    let token: ContractHash = ContractHash::new(token_key.into_hash().unwrap_or_revert());
+    let liquidity: U256 = runtime::get_named_arg(consts::LIQUIDITY_RUNTIME_ARG_NAME);
+    let amount_token_min: U256 = runtime::get_named_arg(consts::AMOUNT_TOKEN_MIN_RUNTIME_ARG_NAME);
+    let amount_cspr_min: U256 = runtime::get_named_arg(consts::AMOUNT_CSPR_MIN_RUNTIME_ARG_NAME);
     let to_key: Key = runtime::get_named_arg(consts::TO_RUNTIME_ARG_NAME);
-    let to = Address::from(AccountHash::new(to_key.into_hash().unwrap_or_revert()));
-    // let dead_line: U256 = runtime::get_named_arg(consts::DEAD_LINE_RUNTIME_ARG_NAME);
+    let to: Address = helpers::key_to_account(to_key);
+    let dead_line: U256 = runtime::get_named_arg(consts::DEAD_LINE_RUNTIME_ARG_NAME);
 
-    // if dead_line < U256::from(u64::from(runtime::get_blocktime())) {
-    //     runtime::revert(error::Error::Expired);
-    // }
+    if dead_line < U256::from(u64::from(runtime::get_blocktime())) {
+        runtime::revert(error::Error::Expired);
+    }
+
+    let wcspr_token: ContractHash = SwapperyRouter::default().wcspr_token();
+    let router = Address::from(helpers::contract_package_hash());
+    let token_balance_before: U256 = SwapperyRouter::default().balance_of(token, router);
+    let (burnt_token, amount_cspr) =
+        SwapperyRouter::default().burn_to_router(token, wcspr_token, liquidity);
+    let received_token: U256 =
+        SwapperyRouter::default().received_since(token, token_balance_before, burnt_token);
+    let amount_token: U256 = SwapperyRouter::default().forward(token, received_token, to);
+    if amount_token < amount_token_min {
+        runtime::revert(error::Error::InsufficientAAmount);
+    }
+    if amount_cspr < amount_cspr_min {
+        runtime::revert(error::Error::InsufficientBAmount);
+    }
+    SwapperyRouter::default().withdraw_cspr(amount_cspr, to);
+    let event = event::RouterEvent::RemoveLiquidity {
+        token0: token,
+        token1: wcspr_token,
+        liquidity: liquidity,
+        recipient: to,
+    };
+    helpers::emit(&event);
+}
+
+/// Returns the amounts a swap of exactly `amount_in` along `path` yields at every hop.
+#[no_mangle]
+pub extern "C" fn get_amounts_out() {
+    let amount_in: U256 = runtime::get_named_arg(consts::AMOUNT_IN_RUNTIME_ARG_NAME);
+    let path_key: Vec<Key> = runtime::get_named_arg(consts::PATH_RUNTIME_ARG_NAME);
+
+    let mut path: Vec<ContractHash> = Vec::new();
+    for i in 0..path_key.len() {
+        path.push({
+            let _hash = path_key.get(i).unwrap().into_hash().unwrap_or_revert();
+            ContractHash::new(_hash)
+        });
+    }
+
+    let amounts: Vec<U256> = SwapperyRouter::default().get_amounts_out(amount_in, path);
+    runtime::ret(CLValue::from_t(amounts).unwrap_or_revert());
+}
+
+/// Returns the best amount out for `amount_in` of `token_in` and the path yielding it, reverting
+/// when no route to `token_out` exists.
+#[no_mangle]
+pub extern "C" fn get_best_amount_out() {
+    let token_in_key: Key = runtime::get_named_arg(consts::TOKEN_IN_RUNTIME_ARG_NAME);
+    let token_out_key: Key = runtime::get_named_arg(consts::TOKEN_OUT_RUNTIME_ARG_NAME);
+    let amount_in: U256 = runtime::get_named_arg(consts::AMOUNT_IN_RUNTIME_ARG_NAME);
+    let intermediates_key: Vec<Key> = runtime::get_named_arg(consts::INTERMEDIATES_RUNTIME_ARG_NAME);
+    let token_in: ContractHash = ContractHash::new(token_in_key.into_hash().unwrap_or_revert());
+    let token_out: ContractHash = ContractHash::new(token_out_key.into_hash().unwrap_or_revert());
+    let intermediates: Vec<ContractHash> = intermediates_key
+        .iter()
+        .map(|key| ContractHash::new(key.into_hash().unwrap_or_revert()))
+        .collect();
+
+    let (amounts, path) = SwapperyRouter::default()
+        .get_best_amount_out(token_in, token_out, amount_in, intermediates)
+        .unwrap_or_revert_with(error::Error::NoRoute);
+    let amount_out: U256 = *amounts.last().unwrap_or_revert();
+    let path: Vec<Key> = path.into_iter().map(Key::from).collect();
+    runtime::ret(CLValue::from_t((amount_out, path)).unwrap_or_revert());
+}
+
+/// Returns the amounts every hop along `path` needs so the swap yields exactly `amount_out`.
+#[no_mangle]
+pub extern "C" fn get_amounts_in() {
+    let amount_out: U256 = runtime::get_named_arg(consts::AMOUNT_OUT_RUNTIME_ARG_NAME);
+    let path_key: Vec<Key> = runtime::get_named_arg(consts::PATH_RUNTIME_ARG_NAME);
+
+    let mut path: Vec<ContractHash> = Vec::new();
+    for i in 0..path_key.len() {
+        path.push({
+            let _hash = path_key.get(i).unwrap().into_hash().unwrap_or_revert();
+            ContractHash::new(_hash)
+        });
+    }
+
+    let amounts: Vec<U256> = SwapperyRouter::default().get_amounts_in(amount_out, path);
+    runtime::ret(CLValue::from_t(amounts).unwrap_or_revert());
+}
+
+/// Returns the amount of B worth `amount_a` of A at the `reserve_a`/`reserve_b` price, fee-free.
+#[no_mangle]
+pub extern "C" fn quote() {
+    let amount_a: U256 = runtime::get_named_arg(consts::AMOUNT_A_RUNTIME_ARG_NAME);
+    let reserve_a: U256 = runtime::get_named_arg(consts::RESERVE_A_RUNTIME_ARG_NAME);
+    let reserve_b: U256 = runtime::get_named_arg(consts::RESERVE_B_RUNTIME_ARG_NAME);
+    let amount_b: U256 = helpers::quote(amount_a, reserve_a, reserve_b);
+    runtime::ret(CLValue::from_t(amount_b).unwrap_or_revert());
+}
+
+#[no_mangle]
+pub extern "C" fn add_liquidity() {
+    let token0_key: Key = runtime::get_named_arg(consts::TOKEN0_RUNTIME_ARG_NAME);
+    let token1_key: Key = runtime::get_named_arg(consts::TOKEN1_RUNTIME_ARG_NAME);
+    let _token0_hash: HashAddr = token0_key.into_hash().unwrap_or_revert();
+    let token0: ContractHash = ContractHash::new(_token0_hash);
+    let _token1_hash: HashAddr = token1_key.into_hash().unwrap_or_revert();
+    let token1: ContractHash = ContractHash::new(_token1_hash);
+    let amount0_desired: U256 = runtime::get_named_arg(consts::AMOUNT0_DESIRED_RUNTIME_ARG_NAME);
+    let amount1_desired: U256 = runtime::get_named_arg(consts::AMOUNT1_DESIRED_RUNTIME_ARG_NAME);
+    let amount0_min: U256 = runtime::get_named_arg(consts::AMOUNT0_MIN_RUNTIME_ARG_NAME);
+    let amount1_min: U256 = runtime::get_named_arg(consts::AMOUNT1_MIN_RUNTIME_ARG_NAME);
+    let to_key: Key = runtime::get_named_arg(consts::TO_RUNTIME_ARG_NAME);
+    let to: Address = helpers::key_to_address(to_key);
+    let dead_line: U256 = runtime::get_named_arg(consts::DEAD_LINE_RUNTIME_ARG_NAME);
+
+    if dead_line < U256::from(u64::from(runtime::get_blocktime())) {
+        runtime::revert(error::Error::Expired);
+    }
+
+    let amounts: (U256, U256) = SwapperyRouter::default()._add_liquidity(
+        token0,
+        token1,
+        amount0_desired,
+        amount1_desired,
+        amount0_min,
+        amount1_min,
+    );
+    let pair: Address = SwapperyRouter::default().get_pair_for(token0, token1);
+    let caller: Address = helpers::get_immediate_caller_address().unwrap_or_revert();
+    runtime::call_contract::<()>(
+        token0,
+        TRANSFER_FROM_ENTRY_POINT_NAME,
+        runtime_args! {
+            OWNER_RUNTIME_ARG_NAME => caller,
+            RECIPIENT_RUNTIME_ARG_NAME => pair,
+            AMOUNT_RUNTIME_ARG_NAME => amounts.0
+        },
+    );
+    runtime::call_contract::<()>(
+        token1,
+        TRANSFER_FROM_ENTRY_POINT_NAME,
+        runtime_args! {
+            OWNER_RUNTIME_ARG_NAME => caller,
+            RECIPIENT_RUNTIME_ARG_NAME => pair,
+            AMOUNT_RUNTIME_ARG_NAME => amounts.1
+        },
+    );
+    let liquidity: U256 = runtime::call_versioned_contract(
+        *pair.as_contract_package_hash().unwrap_or_revert(),
+        None,
+        consts::MINT_ENTRY_POINT_NAME,
+        runtime_args! {
+            consts::TO_RUNTIME_ARG_NAME => to,
+        },
+    );
+    let event = event::RouterEvent::AddLiquidity {
+        token0,
+        token1,
+        amount0: amounts.0,
+        amount1: amounts.1,
+        recipient: to,
+    };
+    helpers::emit(&event);
+    runtime::ret(CLValue::from_t(liquidity).unwrap_or_revert());
+}
+
+#[no_mangle]
+pub extern "C" fn remove_liquidity() {
+    let token0_key: Key = runtime::get_named_arg(consts::TOKEN0_RUNTIME_ARG_NAME);
+    let token1_key: Key = runtime::get_named_arg(consts::TOKEN1_RUNTIME_ARG_NAME);
+    let _token0_hash: HashAddr = token0_key.into_hash().unwrap_or_revert();
+    let token0: ContractHash = ContractHash::new(_token0_hash);
+    let _token1_hash: HashAddr = token1_key.into_hash().unwrap_or_revert();
+    let token1: ContractHash = ContractHash::new(_token1_hash);
+    let liquidity: U256 = runtime::get_named_arg(consts::LIQUIDITY_RUNTIME_ARG_NAME);
+    let amount0_min: U256 = runtime::get_named_arg(consts::AMOUNT0_MIN_RUNTIME_ARG_NAME);
+    let amount1_min: U256 = runtime::get_named_arg(consts::AMOUNT1_MIN_RUNTIME_ARG_NAME);
+    let to_key: Key = runtime::get_named_arg(consts::TO_RUNTIME_ARG_NAME);
+    let to: Address = helpers::key_to_address(to_key);
+    let dead_line: U256 = runtime::get_named_arg(consts::DEAD_LINE_RUNTIME_ARG_NAME);
+
+    if dead_line < U256::from(u64::from(runtime::get_blocktime())) {
+        runtime::revert(error::Error::Expired);
+    }
+
+    let pair: Address = SwapperyRouter::default().get_pair_for(token0, token1);
+    let caller: Address = helpers::get_immediate_caller_address().unwrap_or_revert();
+
+    runtime::call_versioned_contract::<()>(
+        *pair.as_contract_package_hash().unwrap_or_revert(),
+        None,
+        TRANSFER_FROM_ENTRY_POINT_NAME,
+        runtime_args! {
+            OWNER_RUNTIME_ARG_NAME => caller,
+            RECIPIENT_RUNTIME_ARG_NAME => pair,
+            AMOUNT_RUNTIME_ARG_NAME => liquidity
+        },
+    );
+    let amounts: (U256, U256) = runtime::call_versioned_contract(
+        *pair.as_contract_package_hash().unwrap_or_revert(),
+        None,
+        consts::BURN_ENTRY_POINT_NAME,
+        runtime_args! {
+            consts::TO_RUNTIME_ARG_NAME => to,
+        },
+    );
+    if amounts.0 < amount0_min {
+        runtime::revert(error::Error::InsufficientAAmount);
+    }
+    if amounts.1 < amount1_min {
+        runtime::revert(error::Error::InsufficientBAmount);
+    }
+    let event = event::RouterEvent::RemoveLiquidity {
+        token0,
+        token1,
+        liquidity: liquidity,
+        recipient: to,
+    };
+    helpers::emit(&event);
+}
+
+/// Adds liquidity to the `token`/WCSPR pair, wrapping the CSPR side from `purse`; CSPR beyond the
+/// optimal amount stays in `purse`.
+#[no_mangle]
+pub extern "C" fn add_liquidity_cspr() {
+    let token_key: Key = runtime::get_named_arg(consts::TOKEN_RUNTIME_ARG_NAME);
+    let token: ContractHash = ContractHash::new(token_key.into_hash().unwrap_or_revert());
+    let amount_token_desired: U256 =
+        runtime::get_named_arg(consts::AMOUNT_TOKEN_DESIRED_RUNTIME_ARG_NAME);
+    let amount_cspr_desired: U256 =
+        runtime::get_named_arg(consts::AMOUNT_CSPR_DESIRED_RUNTIME_ARG_NAME);
+    let amount_token_min: U256 = runtime::get_named_arg(consts::AMOUNT_TOKEN_MIN_RUNTIME_ARG_NAME);
+    let amount_cspr_min: U256 = runtime::get_named_arg(consts::AMOUNT_CSPR_MIN_RUNTIME_ARG_NAME);
+    let to_key: Key = runtime::get_named_arg(consts::TO_RUNTIME_ARG_NAME);
+    let to: Address = helpers::key_to_address(to_key);
+    let purse: URef = runtime::get_named_arg(consts::PURSE_RUNTIME_ARG_NAME);
+    let dead_line: U256 = runtime::get_named_arg(consts::DEAD_LINE_RUNTIME_ARG_NAME);
+
+    if dead_line < U256::from(u64::from(runtime::get_blocktime())) {
+        runtime::revert(error::Error::Expired);
+    }
+
+    let wcspr_token: ContractHash = SwapperyRouter::default().wcspr_token();
+    let amounts: (U256, U256) = SwapperyRouter::default()._add_liquidity(
+        token,
+        wcspr_token,
+        amount_token_desired,
+        amount_cspr_desired,
+        amount_token_min,
+        amount_cspr_min,
+    );
+    let pair: Address = SwapperyRouter::default().get_pair_for(token, wcspr_token);
+    let caller: Address = helpers::get_immediate_caller_address().unwrap_or_revert();
+    runtime::call_contract::<()>(
+        token,
+        TRANSFER_FROM_ENTRY_POINT_NAME,
+        runtime_args! {
+            OWNER_RUNTIME_ARG_NAME => caller,
+            RECIPIENT_RUNTIME_ARG_NAME => pair,
+            AMOUNT_RUNTIME_ARG_NAME => amounts.0
+        },
+    );
+    SwapperyRouter::default().deposit_cspr(purse, amounts.1);
+    runtime::call_contract::<()>(
+        wcspr_token,
+        consts::TRANSFER_ENTRY_POINT_NAME,
+        runtime_args! {
+            RECIPIENT_RUNTIME_ARG_NAME => pair,
+            AMOUNT_RUNTIME_ARG_NAME => amounts.1
+        },
+    );
+    let liquidity: U256 = runtime::call_versioned_contract(
+        *pair.as_contract_package_hash().unwrap_or_revert(),
+        None,
+        consts::MINT_ENTRY_POINT_NAME,
+        runtime_args! {
+            consts::TO_RUNTIME_ARG_NAME => to,
+        },
+    );
+    let event = event::RouterEvent::AddLiquidity {
+        token0: token,
+        token1: wcspr_token,
+        amount0: amounts.0,
+        amount1: amounts.1,
+        recipient: to,
+    };
+    helpers::emit(&event);
+    runtime::ret(CLValue::from_t(liquidity).unwrap_or_revert());
+}
+
+/// Removes liquidity from the `token`/WCSPR pair, burning to the router so the WCSPR side can be
+/// unwrapped and paid out to `to` as CSPR. `to` must be an account; contract recipients revert
+/// with `InvalidRecipient`.
+#[no_mangle]
+pub extern "C" fn remove_liquidity_cspr() {
+    let token_key: Key = runtime::get_named_arg(consts::TOKEN_RUNTIME_ARG_NAME);
+    let token: ContractHash = ContractHash::new(token_key.into_hash().unwrap_or_revert());
+    let liquidity: U256 = runtime::get_named_arg(consts::LIQUIDITY_RUNTIME_ARG_NAME);
+    let amount_token_min: U256 = runtime::get_named_arg(consts::AMOUNT_TOKEN_MIN_RUNTIME_ARG_NAME);
+    let amount_cspr_min: U256 = runtime::get_named_arg(consts::AMOUNT_CSPR_MIN_RUNTIME_ARG_NAME);
+    let to_key: Key = runtime::get_named_arg(consts::TO_RUNTIME_ARG_NAME);
+    let to: Address = helpers::key_to_account(to_key);
+    let dead_line: U256 = runtime::get_named_arg(consts::DEAD_LINE_RUNTIME_ARG_NAME);
+
+    if dead_line < U256::from(u64::from(runtime::get_blocktime())) {
+        runtime::revert(error::Error::Expired);
+    }
+
+    let wcspr_token: ContractHash = SwapperyRouter::default().wcspr_token();
+    let (amount_token, amount_cspr) =
+        SwapperyRouter::default().burn_to_router(token, wcspr_token, liquidity);
+    if amount_token < amount_token_min {
+        runtime::revert(error::Error::InsufficientAAmount);
+    }
+    if amount_cspr < amount_cspr_min {
+        runtime::revert(error::Error::InsufficientBAmount);
+    }
+    runtime::call_contract::<()>(
+        token,
+        consts::TRANSFER_ENTRY_POINT_NAME,
+        runtime_args! {
+            RECIPIENT_RUNTIME_ARG_NAME => to,
+            AMOUNT_RUNTIME_ARG_NAME => amount_token
+        },
+    );
+    SwapperyRouter::default().withdraw_cspr(amount_cspr, to);
+    let event = event::RouterEvent::RemoveLiquidity {
+        token0: token,
+        token1: wcspr_token,
+        liquidity: liquidity,
+        recipient: to,
+    };
+    helpers::emit(&event);
+}
+
+#[no_mangle]
+pub extern "C" fn swap_exact_tokens_for_tokens() {
+    let amount_in: U256 = runtime::get_named_arg(consts::AMOUNT_IN_RUNTIME_ARG_NAME);
+    let amount_out_min: U256 = runtime::get_named_arg(consts::AMOUNT_OUT_MIN_RUNTIME_ARG_NAME);
+    let path_key: Vec<Key> = runtime::get_named_arg(consts::PATH_RUNTIME_ARG_NAME);
+    let to_key: Key = runtime::get_named_arg(consts::TO_RUNTIME_ARG_NAME);
+    let to: Address = helpers::key_to_address(to_key);
+    let dead_line: U256 = runtime::get_named_arg(consts::DEAD_LINE_RUNTIME_ARG_NAME);
+
+    if dead_line < U256::from(u64::from(runtime::get_blocktime())) {
+        runtime::revert(error::Error::Expired);
+    }
 
     let mut path: Vec<ContractHash> = Vec::new();
     for i in 0..path_key.len() {
@@ -563,7 +1299,136 @@ pub extern "C" fn swap_exact_tokens_for_tokens() {
         amount_in: (*amounts.get(0).unwrap_or_revert()),
         amount_out: { *amounts.last().unwrap_or_revert() },
         path,
-        recipient: to.as_account_hash().unwrap().to_formatted_string(),
+        recipient: to,
+    };
+    helpers::emit(&event);
+}
+
+/// Swaps along several `(path, amount_in)` legs sharing their first and last token, checking
+/// `amount_out_min` against the sum of their outputs.
+///
+/// Legs run one after the other, each priced against the reserves the previous ones left, so legs
+/// sharing a pair stay exact.
+#[no_mangle]
+pub extern "C" fn swap_exact_tokens_for_tokens_split() {
+    let legs_arg: Vec<(Vec<Key>, U256)> = runtime::get_named_arg(consts::LEGS_RUNTIME_ARG_NAME);
+    let amount_out_min: U256 = runtime::get_named_arg(consts::AMOUNT_OUT_MIN_RUNTIME_ARG_NAME);
+    let to_key: Key = runtime::get_named_arg(consts::TO_RUNTIME_ARG_NAME);
+    let to: Address = helpers::key_to_address(to_key);
+    let dead_line: U256 = runtime::get_named_arg(consts::DEAD_LINE_RUNTIME_ARG_NAME);
+
+    if dead_line < U256::from(u64::from(runtime::get_blocktime())) {
+        runtime::revert(error::Error::Expired);
+    }
+    if legs_arg.is_empty() {
+        runtime::revert(error::Error::InvalidPath);
+    }
+
+    let router = SwapperyRouter::default();
+    let caller: Address = helpers::get_immediate_caller_address().unwrap_or_revert();
+    let mut ends: Option<(ContractHash, ContractHash)> = None;
+    let mut legs: Vec<(Vec<ContractHash>, U256, U256)> = Vec::with_capacity(legs_arg.len());
+    let mut total_in: U256 = U256::zero();
+    let mut total_out: U256 = U256::zero();
+    for (path_key, amount_in) in legs_arg {
+        let path: Vec<ContractHash> = path_key
+            .iter()
+            .map(|key| ContractHash::new(key.into_hash().unwrap_or_revert()))
+            .collect();
+        let (first, last) = match (path.first(), path.last()) {
+            (Some(first), Some(last)) => (*first, *last),
+            _ => runtime::revert(error::Error::InvalidPath),
+        };
+        if *ends.get_or_insert((first, last)) != (first, last) {
+            runtime::revert(error::Error::InvalidPath);
+        }
+
+        let amounts: Vec<U256> = router.get_amounts_out(amount_in, path.clone());
+        runtime::call_contract::<()>(
+            first,
+            TRANSFER_FROM_ENTRY_POINT_NAME,
+            runtime_args! {
+                OWNER_RUNTIME_ARG_NAME => caller,
+                RECIPIENT_RUNTIME_ARG_NAME => router.get_pair_for(
+                    first,
+                    *path.get(1).unwrap_or_revert(),
+                ),
+                AMOUNT_RUNTIME_ARG_NAME => amount_in,
+            },
+        );
+        router._swap(amounts.clone(), path.clone(), to);
+
+        let amount_out: U256 = *amounts.last().unwrap_or_revert();
+        total_in = total_in
+            .checked_add(amount_in)
+            .unwrap_or_revert_with(error::Error::Overflow);
+        total_out = total_out
+            .checked_add(amount_out)
+            .unwrap_or_revert_with(error::Error::Overflow);
+        legs.push((path, amount_in, amount_out));
+    }
+
+    if total_out < amount_out_min {
+        runtime::revert(error::Error::InsufficientOutputAmount);
+    }
+    let event = event::RouterEvent::SwapSplit {
+        legs,
+        amount_in: total_in,
+        amount_out: total_out,
+        recipient: to,
+    };
+    helpers::emit(&event);
+}
+
+/// Swaps `amount_in` of `token_in` for `token_out` along the best path found at execution time.
+#[no_mangle]
+pub extern "C" fn swap_exact_tokens_for_tokens_best() {
+    let token_in_key: Key = runtime::get_named_arg(consts::TOKEN_IN_RUNTIME_ARG_NAME);
+    let token_out_key: Key = runtime::get_named_arg(consts::TOKEN_OUT_RUNTIME_ARG_NAME);
+    let amount_in: U256 = runtime::get_named_arg(consts::AMOUNT_IN_RUNTIME_ARG_NAME);
+    let amount_out_min: U256 = runtime::get_named_arg(consts::AMOUNT_OUT_MIN_RUNTIME_ARG_NAME);
+    let intermediates_key: Vec<Key> = runtime::get_named_arg(consts::INTERMEDIATES_RUNTIME_ARG_NAME);
+    let to_key: Key = runtime::get_named_arg(consts::TO_RUNTIME_ARG_NAME);
+    let to: Address = helpers::key_to_address(to_key);
+    let dead_line: U256 = runtime::get_named_arg(consts::DEAD_LINE_RUNTIME_ARG_NAME);
+
+    if dead_line < U256::from(u64::from(runtime::get_blocktime())) {
+        runtime::revert(error::Error::Expired);
+    }
+
+    let token_in: ContractHash = ContractHash::new(token_in_key.into_hash().unwrap_or_revert());
+    let token_out: ContractHash = ContractHash::new(token_out_key.into_hash().unwrap_or_revert());
+    let intermediates: Vec<ContractHash> = intermediates_key
+        .iter()
+        .map(|key| ContractHash::new(key.into_hash().unwrap_or_revert()))
+        .collect();
+
+    let router = SwapperyRouter::default();
+    // The amounts were quoted from the reserves the swap runs against, so they are reused as is.
+    let (amounts, path) = router
+        .get_best_amount_out(token_in, token_out, amount_in, intermediates)
+        .unwrap_or_revert_with(error::Error::NoRoute);
+
+    if !(amounts.last().unwrap_or_revert() >= &amount_out_min) {
+        runtime::revert(error::Error::InsufficientOutputAmount);
+    }
+
+    let caller: Address = helpers::get_immediate_caller_address().unwrap_or_revert();
+    runtime::call_contract::<()>(
+        token_in,
+        TRANSFER_FROM_ENTRY_POINT_NAME,
+        runtime_args! {
+            OWNER_RUNTIME_ARG_NAME => caller,
+            RECIPIENT_RUNTIME_ARG_NAME => router.get_pair_for(token_in, *path.get(1).unwrap_or_revert()),
+            AMOUNT_RUNTIME_ARG_NAME => amount_in,
+        },
+    );
+    router._swap(amounts.clone(), path.clone(), to);
+    let event = event::RouterEvent::SwapExactIn {
+        amount_in,
+        amount_out: *amounts.last().unwrap_or_revert(),
+        path,
+        recipient: to,
     };
     helpers::emit(&event);
 }
@@ -574,12 +1439,12 @@ pub extern "C" fn swap_tokens_for_exact_tokens() {
     let amount_in_max: U256 = runtime::get_named_arg(consts::AMOUNT_IN_MAX_RUNTIME_ARG_NAME);
     let path_key: Vec<Key> = runtime::get_named_arg(consts::PATH_RUNTIME_ARG_NAME);
     let to_key: Key = runtime::get_named_arg(consts::TO_RUNTIME_ARG_NAME);
-    let to = Address::from(AccountHash::new(to_key.into_hash().unwrap_or_revert()));
-    // let dead_line: U256 = runtime::get_named_arg(consts::DEAD_LINE_RUNTIME_ARG_NAME);
+    let to: Address = helpers::key_to_address(to_key);
+    let dead_line: U256 = runtime::get_named_arg(consts::DEAD_LINE_RUNTIME_ARG_NAME);
 
-    // if dead_line < U256::from(u64::from(runtime::get_blocktime())) {
-    //     runtime::revert(error::Error::Expired);
-    // }
+    if dead_line < U256::from(u64::from(runtime::get_blocktime())) {
+        runtime::revert(error::Error::Expired);
+    }
 
     let mut path: Vec<ContractHash> = Vec::new();
     for i in 0..path_key.len() {
@@ -613,7 +1478,238 @@ pub extern "C" fn swap_tokens_for_exact_tokens() {
         amount_in: (*amounts.get(0).unwrap_or_revert()),
         amount_out: { *amounts.last().unwrap_or_revert() },
         path,
-        recipient: to.as_account_hash().unwrap().to_formatted_string(),
+        recipient: to,
+    };
+    helpers::emit(&event);
+}
+
+/// Swaps exactly `amount_in` motes from `purse`, wrapped into WCSPR, for tokens along `path`.
+#[no_mangle]
+pub extern "C" fn swap_exact_cspr_for_tokens() {
+    let amount_in: U256 = runtime::get_named_arg(consts::AMOUNT_IN_RUNTIME_ARG_NAME);
+    let amount_out_min: U256 = runtime::get_named_arg(consts::AMOUNT_OUT_MIN_RUNTIME_ARG_NAME);
+    let path_key: Vec<Key> = runtime::get_named_arg(consts::PATH_RUNTIME_ARG_NAME);
+    let to_key: Key = runtime::get_named_arg(consts::TO_RUNTIME_ARG_NAME);
+    let to: Address = helpers::key_to_address(to_key);
+    let purse: URef = runtime::get_named_arg(consts::PURSE_RUNTIME_ARG_NAME);
+    let dead_line: U256 = runtime::get_named_arg(consts::DEAD_LINE_RUNTIME_ARG_NAME);
+
+    if dead_line < U256::from(u64::from(runtime::get_blocktime())) {
+        runtime::revert(error::Error::Expired);
+    }
+
+    let mut path: Vec<ContractHash> = Vec::new();
+    for i in 0..path_key.len() {
+        path.push({
+            let _hash = path_key.get(i).unwrap().into_hash().unwrap_or_revert();
+            ContractHash::new(_hash)
+        });
+    }
+
+    let wcspr_token: ContractHash = SwapperyRouter::default().wcspr_token();
+    if !(path.first() == Some(&wcspr_token)) {
+        runtime::revert(error::Error::InvalidPath);
+    }
+
+    let amounts: Vec<U256> = SwapperyRouter::default().get_amounts_out(amount_in, path.clone());
+
+    if !(amounts.last().unwrap_or_revert() >= &amount_out_min) {
+        runtime::revert(error::Error::InsufficientOutputAmount);
+    }
+
+    SwapperyRouter::default().deposit_cspr(purse, *amounts.get(0).unwrap_or_revert());
+    runtime::call_contract::<()>(
+        wcspr_token,
+        consts::TRANSFER_ENTRY_POINT_NAME,
+        runtime_args! {
+            RECIPIENT_RUNTIME_ARG_NAME => SwapperyRouter::default().get_pair_for(
+                *path.get(0).unwrap_or_revert(),
+                *path.get(1).unwrap_or_revert(),
+            ),
+            AMOUNT_RUNTIME_ARG_NAME => *amounts.get(0).unwrap_or_revert(),
+        },
+    );
+    SwapperyRouter::default()._swap(amounts.clone(), path.clone(), to);
+    let event = event::RouterEvent::SwapExactIn {
+        amount_in: (*amounts.get(0).unwrap_or_revert()),
+        amount_out: { *amounts.last().unwrap_or_revert() },
+        path,
+        recipient: to,
+    };
+    helpers::emit(&event);
+}
+
+/// Swaps at most `amount_in_max` motes from `purse` for exactly `amount_out` tokens; whatever is
+/// not wrapped stays in `purse`.
+#[no_mangle]
+pub extern "C" fn swap_cspr_for_exact_tokens() {
+    let amount_out: U256 = runtime::get_named_arg(consts::AMOUNT_OUT_RUNTIME_ARG_NAME);
+    let amount_in_max: U256 = runtime::get_named_arg(consts::AMOUNT_IN_MAX_RUNTIME_ARG_NAME);
+    let path_key: Vec<Key> = runtime::get_named_arg(consts::PATH_RUNTIME_ARG_NAME);
+    let to_key: Key = runtime::get_named_arg(consts::TO_RUNTIME_ARG_NAME);
+    let to: Address = helpers::key_to_address(to_key);
+    let purse: URef = runtime::get_named_arg(consts::PURSE_RUNTIME_ARG_NAME);
+    let dead_line: U256 = runtime::get_named_arg(consts::DEAD_LINE_RUNTIME_ARG_NAME);
+
+    if dead_line < U256::from(u64::from(runtime::get_blocktime())) {
+        runtime::revert(error::Error::Expired);
+    }
+
+    let mut path: Vec<ContractHash> = Vec::new();
+    for i in 0..path_key.len() {
+        path.push({
+            let _hash = path_key.get(i).unwrap().into_hash().unwrap_or_revert();
+            ContractHash::new(_hash)
+        });
+    }
+
+    let wcspr_token: ContractHash = SwapperyRouter::default().wcspr_token();
+    if !(path.first() == Some(&wcspr_token)) {
+        runtime::revert(error::Error::InvalidPath);
+    }
+
+    let amounts: Vec<U256> = SwapperyRouter::default().get_amounts_in(amount_out, path.clone());
+
+    if !(amounts.get(0).unwrap_or_revert() <= &amount_in_max) {
+        runtime::revert(error::Error::InsufficientInputAmount);
+    }
+
+    SwapperyRouter::default().deposit_cspr(purse, *amounts.get(0).unwrap_or_revert());
+    runtime::call_contract::<()>(
+        wcspr_token,
+        consts::TRANSFER_ENTRY_POINT_NAME,
+        runtime_args! {
+            RECIPIENT_RUNTIME_ARG_NAME => SwapperyRouter::default().get_pair_for(
+                *path.get(0).unwrap_or_revert(),
+                *path.get(1).unwrap_or_revert(),
+            ),
+            AMOUNT_RUNTIME_ARG_NAME => *amounts.get(0).unwrap_or_revert(),
+        },
+    );
+    SwapperyRouter::default()._swap(amounts.clone(), path.clone(), to);
+    let event = event::RouterEvent::SwapExactOut {
+        amount_in: (*amounts.get(0).unwrap_or_revert()),
+        amount_out: { *amounts.last().unwrap_or_revert() },
+        path,
+        recipient: to,
+    };
+    helpers::emit(&event);
+}
+
+/// Swaps exactly `amount_in` tokens for WCSPR along `path` and pays it out as CSPR to `to`, which
+/// must be an account.
+#[no_mangle]
+pub extern "C" fn swap_exact_tokens_for_cspr() {
+    let amount_in: U256 = runtime::get_named_arg(consts::AMOUNT_IN_RUNTIME_ARG_NAME);
+    let amount_out_min: U256 = runtime::get_named_arg(consts::AMOUNT_OUT_MIN_RUNTIME_ARG_NAME);
+    let path_key: Vec<Key> = runtime::get_named_arg(consts::PATH_RUNTIME_ARG_NAME);
+    let to_key: Key = runtime::get_named_arg(consts::TO_RUNTIME_ARG_NAME);
+    let to: Address = helpers::key_to_account(to_key);
+    let dead_line: U256 = runtime::get_named_arg(consts::DEAD_LINE_RUNTIME_ARG_NAME);
+
+    if dead_line < U256::from(u64::from(runtime::get_blocktime())) {
+        runtime::revert(error::Error::Expired);
+    }
+
+    let mut path: Vec<ContractHash> = Vec::new();
+    for i in 0..path_key.len() {
+        path.push({
+            let _hash = path_key.get(i).unwrap().into_hash().unwrap_or_revert();
+            ContractHash::new(_hash)
+        });
+    }
+
+    if !(path.last() == Some(&SwapperyRouter::default().wcspr_token())) {
+        runtime::revert(error::Error::InvalidPath);
+    }
+
+    let amounts: Vec<U256> = SwapperyRouter::default().get_amounts_out(amount_in, path.clone());
+
+    if !(amounts.last().unwrap_or_revert() >= &amount_out_min) {
+        runtime::revert(error::Error::InsufficientOutputAmount);
+    }
+
+    let caller: Address = helpers::get_immediate_caller_address().unwrap_or_revert();
+    runtime::call_contract::<()>(
+        *path.get(0).unwrap_or_revert(),
+        TRANSFER_FROM_ENTRY_POINT_NAME,
+        runtime_args! {
+            OWNER_RUNTIME_ARG_NAME => caller,
+            RECIPIENT_RUNTIME_ARG_NAME => SwapperyRouter::default().get_pair_for(
+                *path.get(0).unwrap_or_revert(),
+                *path.get(1).unwrap_or_revert(),
+            ),
+            AMOUNT_RUNTIME_ARG_NAME => *amounts.get(0).unwrap_or_revert(),
+        },
+    );
+    // The last hop pays the router, which unwraps the WCSPR for `to`.
+    let router = Address::from(helpers::contract_package_hash());
+    SwapperyRouter::default()._swap(amounts.clone(), path.clone(), router);
+    SwapperyRouter::default().withdraw_cspr(*amounts.last().unwrap_or_revert(), to);
+    let event = event::RouterEvent::SwapExactIn {
+        amount_in: (*amounts.get(0).unwrap_or_revert()),
+        amount_out: { *amounts.last().unwrap_or_revert() },
+        path,
+        recipient: to,
+    };
+    helpers::emit(&event);
+}
+
+/// Swaps at most `amount_in_max` tokens for exactly `amount_out` WCSPR and pays it out as CSPR to
+/// `to`, which must be an account.
+#[no_mangle]
+pub extern "C" fn swap_tokens_for_exact_cspr() {
+    let amount_out: U256 = runtime::get_named_arg(consts::AMOUNT_OUT_RUNTIME_ARG_NAME);
+    let amount_in_max: U256 = runtime::get_named_arg(consts::AMOUNT_IN_MAX_RUNTIME_ARG_NAME);
+    let path_key: Vec<Key> = runtime::get_named_arg(consts::PATH_RUNTIME_ARG_NAME);
+    let to_key: Key = runtime::get_named_arg(consts::TO_RUNTIME_ARG_NAME);
+    let to: Address = helpers::key_to_account(to_key);
+    let dead_line: U256 = runtime::get_named_arg(consts::DEAD_LINE_RUNTIME_ARG_NAME);
+
+    if dead_line < U256::from(u64::from(runtime::get_blocktime())) {
+        runtime::revert(error::Error::Expired);
+    }
+
+    let mut path: Vec<ContractHash> = Vec::new();
+    for i in 0..path_key.len() {
+        path.push({
+            let _hash = path_key.get(i).unwrap().into_hash().unwrap_or_revert();
+            ContractHash::new(_hash)
+        });
+    }
+
+    if !(path.last() == Some(&SwapperyRouter::default().wcspr_token())) {
+        runtime::revert(error::Error::InvalidPath);
+    }
+
+    let amounts: Vec<U256> = SwapperyRouter::default().get_amounts_in(amount_out, path.clone());
+
+    if !(amounts.get(0).unwrap_or_revert() <= &amount_in_max) {
+        runtime::revert(error::Error::InsufficientInputAmount);
+    }
+
+    let caller: Address = helpers::get_immediate_caller_address().unwrap_or_revert();
+    runtime::call_contract::<()>(
+        *path.get(0).unwrap_or_revert(),
+        TRANSFER_FROM_ENTRY_POINT_NAME,
+        runtime_args! {
+            OWNER_RUNTIME_ARG_NAME => caller,
+            RECIPIENT_RUNTIME_ARG_NAME => SwapperyRouter::default().get_pair_for(
+                *path.get(0).unwrap_or_revert(),
+                *path.get(1).unwrap_or_revert(),
+            ),
+            AMOUNT_RUNTIME_ARG_NAME => *amounts.get(0).unwrap_or_revert(),
+        },
+    );
+    // The last hop pays the router, which unwraps the WCSPR for `to`.
+    let router = Address::from(helpers::contract_package_hash());
+    SwapperyRouter::default()._swap(amounts.clone(), path.clone(), router);
+    SwapperyRouter::default().withdraw_cspr(*amounts.last().unwrap_or_revert(), to);
+    let event = event::RouterEvent::SwapExactOut {
+        amount_in: (*amounts.get(0).unwrap_or_revert()),
+        amount_out: { *amounts.last().unwrap_or_revert() },
+        path,
+        recipient: to,
     };
     helpers::emit(&event);
 }
@@ -624,12 +1720,12 @@ pub extern "C" fn swap_exact_tokens_for_tokens_supporting_fee() {
     let amount_out_min: U256 = runtime::get_named_arg(consts::AMOUNT_OUT_MIN_RUNTIME_ARG_NAME);
     let path_key: Vec<Key> = runtime::get_named_arg(consts::PATH_RUNTIME_ARG_NAME);
     let to_key: Key = runtime::get_named_arg(consts::TO_RUNTIME_ARG_NAME);
-    let to = Address::from(AccountHash::new(to_key.into_hash().unwrap_or_revert()));
-    // let dead_line: U256 = runtime::get_named_arg(consts::DEAD_LINE_RUNTIME_ARG_NAME);
+    let to: Address = helpers::key_to_address(to_key);
+    let dead_line: U256 = runtime::get_named_arg(consts::DEAD_LINE_RUNTIME_ARG_NAME);
 
-    // if dead_line < U256::from(u64::from(runtime::get_blocktime())) {
-    //     runtime::revert(error::Error::Expired);
-    // }
+    if dead_line < U256::from(u64::from(runtime::get_blocktime())) {
+        runtime::revert(error::Error::Expired);
+    }
 
     let mut path: Vec<ContractHash> = Vec::new();
     for i in 0..path_key.len() {
@@ -669,24 +1765,26 @@ pub extern "C" fn swap_exact_tokens_for_tokens_supporting_fee() {
             consts::ADDRESS_RUNTIME_ARG_NAME => to,
         },
     );
-    if (balance_after - balance_before) < amount_out_min {
+    // A balance that shrank, as a rebasing token's can, received nothing.
+    let received: U256 = balance_after
+        .checked_sub(balance_before)
+        .unwrap_or_revert_with(error::Error::InsufficientOutputAmount);
+    if received < amount_out_min {
         runtime::revert(error::Error::InsufficientOutputAmount);
     }
 }
 
 #[no_mangle]
 fn call() {
-    // let feeto = Address::Account(runtime::get_named_arg(consts::FEETO_KEY_NAME));
-    // let feeto_setter = Address::Account(runtime::get_named_arg(consts::FEETO_SETTER_KEY_NAME));
-    let wcspr_token_key: Key = runtime::get_named_arg(consts::WCSPR_CONTRACT_KEY_NAME);
+    // let feeto = Address::Account(runtime::get_named_arg(consts::FEETO_RUNTIME_ARG_NAME));
+    // let feeto_setter = Address::Account(runtime::get_named_arg(consts::FEETO_SETTER_RUNTIME_ARG_NAME));
+    let wcspr_token_key: Key = runtime::get_named_arg(consts::WCSPR_TOKEN_RUNTIME_ARG_NAME);
     let wcspr_token = ContractHash::new(wcspr_token_key.into_hash().unwrap_or_revert());
-    let feeto_key: Key = runtime::get_named_arg(consts::FEETO_KEY_NAME);
-    let feeto = Address::from(AccountHash::new(feeto_key.into_hash().unwrap_or_revert()));
-    let feeto_setter_key: Key = runtime::get_named_arg(consts::FEETO_SETTER_KEY_NAME);
-    let feeto_setter = Address::from(AccountHash::new(
-        feeto_setter_key.into_hash().unwrap_or_revert(),
-    ));
-    let contract_key_name: String = runtime::get_named_arg(consts::CONTRACT_KEY_NAME_ARG_NAME);
+    let feeto_key: Key = runtime::get_named_arg(consts::FEETO_RUNTIME_ARG_NAME);
+    let feeto: Address = helpers::key_to_address(feeto_key);
+    let feeto_setter_key: Key = runtime::get_named_arg(consts::FEETO_SETTER_RUNTIME_ARG_NAME);
+    let feeto_setter: Address = helpers::key_to_address(feeto_setter_key);
+    let contract_key_name: String = runtime::get_named_arg(consts::CONTRACT_KEY_NAME_RUNTIME_ARG_NAME);
 
     let _ = SwapperyRouter::create(feeto, feeto_setter, wcspr_token, contract_key_name);
 }
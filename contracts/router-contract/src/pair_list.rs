@@ -1,4 +1,7 @@
-use alloc::{string::String, vec::Vec};
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
 
 use casper_contract::{
     contract_api::{runtime, storage},
@@ -10,12 +13,31 @@ use casper_erc20::Address;
 
 use crate::helpers::get_uref;
 
-use crate::constants::PAIR_LIST_KEY_NAME;
+use crate::constants::{
+    ALL_PAIRS_KEY_NAME, ALL_PAIRS_LENGTH_KEY_NAME, PAIR_LIST_KEY_NAME, TOKEN_PAIRS_KEY_NAME,
+    TOKEN_PAIRS_LENGTH_KEY_NAME,
+};
 
 pub(crate) fn get_pair_list_uref() -> URef {
     get_uref(PAIR_LIST_KEY_NAME)
 }
 
+pub(crate) fn get_all_pairs_uref() -> URef {
+    get_uref(ALL_PAIRS_KEY_NAME)
+}
+
+pub(crate) fn get_all_pairs_length_uref() -> URef {
+    get_uref(ALL_PAIRS_LENGTH_KEY_NAME)
+}
+
+pub(crate) fn get_token_pairs_uref() -> URef {
+    get_uref(TOKEN_PAIRS_KEY_NAME)
+}
+
+pub(crate) fn get_token_pairs_length_uref() -> URef {
+    get_uref(TOKEN_PAIRS_LENGTH_KEY_NAME)
+}
+
 fn make_dictionary_item_key(token0: ContractHash, token1: ContractHash) -> String {
     let token0_key = Key::Hash(token0.value());
     let token1_key = Key::Hash(token1.value());
@@ -39,13 +61,107 @@ pub(crate) fn add_pair_for(
     storage::dictionary_put(pair_list_uref, &dictionary_item_key, pair_address);
 }
 
+pub(crate) fn try_get_pair_for(
+    pair_list_uref: URef,
+    token0: ContractHash,
+    token1: ContractHash,
+) -> Option<Address> {
+    let dictionary_item_key = make_dictionary_item_key(token0, token1);
+    storage::dictionary_get(pair_list_uref, &dictionary_item_key).unwrap_or_revert()
+}
+
 pub(crate) fn get_pair_for(
     pair_list_uref: URef,
     token0: ContractHash,
     token1: ContractHash,
 ) -> Address {
-    let dictionary_item_key = make_dictionary_item_key(token0, token1);
-    storage::dictionary_get(pair_list_uref, &dictionary_item_key)
+    try_get_pair_for(pair_list_uref, token0, token1).unwrap_or_revert()
+}
+
+/// Key of the number of pairs `token` is part of, in the `token_pairs_length` dictionary.
+fn make_token_length_item_key(token: ContractHash) -> String {
+    hex::encode(token.value())
+}
+
+/// Key of the `index`th pair `token` is part of, in the `token_pairs` dictionary.
+fn make_token_pair_item_key(token: ContractHash, index: u64) -> String {
+    let mut preimage = Vec::new();
+    preimage.append(&mut Key::Hash(token.value()).to_bytes().unwrap_or_revert());
+    preimage.append(&mut index.to_bytes().unwrap_or_revert());
+
+    let key_bytes = runtime::blake2b(&preimage);
+    hex::encode(&key_bytes)
+}
+
+pub(crate) fn read_all_pairs_length_from(all_pairs_length_uref: URef) -> u64 {
+    storage::read(all_pairs_length_uref)
         .unwrap_or_revert()
         .unwrap_or_revert()
 }
+
+pub(crate) fn get_pair_at(all_pairs_uref: URef, index: u64) -> Option<Address> {
+    storage::dictionary_get(all_pairs_uref, &index.to_string()).unwrap_or_revert()
+}
+
+fn read_token_pairs_length(token_pairs_length_uref: URef, token: ContractHash) -> u64 {
+    storage::dictionary_get(token_pairs_length_uref, &make_token_length_item_key(token))
+        .unwrap_or_revert()
+        .unwrap_or_default()
+}
+
+fn push_token_pair(
+    token_pairs_uref: URef,
+    token_pairs_length_uref: URef,
+    token: ContractHash,
+    pair_address: Address,
+) {
+    let length = read_token_pairs_length(token_pairs_length_uref, token);
+    storage::dictionary_put(
+        token_pairs_uref,
+        &make_token_pair_item_key(token, length),
+        pair_address,
+    );
+    storage::dictionary_put(
+        token_pairs_length_uref,
+        &make_token_length_item_key(token),
+        length + 1,
+    );
+}
+
+/// Appends `pair_address` to the list of all pairs and to the pairs of both its tokens.
+pub(crate) fn push_pair(
+    all_pairs_uref: URef,
+    all_pairs_length_uref: URef,
+    token_pairs_uref: URef,
+    token_pairs_length_uref: URef,
+    token0: ContractHash,
+    token1: ContractHash,
+    pair_address: Address,
+) {
+    let length = read_all_pairs_length_from(all_pairs_length_uref);
+    storage::dictionary_put(all_pairs_uref, &length.to_string(), pair_address);
+    storage::write(all_pairs_length_uref, length + 1);
+
+    push_token_pair(token_pairs_uref, token_pairs_length_uref, token0, pair_address);
+    push_token_pair(token_pairs_uref, token_pairs_length_uref, token1, pair_address);
+}
+
+/// Returns at most `limit` of the pairs `token` is part of, starting from the `offset`th one in
+/// creation order.
+pub(crate) fn get_pairs_for_token(
+    token_pairs_uref: URef,
+    token_pairs_length_uref: URef,
+    token: ContractHash,
+    offset: u64,
+    limit: u64,
+) -> Vec<Address> {
+    let length = read_token_pairs_length(token_pairs_length_uref, token);
+    let end = offset.saturating_add(limit).min(length);
+    (offset..end)
+        .map(|index| {
+            storage::dictionary_get(token_pairs_uref, &make_token_pair_item_key(token, index))
+                .unwrap_or_revert()
+                .unwrap_or_revert()
+        })
+        .collect()
+}
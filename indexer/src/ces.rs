@@ -0,0 +1,89 @@
+//! Finding events among the writes of an execution.
+//!
+//! A contract's events live in its `__events` dictionary keyed by index, each one serialized as
+//! its `event_`-prefixed name followed by its fields. Every dictionary write stores the value
+//! together with the address of the dictionary's seed [`URef`](casper_types::URef) and the item
+//! key, so a write carries both the event and the dictionary, hence the contract, it belongs to.
+
+use casper_types::{
+    bytesrepr::{Bytes, FromBytes},
+    CLType, CLValue, Key, URefAddr,
+};
+
+use crate::event::{CesEvent, Event};
+
+pub const EVENTS_DICT: &str = "__events";
+pub const EVENTS_LENGTH: &str = "__events_length";
+pub const EVENTS_SCHEMA: &str = "__events_schema";
+pub const CES_VERSION_KEY: &str = "__events_ces_version";
+pub const EVENT_PREFIX: &str = "event_";
+
+/// An event as written to a contract's events dictionary.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RawEvent {
+    /// Address of the events dictionary, which identifies the emitting contract.
+    pub source: URefAddr,
+    /// Position of the event among those of its contract.
+    pub index: u32,
+    pub name: String,
+    pub fields: Vec<u8>,
+}
+
+/// A decoded event and where it was emitted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Emitted {
+    pub source: URefAddr,
+    pub index: u32,
+    pub event: Event,
+}
+
+/// Reads the event a write of `stored_bytes` under `key` puts into an events dictionary.
+///
+/// `stored_bytes` are the inner bytes of the written [`CLValue`]. Returns `None` for writes that
+/// are not of a dictionary item holding an event.
+pub fn raw_event(key: &Key, stored_bytes: &[u8]) -> Option<RawEvent> {
+    if !matches!(key, Key::Dictionary(_)) {
+        return None;
+    }
+    let (value, bytes) = CLValue::from_bytes(stored_bytes).ok()?;
+    let (seed_uref_addr, bytes) = Bytes::from_bytes(bytes).ok()?;
+    let (item_key, bytes) = Bytes::from_bytes(bytes).ok()?;
+    if !bytes.is_empty() || *value.cl_type() != CLType::List(Box::new(CLType::U8)) {
+        return None;
+    }
+
+    let source: URefAddr = seed_uref_addr[..].try_into().ok()?;
+    let index: u32 = std::str::from_utf8(&item_key).ok()?.parse().ok()?;
+    let event: Bytes = value.into_t().ok()?;
+    let (name, fields) = String::from_bytes(&event).ok()?;
+    let name = name.strip_prefix(EVENT_PREFIX)?.to_string();
+    Some(RawEvent {
+        source,
+        index,
+        name,
+        fields: fields.to_vec(),
+    })
+}
+
+/// Decodes a [`RawEvent`] of the router or a pair, if it is one.
+pub fn decode(raw: &RawEvent) -> Option<Emitted> {
+    match Event::decode(&raw.name, &raw.fields) {
+        Ok((event, remainder)) if remainder.is_empty() => Some(Emitted {
+            source: raw.source,
+            index: raw.index,
+            event,
+        }),
+        _ => None,
+    }
+}
+
+/// Decodes the Swappery events among `writes`, ordered by contract and index.
+pub fn events_in<'a>(writes: impl IntoIterator<Item = (&'a Key, &'a [u8])>) -> Vec<Emitted> {
+    let mut events: Vec<Emitted> = writes
+        .into_iter()
+        .filter_map(|(key, bytes)| raw_event(key, bytes))
+        .filter_map(|raw| decode(&raw))
+        .collect();
+    events.sort_by_key(|emitted| (emitted.source, emitted.index));
+    events
+}
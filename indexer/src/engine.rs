@@ -0,0 +1,34 @@
+//! Events out of the execution engine's transforms, e.g. those of `InMemoryWasmTestBuilder`.
+
+use casper_execution_engine::shared::{
+    additive_map::AdditiveMap, stored_value::StoredValue, transform::Transform,
+};
+use casper_types::Key;
+
+use crate::ces::{self, Emitted};
+use crate::Execution;
+
+/// Decodes the Swappery events written by one execution's `transforms`.
+pub fn events_in_transforms(transforms: &AdditiveMap<Key, Transform>) -> Vec<Emitted> {
+    let writes = transforms
+        .iter()
+        .filter_map(|(key, transform)| match transform {
+            Transform::Write(StoredValue::CLValue(cl_value)) => {
+                Some((key, &cl_value.inner_bytes()[..]))
+            }
+            _ => None,
+        });
+    ces::events_in(writes)
+}
+
+/// Returns an [`Execution`] per entry of `transforms`, such as
+/// `InMemoryWasmTestBuilder::get_transforms`.
+pub fn executions(transforms: &[AdditiveMap<Key, Transform>]) -> Vec<Execution> {
+    transforms
+        .iter()
+        .map(|transforms| Execution {
+            deploy_hash: None,
+            events: events_in_transforms(transforms),
+        })
+        .collect()
+}
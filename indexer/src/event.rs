@@ -0,0 +1,266 @@
+//! Typed Swappery events, decoded from the fields following their CES name.
+
+use casper_erc20::Address;
+use casper_types::{
+    bytesrepr::{self, FromBytes},
+    ContractHash, URef, U256,
+};
+
+/// An event decodable from the fields following its name.
+pub trait CesEvent: Sized {
+    fn decode(name: &str, fields: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error>;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RouterEvent {
+    /// Creation of `pair`, whose events are written to the `events` dictionary.
+    CreatePair {
+        token0: ContractHash,
+        token1: ContractHash,
+        pair: Address,
+        events: URef,
+    },
+    AddLiquidity {
+        token0: ContractHash,
+        token1: ContractHash,
+        amount0: U256,
+        amount1: U256,
+        recipient: Address,
+    },
+    RemoveLiquidity {
+        token0: ContractHash,
+        token1: ContractHash,
+        liquidity: U256,
+        recipient: Address,
+    },
+    SwapExactIn {
+        amount_in: U256,
+        amount_out: U256,
+        path: Vec<ContractHash>,
+        recipient: Address,
+    },
+    SwapExactOut {
+        amount_in: U256,
+        amount_out: U256,
+        path: Vec<ContractHash>,
+        recipient: Address,
+    },
+    SwapSplit {
+        legs: Vec<(Vec<ContractHash>, U256, U256)>,
+        amount_in: U256,
+        amount_out: U256,
+        recipient: Address,
+    },
+    Installed {
+        contract_hash: ContractHash,
+    },
+}
+
+impl CesEvent for RouterEvent {
+    fn decode(name: &str, bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        match name {
+            "create_pair" => {
+                let (token0, bytes) = ContractHash::from_bytes(bytes)?;
+                let (token1, bytes) = ContractHash::from_bytes(bytes)?;
+                let (pair, bytes) = Address::from_bytes(bytes)?;
+                let (events, bytes) = URef::from_bytes(bytes)?;
+                let event = RouterEvent::CreatePair {
+                    token0,
+                    token1,
+                    pair,
+                    events,
+                };
+                Ok((event, bytes))
+            }
+            "add_liquidity" => {
+                let (token0, bytes) = ContractHash::from_bytes(bytes)?;
+                let (token1, bytes) = ContractHash::from_bytes(bytes)?;
+                let (amount0, bytes) = U256::from_bytes(bytes)?;
+                let (amount1, bytes) = U256::from_bytes(bytes)?;
+                let (recipient, bytes) = Address::from_bytes(bytes)?;
+                let event = RouterEvent::AddLiquidity {
+                    token0,
+                    token1,
+                    amount0,
+                    amount1,
+                    recipient,
+                };
+                Ok((event, bytes))
+            }
+            "remove_liquidity" => {
+                let (token0, bytes) = ContractHash::from_bytes(bytes)?;
+                let (token1, bytes) = ContractHash::from_bytes(bytes)?;
+                let (liquidity, bytes) = U256::from_bytes(bytes)?;
+                let (recipient, bytes) = Address::from_bytes(bytes)?;
+                let event = RouterEvent::RemoveLiquidity {
+                    token0,
+                    token1,
+                    liquidity,
+                    recipient,
+                };
+                Ok((event, bytes))
+            }
+            "swap_exact_in" | "swap_exact_out" => {
+                let (amount_in, bytes) = U256::from_bytes(bytes)?;
+                let (amount_out, bytes) = U256::from_bytes(bytes)?;
+                let (path, bytes) = Vec::<ContractHash>::from_bytes(bytes)?;
+                let (recipient, bytes) = Address::from_bytes(bytes)?;
+                let event = if name == "swap_exact_in" {
+                    RouterEvent::SwapExactIn { amount_in, amount_out, path, recipient }
+                } else {
+                    RouterEvent::SwapExactOut { amount_in, amount_out, path, recipient }
+                };
+                Ok((event, bytes))
+            }
+            "swap_split" => {
+                let (legs, bytes) = Vec::<(Vec<ContractHash>, U256, U256)>::from_bytes(bytes)?;
+                let (amount_in, bytes) = U256::from_bytes(bytes)?;
+                let (amount_out, bytes) = U256::from_bytes(bytes)?;
+                let (recipient, bytes) = Address::from_bytes(bytes)?;
+                let event = RouterEvent::SwapSplit {
+                    legs,
+                    amount_in,
+                    amount_out,
+                    recipient,
+                };
+                Ok((event, bytes))
+            }
+            "installed" => {
+                let (contract_hash, bytes) = ContractHash::from_bytes(bytes)?;
+                Ok((RouterEvent::Installed { contract_hash }, bytes))
+            }
+            _ => Err(bytesrepr::Error::Formatting),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PairEvent {
+    Mint {
+        sender: Address,
+        amount0: U256,
+        amount1: U256,
+        to: Address,
+        liquidity: U256,
+    },
+    Burn {
+        sender: Address,
+        amount0: U256,
+        amount1: U256,
+        to: Address,
+        liquidity: U256,
+    },
+    Swap {
+        sender: Address,
+        amount0_in: U256,
+        amount1_in: U256,
+        amount0_out: U256,
+        amount1_out: U256,
+        to: Address,
+    },
+    Sync {
+        reserve0: U256,
+        reserve1: U256,
+    },
+    MintFee {
+        fee_to: Address,
+        liquidity: U256,
+    },
+    SetSwapFee {
+        swap_fee_bps: u32,
+    },
+}
+
+impl CesEvent for PairEvent {
+    fn decode(name: &str, bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        match name {
+            "mint" | "burn" => {
+                let (sender, bytes) = Address::from_bytes(bytes)?;
+                let (amount0, bytes) = U256::from_bytes(bytes)?;
+                let (amount1, bytes) = U256::from_bytes(bytes)?;
+                let (to, bytes) = Address::from_bytes(bytes)?;
+                let (liquidity, bytes) = U256::from_bytes(bytes)?;
+                let event = if name == "mint" {
+                    PairEvent::Mint { sender, amount0, amount1, to, liquidity }
+                } else {
+                    PairEvent::Burn { sender, amount0, amount1, to, liquidity }
+                };
+                Ok((event, bytes))
+            }
+            "swap" => {
+                let (sender, bytes) = Address::from_bytes(bytes)?;
+                let (amount0_in, bytes) = U256::from_bytes(bytes)?;
+                let (amount1_in, bytes) = U256::from_bytes(bytes)?;
+                let (amount0_out, bytes) = U256::from_bytes(bytes)?;
+                let (amount1_out, bytes) = U256::from_bytes(bytes)?;
+                let (to, bytes) = Address::from_bytes(bytes)?;
+                let event = PairEvent::Swap {
+                    sender,
+                    amount0_in,
+                    amount1_in,
+                    amount0_out,
+                    amount1_out,
+                    to,
+                };
+                Ok((event, bytes))
+            }
+            "sync" => {
+                let (reserve0, bytes) = U256::from_bytes(bytes)?;
+                let (reserve1, bytes) = U256::from_bytes(bytes)?;
+                Ok((PairEvent::Sync { reserve0, reserve1 }, bytes))
+            }
+            "mint_fee" => {
+                let (fee_to, bytes) = Address::from_bytes(bytes)?;
+                let (liquidity, bytes) = U256::from_bytes(bytes)?;
+                Ok((PairEvent::MintFee { fee_to, liquidity }, bytes))
+            }
+            "set_swap_fee" => {
+                let (swap_fee_bps, bytes) = u32::from_bytes(bytes)?;
+                Ok((PairEvent::SetSwapFee { swap_fee_bps }, bytes))
+            }
+            _ => Err(bytesrepr::Error::Formatting),
+        }
+    }
+}
+
+/// An event of either contract.
+///
+/// Router and pair event names are disjoint, so the name alone tells which contract emitted it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Event {
+    Router(RouterEvent),
+    Pair(PairEvent),
+}
+
+impl CesEvent for Event {
+    fn decode(name: &str, bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        match RouterEvent::decode(name, bytes) {
+            Ok((event, bytes)) => Ok((Event::Router(event), bytes)),
+            Err(_) => {
+                let (event, bytes) = PairEvent::decode(name, bytes)?;
+                Ok((Event::Pair(event), bytes))
+            }
+        }
+    }
+}
+
+impl Event {
+    /// Returns the event's CES name, without the `event_` prefix.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Event::Router(RouterEvent::CreatePair { .. }) => "create_pair",
+            Event::Router(RouterEvent::AddLiquidity { .. }) => "add_liquidity",
+            Event::Router(RouterEvent::RemoveLiquidity { .. }) => "remove_liquidity",
+            Event::Router(RouterEvent::SwapExactIn { .. }) => "swap_exact_in",
+            Event::Router(RouterEvent::SwapExactOut { .. }) => "swap_exact_out",
+            Event::Router(RouterEvent::SwapSplit { .. }) => "swap_split",
+            Event::Router(RouterEvent::Installed { .. }) => "installed",
+            Event::Pair(PairEvent::Mint { .. }) => "mint",
+            Event::Pair(PairEvent::Burn { .. }) => "burn",
+            Event::Pair(PairEvent::Swap { .. }) => "swap",
+            Event::Pair(PairEvent::Sync { .. }) => "sync",
+            Event::Pair(PairEvent::MintFee { .. }) => "mint_fee",
+            Event::Pair(PairEvent::SetSwapFee { .. }) => "set_swap_fee",
+        }
+    }
+}
@@ -0,0 +1,163 @@
+//! Events out of execution results saved as JSON.
+//!
+//! Accepts the response of `casper-client get-deploy`, with or without its JSON-RPC envelope, as
+//! well as a bare execution result. Only successful executions are read; a failed deploy's writes
+//! are reverted and its events never happened.
+
+use casper_types::Key;
+use serde_json::Value;
+
+use crate::ces::{self, Emitted};
+use crate::Execution;
+
+/// Failure to read saved execution results.
+#[derive(Debug)]
+pub enum JsonError {
+    /// The document holds no execution result.
+    NoExecutionResults,
+    /// A transform's key or written bytes are malformed.
+    InvalidTransform(String),
+}
+
+impl std::fmt::Display for JsonError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            JsonError::NoExecutionResults => write!(f, "no execution results"),
+            JsonError::InvalidTransform(reason) => write!(f, "invalid transform: {}", reason),
+        }
+    }
+}
+
+impl std::error::Error for JsonError {}
+
+/// Returns an [`Execution`] per successful execution result in `document`.
+pub fn executions(document: &Value) -> Result<Vec<Execution>, JsonError> {
+    let document = document.get("result").unwrap_or(document);
+    let deploy_hash = document
+        .pointer("/deploy/hash")
+        .and_then(Value::as_str)
+        .map(String::from);
+
+    let results: Vec<&Value> = match document.get("execution_results") {
+        Some(Value::Array(results)) => results
+            .iter()
+            .map(|result| result.get("result").unwrap_or(result))
+            .collect(),
+        _ if document.get("Success").is_some() || document.get("Failure").is_some() => {
+            vec![document]
+        }
+        _ => return Err(JsonError::NoExecutionResults),
+    };
+
+    let mut executions = Vec::new();
+    for result in results {
+        if let Some(transforms) = result.pointer("/Success/effect/transforms") {
+            executions.push(Execution {
+                deploy_hash: deploy_hash.clone(),
+                events: events_in_transforms(transforms)?,
+            });
+        }
+    }
+    Ok(executions)
+}
+
+/// Decodes the Swappery events among a JSON array of transforms.
+pub fn events_in_transforms(transforms: &Value) -> Result<Vec<Emitted>, JsonError> {
+    let mut writes: Vec<(Key, Vec<u8>)> = Vec::new();
+    for entry in transforms.as_array().into_iter().flatten() {
+        let key = entry.get("key").and_then(Value::as_str);
+        let bytes = entry
+            .pointer("/transform/WriteCLValue/bytes")
+            .and_then(Value::as_str);
+        if let (Some(key), Some(bytes)) = (key, bytes) {
+            let key = Key::from_formatted_str(key)
+                .map_err(|error| JsonError::InvalidTransform(error.to_string()))?;
+            let bytes = hex::decode(bytes)
+                .map_err(|error| JsonError::InvalidTransform(error.to_string()))?;
+            writes.push((key, bytes));
+        }
+    }
+    Ok(ces::events_in(
+        writes.iter().map(|(key, bytes)| (key, bytes.as_slice())),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use casper_types::{
+        bytesrepr::{Bytes, ToBytes},
+        CLValue, U256,
+    };
+    use serde_json::json;
+
+    use super::*;
+    use crate::event::{Event, PairEvent};
+
+    /// Returns what a `dictionary_put` of `event` at `index` into the dictionary seeded by
+    /// `source` writes.
+    fn dictionary_write(source: [u8; 32], index: u32, name: &str, fields: &[u8]) -> String {
+        let mut event = format!("event_{}", name).to_bytes().unwrap();
+        event.extend_from_slice(fields);
+        let mut bytes = CLValue::from_t(Bytes::from(event))
+            .unwrap()
+            .to_bytes()
+            .unwrap();
+        bytes.append(&mut Bytes::from(source.to_vec()).to_bytes().unwrap());
+        bytes.append(&mut Bytes::from(index.to_string().into_bytes()).to_bytes().unwrap());
+        hex::encode(bytes)
+    }
+
+    #[test]
+    fn should_read_events_of_get_deploy_output() {
+        let mut fields = U256::from(40_000u64).to_bytes().unwrap();
+        fields.append(&mut U256::from(37_519u64).to_bytes().unwrap());
+        let write = |key: &str, bytes: String| {
+            json!({
+                "key": key,
+                "transform": {
+                    "WriteCLValue": { "cl_type": "Any", "bytes": bytes, "parsed": null }
+                }
+            })
+        };
+        let document = json!({
+            "jsonrpc": "2.0",
+            "result": {
+                "deploy": { "hash": "ab01" },
+                "execution_results": [{
+                    "block_hash": "cd02",
+                    "result": { "Success": { "effect": { "transforms": [
+                        write(&format!("dictionary-{}", "11".repeat(32)),
+                              dictionary_write([7; 32], 2, "sync", &fields)),
+                        write(&format!("dictionary-{}", "22".repeat(32)),
+                              dictionary_write([7; 32], 3, "unknown", &[])),
+                        { "key": format!("hash-{}", "33".repeat(32)), "transform": "Identity" },
+                    ] } } }
+                }]
+            }
+        });
+
+        let executions = executions(&document).unwrap();
+        assert_eq!(executions.len(), 1);
+        assert_eq!(executions[0].deploy_hash.as_deref(), Some("ab01"));
+        assert_eq!(
+            executions[0].events,
+            vec![Emitted {
+                source: [7; 32],
+                index: 2,
+                event: Event::Pair(PairEvent::Sync {
+                    reserve0: U256::from(40_000u64),
+                    reserve1: U256::from(37_519u64),
+                }),
+            }]
+        );
+    }
+
+    #[test]
+    fn should_skip_failed_executions() {
+        let document = json!({
+            "Failure": { "effect": { "transforms": [] }, "error_message": "" }
+        });
+        assert_eq!(executions(&document).unwrap(), vec![]);
+        assert!(executions(&json!({})).is_err());
+    }
+}
@@ -0,0 +1,24 @@
+//! Host-side decoding and indexing of the events of the Swappery router and pairs.
+//!
+//! [`ces`] finds events among the writes of an execution and decodes them into the types of
+//! [`event`]. Writes come from saved execution-result JSON through [`json`], or from the execution
+//! engine through `engine` with the `engine` feature. [`store`] folds the decoded events into
+//! per-pair volume and liquidity tables in SQLite.
+
+pub mod ces;
+#[cfg(feature = "engine")]
+pub mod engine;
+pub mod event;
+pub mod json;
+pub mod store;
+
+use ces::Emitted;
+
+/// The Swappery events of one execution.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Execution {
+    /// Hash of the deploy, when the source records it.
+    pub deploy_hash: Option<String>,
+    /// Events ordered by emitting contract and index.
+    pub events: Vec<Emitted>,
+}
@@ -0,0 +1,93 @@
+//! Folds saved execution results into the per-pair tables of an SQLite database.
+//!
+//! Each file holds the JSON of `casper-client get-deploy` or a bare execution result. Files can be
+//! ingested again safely; events already indexed are skipped.
+
+use std::fs;
+use std::path::PathBuf;
+use std::process;
+
+use clap::Parser;
+
+use swappery_indexer::json;
+use swappery_indexer::store::Store;
+
+#[derive(Debug, Parser)]
+#[clap(name = "swappery-indexer", version, about)]
+struct Cli {
+    /// SQLite database to index into, created if missing.
+    #[clap(long, default_value = "swappery.sqlite")]
+    db: PathBuf,
+
+    /// Print the per-pair tables once the files are ingested.
+    #[clap(long)]
+    report: bool,
+
+    /// Execution-result JSON files.
+    files: Vec<PathBuf>,
+}
+
+fn format_pair(pair: &Option<String>, tokens: &Option<(String, String)>) -> String {
+    match (pair, tokens) {
+        (Some(pair), Some((token0, token1))) => format!("{}: {} / {}", pair, token0, token1),
+        _ => String::from("creation not indexed"),
+    }
+}
+
+fn report(store: &Store) -> Result<(), Box<dyn std::error::Error>> {
+    for volume in store.pair_volumes()? {
+        println!(
+            "volume {} ({}): {} swaps, in {} / {}, out {} / {}",
+            volume.source,
+            format_pair(&volume.pair, &volume.tokens),
+            volume.swaps,
+            volume.amount0_in,
+            volume.amount1_in,
+            volume.amount0_out,
+            volume.amount1_out
+        );
+    }
+    for liquidity in store.pair_liquidity()? {
+        let total_supply = match liquidity.total_supply() {
+            Some(total_supply) => total_supply.to_string(),
+            None => String::from("unknown"),
+        };
+        println!(
+            "liquidity {} ({}): reserves {} / {}, supply {} ({} protocol fee), {} mints, {} burns",
+            liquidity.source,
+            format_pair(&liquidity.pair, &liquidity.tokens),
+            liquidity.reserve0,
+            liquidity.reserve1,
+            total_supply,
+            liquidity.protocol_fee,
+            liquidity.mints,
+            liquidity.burns
+        );
+    }
+    Ok(())
+}
+
+fn run(cli: Cli) -> Result<(), Box<dyn std::error::Error>> {
+    let mut store = Store::open(&cli.db)?;
+    for file in &cli.files {
+        let document: serde_json::Value = serde_json::from_slice(&fs::read(file)?)?;
+        let executions =
+            json::executions(&document).map_err(|error| format!("{}: {}", file.display(), error))?;
+        let mut ingested = 0;
+        for execution in &executions {
+            ingested += store.ingest(execution)?;
+        }
+        println!("{}: {} new events", file.display(), ingested);
+    }
+    if cli.report {
+        report(&store)?;
+    }
+    Ok(())
+}
+
+fn main() {
+    if let Err(error) = run(Cli::parse()) {
+        eprintln!("error: {}", error);
+        process::exit(1);
+    }
+}
@@ -0,0 +1,426 @@
+//! Per-pair volume and liquidity tables in SQLite.
+//!
+//! Pairs are keyed by the address of their events dictionary, the one thing every pair event
+//! carries; the router's `create_pair` event ties it to the pair's package and tokens. Amounts are
+//! stored as decimal text since they do not fit SQLite's integers. Each event is folded in once,
+//! however often the execution holding it is ingested, and executions can be ingested in any
+//! order: everything but the reserves is a sum or a count, and the reserves come from the latest
+//! sync.
+
+use std::path::Path;
+
+use casper_erc20::Address;
+use casper_types::U256;
+use rusqlite::types::{FromSql, FromSqlError, FromSqlResult, ToSql, ToSqlOutput, ValueRef};
+use rusqlite::{params, Connection, OptionalExtension, Transaction};
+
+use crate::event::{Event, PairEvent, RouterEvent};
+use crate::Execution;
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS events (
+    source TEXT NOT NULL,
+    idx INTEGER NOT NULL,
+    deploy_hash TEXT,
+    name TEXT NOT NULL,
+    PRIMARY KEY (source, idx)
+);
+CREATE TABLE IF NOT EXISTS router_pairs (
+    source TEXT PRIMARY KEY,
+    pair TEXT NOT NULL,
+    token0 TEXT NOT NULL,
+    token1 TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS pair_volume (
+    source TEXT PRIMARY KEY,
+    swaps INTEGER NOT NULL,
+    amount0_in TEXT NOT NULL,
+    amount1_in TEXT NOT NULL,
+    amount0_out TEXT NOT NULL,
+    amount1_out TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS pair_liquidity (
+    source TEXT PRIMARY KEY,
+    reserve0 TEXT NOT NULL,
+    reserve1 TEXT NOT NULL,
+    last_sync INTEGER,
+    minted TEXT NOT NULL,
+    burned TEXT NOT NULL,
+    protocol_fee TEXT NOT NULL,
+    mints INTEGER NOT NULL,
+    burns INTEGER NOT NULL
+);
+";
+
+/// Failure to fold events into the tables.
+#[derive(Debug)]
+pub enum StoreError {
+    Sqlite(rusqlite::Error),
+    /// A sum kept for the pair whose events dictionary is `source` exceeds [`U256`].
+    Overflow {
+        source: String,
+    },
+}
+
+impl std::fmt::Display for StoreError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StoreError::Sqlite(error) => write!(f, "sqlite: {}", error),
+            StoreError::Overflow { source } => write!(f, "amounts of pair {} overflow", source),
+        }
+    }
+}
+
+impl std::error::Error for StoreError {}
+
+impl From<rusqlite::Error> for StoreError {
+    fn from(error: rusqlite::Error) -> Self {
+        StoreError::Sqlite(error)
+    }
+}
+
+/// Returns `a + b`, failing with [`StoreError::Overflow`] for the pair `source`.
+fn checked_add(source: &str, a: U256, b: U256) -> Result<U256, StoreError> {
+    a.checked_add(b).ok_or_else(|| StoreError::Overflow {
+        source: source.into(),
+    })
+}
+
+/// A [`U256`] stored as decimal text.
+struct Amount(U256);
+
+impl ToSql for Amount {
+    fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+        Ok(ToSqlOutput::from(self.0.to_string()))
+    }
+}
+
+impl FromSql for Amount {
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        U256::from_dec_str(value.as_str()?)
+            .map(Amount)
+            .map_err(|_| FromSqlError::InvalidType)
+    }
+}
+
+/// Swaps through a pair, in its token0/token1 order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PairVolume {
+    /// Hex address of the pair's events dictionary.
+    pub source: String,
+    /// Package hash of the pair, once its `create_pair` event is indexed.
+    pub pair: Option<String>,
+    /// The pair's tokens, once its `create_pair` event is indexed.
+    pub tokens: Option<(String, String)>,
+    pub swaps: u64,
+    pub amount0_in: U256,
+    pub amount1_in: U256,
+    pub amount0_out: U256,
+    pub amount1_out: U256,
+}
+
+/// Reserves and LP supply of a pair.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PairLiquidity {
+    /// Hex address of the pair's events dictionary.
+    pub source: String,
+    /// Package hash of the pair, once its `create_pair` event is indexed.
+    pub pair: Option<String>,
+    /// The pair's tokens, once its `create_pair` event is indexed.
+    pub tokens: Option<(String, String)>,
+    pub reserve0: U256,
+    pub reserve1: U256,
+    /// LP minted to liquidity providers.
+    pub minted: U256,
+    /// LP burned by liquidity providers.
+    pub burned: U256,
+    /// LP minted to the protocol fee recipient.
+    pub protocol_fee: U256,
+    pub mints: u64,
+    pub burns: u64,
+}
+
+impl PairLiquidity {
+    /// Returns the LP supply, including the minimum liquidity locked by the first mint.
+    ///
+    /// Returns `None` while more is burned than the indexed mints account for, as when the
+    /// execution minting it has not been ingested yet.
+    pub fn total_supply(&self) -> Option<U256> {
+        let locked = match self.mints {
+            0 => U256::zero(),
+            _ => U256::from(swappery_math::MINIMUM_LIQUIDITY),
+        };
+        self.minted
+            .checked_add(self.protocol_fee)?
+            .checked_add(locked)?
+            .checked_sub(self.burned)
+    }
+}
+
+fn format_address(address: &Address) -> String {
+    match address {
+        Address::Account(account_hash) => account_hash.to_formatted_string(),
+        Address::Contract(package_hash) => package_hash.to_formatted_string(),
+    }
+}
+
+fn fold_router(transaction: &Transaction, event: &RouterEvent) -> rusqlite::Result<()> {
+    if let RouterEvent::CreatePair {
+        token0,
+        token1,
+        pair,
+        events,
+    } = event
+    {
+        transaction.execute(
+            "INSERT OR REPLACE INTO router_pairs (source, pair, token0, token1)
+             VALUES (?1, ?2, ?3, ?4)",
+            params![
+                hex::encode(events.addr()),
+                format_address(pair),
+                token0.to_formatted_string(),
+                token1.to_formatted_string()
+            ],
+        )?;
+    }
+    Ok(())
+}
+
+fn fold_swap(
+    transaction: &Transaction,
+    source: &str,
+    amounts_in: (U256, U256),
+    amounts_out: (U256, U256),
+) -> Result<(), StoreError> {
+    let volume: Option<(u64, Amount, Amount, Amount, Amount)> = transaction
+        .query_row(
+            "SELECT swaps, amount0_in, amount1_in, amount0_out, amount1_out
+             FROM pair_volume WHERE source = ?1",
+            params![source],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?, row.get(4)?)),
+        )
+        .optional()?;
+    let zero = || Amount(U256::zero());
+    let (swaps, amount0_in, amount1_in, amount0_out, amount1_out) =
+        volume.unwrap_or_else(|| (0, zero(), zero(), zero(), zero()));
+    transaction.execute(
+        "INSERT OR REPLACE INTO pair_volume
+         (source, swaps, amount0_in, amount1_in, amount0_out, amount1_out)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+        params![
+            source,
+            swaps + 1,
+            Amount(checked_add(source, amount0_in.0, amounts_in.0)?),
+            Amount(checked_add(source, amount1_in.0, amounts_in.1)?),
+            Amount(checked_add(source, amount0_out.0, amounts_out.0)?),
+            Amount(checked_add(source, amount1_out.0, amounts_out.1)?)
+        ],
+    )?;
+    Ok(())
+}
+
+/// A row of `pair_liquidity` being folded into.
+#[derive(Default)]
+struct LiquidityRow {
+    reserve0: U256,
+    reserve1: U256,
+    last_sync: Option<u32>,
+    minted: U256,
+    burned: U256,
+    protocol_fee: U256,
+    mints: u64,
+    burns: u64,
+}
+
+fn fold_liquidity(
+    transaction: &Transaction,
+    source: &str,
+    index: u32,
+    event: &PairEvent,
+) -> Result<(), StoreError> {
+    let mut row: LiquidityRow = transaction
+        .query_row(
+            "SELECT reserve0, reserve1, last_sync, minted, burned, protocol_fee, mints, burns
+             FROM pair_liquidity WHERE source = ?1",
+            params![source],
+            |row| {
+                Ok(LiquidityRow {
+                    reserve0: row.get::<_, Amount>(0)?.0,
+                    reserve1: row.get::<_, Amount>(1)?.0,
+                    last_sync: row.get(2)?,
+                    minted: row.get::<_, Amount>(3)?.0,
+                    burned: row.get::<_, Amount>(4)?.0,
+                    protocol_fee: row.get::<_, Amount>(5)?.0,
+                    mints: row.get(6)?,
+                    burns: row.get(7)?,
+                })
+            },
+        )
+        .optional()?
+        .unwrap_or_default();
+
+    match event {
+        PairEvent::Sync { reserve0, reserve1 } => {
+            // Executions may be ingested out of order; the latest sync holds the reserves.
+            if row.last_sync.map_or(true, |last_sync| index > last_sync) {
+                row.reserve0 = *reserve0;
+                row.reserve1 = *reserve1;
+                row.last_sync = Some(index);
+            }
+        }
+        PairEvent::Mint { liquidity, .. } => {
+            row.minted = checked_add(source, row.minted, *liquidity)?;
+            row.mints += 1;
+        }
+        PairEvent::Burn { liquidity, .. } => {
+            row.burned = checked_add(source, row.burned, *liquidity)?;
+            row.burns += 1;
+        }
+        PairEvent::MintFee { liquidity, .. } => {
+            row.protocol_fee = checked_add(source, row.protocol_fee, *liquidity)?;
+        }
+        PairEvent::Swap { .. } | PairEvent::SetSwapFee { .. } => return Ok(()),
+    }
+
+    transaction.execute(
+        "INSERT OR REPLACE INTO pair_liquidity
+         (source, reserve0, reserve1, last_sync, minted, burned, protocol_fee, mints, burns)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+        params![
+            source,
+            Amount(row.reserve0),
+            Amount(row.reserve1),
+            row.last_sync,
+            Amount(row.minted),
+            Amount(row.burned),
+            Amount(row.protocol_fee),
+            row.mints,
+            row.burns
+        ],
+    )?;
+    Ok(())
+}
+
+fn fold_pair(
+    transaction: &Transaction,
+    source: &str,
+    index: u32,
+    event: &PairEvent,
+) -> Result<(), StoreError> {
+    if let PairEvent::Swap {
+        amount0_in,
+        amount1_in,
+        amount0_out,
+        amount1_out,
+        ..
+    } = event
+    {
+        return fold_swap(
+            transaction,
+            source,
+            (*amount0_in, *amount1_in),
+            (*amount0_out, *amount1_out),
+        );
+    }
+    fold_liquidity(transaction, source, index, event)
+}
+
+/// An SQLite database of indexed events.
+pub struct Store {
+    connection: Connection,
+}
+
+impl Store {
+    pub fn open(path: impl AsRef<Path>) -> rusqlite::Result<Self> {
+        Store::new(Connection::open(path)?)
+    }
+
+    pub fn open_in_memory() -> rusqlite::Result<Self> {
+        Store::new(Connection::open_in_memory()?)
+    }
+
+    fn new(connection: Connection) -> rusqlite::Result<Self> {
+        connection.execute_batch(SCHEMA)?;
+        Ok(Store { connection })
+    }
+
+    /// Folds the events of `execution` not indexed yet into the tables, returning how many there
+    /// were.
+    pub fn ingest(&mut self, execution: &Execution) -> Result<usize, StoreError> {
+        let transaction = self.connection.transaction()?;
+        let mut ingested = 0;
+        for emitted in &execution.events {
+            let source = hex::encode(emitted.source);
+            let inserted = transaction.execute(
+                "INSERT OR IGNORE INTO events (source, idx, deploy_hash, name)
+                 VALUES (?1, ?2, ?3, ?4)",
+                params![
+                    source,
+                    emitted.index,
+                    execution.deploy_hash,
+                    emitted.event.name()
+                ],
+            )?;
+            if inserted == 0 {
+                continue;
+            }
+            ingested += 1;
+            match &emitted.event {
+                Event::Router(event) => fold_router(&transaction, event)?,
+                Event::Pair(event) => fold_pair(&transaction, &source, emitted.index, event)?,
+            }
+        }
+        transaction.commit()?;
+        Ok(ingested)
+    }
+
+    /// Returns the swap volume of every pair that has been swapped through.
+    pub fn pair_volumes(&self) -> rusqlite::Result<Vec<PairVolume>> {
+        let mut statement = self.connection.prepare(
+            "SELECT v.source, p.pair, p.token0, p.token1,
+                    v.swaps, v.amount0_in, v.amount1_in, v.amount0_out, v.amount1_out
+             FROM pair_volume v LEFT JOIN router_pairs p ON p.source = v.source
+             ORDER BY v.source",
+        )?;
+        let rows = statement.query_map([], |row| {
+            let tokens: (Option<String>, Option<String>) = (row.get(2)?, row.get(3)?);
+            Ok(PairVolume {
+                source: row.get(0)?,
+                pair: row.get(1)?,
+                tokens: tokens.0.zip(tokens.1),
+                swaps: row.get(4)?,
+                amount0_in: row.get::<_, Amount>(5)?.0,
+                amount1_in: row.get::<_, Amount>(6)?.0,
+                amount0_out: row.get::<_, Amount>(7)?.0,
+                amount1_out: row.get::<_, Amount>(8)?.0,
+            })
+        })?;
+        rows.collect()
+    }
+
+    /// Returns the reserves and LP supply of every pair that has been synced or minted.
+    pub fn pair_liquidity(&self) -> rusqlite::Result<Vec<PairLiquidity>> {
+        let mut statement = self.connection.prepare(
+            "SELECT l.source, p.pair, p.token0, p.token1, l.reserve0, l.reserve1,
+                    l.minted, l.burned, l.protocol_fee, l.mints, l.burns
+             FROM pair_liquidity l LEFT JOIN router_pairs p ON p.source = l.source
+             ORDER BY l.source",
+        )?;
+        let rows = statement.query_map([], |row| {
+            let tokens: (Option<String>, Option<String>) = (row.get(2)?, row.get(3)?);
+            Ok(PairLiquidity {
+                source: row.get(0)?,
+                pair: row.get(1)?,
+                tokens: tokens.0.zip(tokens.1),
+                reserve0: row.get::<_, Amount>(4)?.0,
+                reserve1: row.get::<_, Amount>(5)?.0,
+                minted: row.get::<_, Amount>(6)?.0,
+                burned: row.get::<_, Amount>(7)?.0,
+                protocol_fee: row.get::<_, Amount>(8)?.0,
+                mints: row.get(9)?,
+                burns: row.get(10)?,
+            })
+        })?;
+        rows.collect()
+    }
+}
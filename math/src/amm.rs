@@ -0,0 +1,136 @@
+//! Quoting against a pair's reserves.
+
+use casper_types::{U256, U512};
+
+use crate::wide::{checked_div, checked_mul, mul, mul_div, narrow, widen};
+use crate::{MathError, FEE_DENOMINATOR};
+
+/// Returns the part of [`FEE_DENOMINATOR`] left after a fee of `fee_bps`, failing unless the fee
+/// is below the denominator.
+pub(crate) fn fee_complement(fee_bps: u32) -> Result<U512, MathError> {
+    match u64::from(fee_bps) {
+        fee_bps if fee_bps < FEE_DENOMINATOR => Ok(U512::from(FEE_DENOMINATOR - fee_bps)),
+        _ => Err(MathError::InvalidFee),
+    }
+}
+
+/// Returns `a` and `b` in the order pairs store their tokens.
+pub fn sort_tokens<T: Ord>(a: T, b: T) -> (T, T) {
+    if a < b {
+        (a, b)
+    } else {
+        (b, a)
+    }
+}
+
+/// Returns the amount of B worth `amount_a` of A at the reserves' price.
+pub fn quote(amount_a: U256, reserve_a: U256, reserve_b: U256) -> Result<U256, MathError> {
+    if amount_a.is_zero() {
+        return Err(MathError::InsufficientAmount);
+    }
+    if reserve_a.is_zero() || reserve_b.is_zero() {
+        return Err(MathError::InsufficientLiquidity);
+    }
+    mul_div(amount_a, reserve_b, reserve_a)
+}
+
+/// Returns what swapping `amount_in` into a pair pays out, after a fee of `swap_fee_bps`, which
+/// must be below [`FEE_DENOMINATOR`].
+pub fn get_amount_out(
+    amount_in: U256,
+    reserve_in: U256,
+    reserve_out: U256,
+    swap_fee_bps: u32,
+) -> Result<U256, MathError> {
+    if amount_in.is_zero() {
+        return Err(MathError::InsufficientInputAmount);
+    }
+    if reserve_in.is_zero() || reserve_out.is_zero() {
+        return Err(MathError::InsufficientLiquidity);
+    }
+
+    let fee_denominator: U512 = U512::from(FEE_DENOMINATOR);
+    let amount_with_fee: U512 = widen(amount_in) * fee_complement(swap_fee_bps)?;
+    let numerator: U512 = checked_mul(amount_with_fee, widen(reserve_out))?;
+    let denominator: U512 = widen(reserve_in) * fee_denominator + amount_with_fee;
+    narrow(checked_div(numerator, denominator)?)
+}
+
+/// Returns the least a pair must be paid, after a fee of `swap_fee_bps`, to pay out `amount_out`.
+/// The fee must be below [`FEE_DENOMINATOR`].
+pub fn get_amount_in(
+    amount_out: U256,
+    reserve_in: U256,
+    reserve_out: U256,
+    swap_fee_bps: u32,
+) -> Result<U256, MathError> {
+    if amount_out.is_zero() {
+        return Err(MathError::InsufficientOutputAmount);
+    }
+    if reserve_in.is_zero() || amount_out >= reserve_out {
+        return Err(MathError::InsufficientLiquidity);
+    }
+
+    let fee_denominator: U512 = U512::from(FEE_DENOMINATOR);
+    let numerator: U512 = checked_mul(mul(reserve_in, amount_out), fee_denominator)?;
+    let denominator: U512 = widen(reserve_out - amount_out) * fee_complement(swap_fee_bps)?;
+    narrow(checked_div(numerator, denominator)? + U512::one())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_sort_tokens() {
+        assert_eq!(sort_tokens(2, 1), (1, 2));
+        assert_eq!(sort_tokens(1, 2), (1, 2));
+    }
+
+    #[test]
+    fn should_quote() {
+        assert_eq!(quote(10_000.into(), 30_000.into(), 50_000.into()), Ok(U256::from(16_666)));
+        assert_eq!(quote(0.into(), 1.into(), 1.into()), Err(MathError::InsufficientAmount));
+        assert_eq!(quote(1.into(), 0.into(), 1.into()), Err(MathError::InsufficientLiquidity));
+    }
+
+    #[test]
+    fn should_get_amount_out() {
+        assert_eq!(
+            get_amount_out(10_000.into(), 30_000.into(), 50_000.into(), 20),
+            Ok(U256::from(12_481))
+        );
+        assert_eq!(
+            get_amount_out(0.into(), 30_000.into(), 50_000.into(), 20),
+            Err(MathError::InsufficientInputAmount)
+        );
+        assert_eq!(
+            get_amount_out(1.into(), 0.into(), 50_000.into(), 20),
+            Err(MathError::InsufficientLiquidity)
+        );
+        assert_eq!(
+            get_amount_out(1.into(), 30_000.into(), 50_000.into(), 10_000),
+            Err(MathError::InvalidFee)
+        );
+    }
+
+    #[test]
+    fn should_get_amount_in() {
+        assert_eq!(
+            get_amount_in(12_481.into(), 30_000.into(), 50_000.into(), 20),
+            Ok(U256::from(10_000))
+        );
+        assert_eq!(
+            get_amount_in(0.into(), 30_000.into(), 50_000.into(), 20),
+            Err(MathError::InsufficientOutputAmount)
+        );
+        assert_eq!(
+            get_amount_in(50_000.into(), 30_000.into(), 50_000.into(), 20),
+            Err(MathError::InsufficientLiquidity)
+        );
+        assert_eq!(
+            get_amount_in(1.into(), 30_000.into(), 50_000.into(), 10_000),
+            Err(MathError::InvalidFee)
+        );
+    }
+}
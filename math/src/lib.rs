@@ -0,0 +1,44 @@
+//! AMM arithmetic shared by the Swappery contracts and off-chain code.
+//!
+//! Everything here is pure and `no_std`, so the router's quotes, the pair's liquidity and K checks
+//! and any off-chain quoting run the very same code. Contracts map [`MathError`] into their own
+//! error codes.
+#![no_std]
+
+mod amm;
+mod pair;
+mod wide;
+
+pub use amm::{get_amount_in, get_amount_out, quote, sort_tokens};
+pub use pair::{amount_in, burn_amounts, fee_liquidity, k_holds, mint_liquidity};
+pub use wide::{checked_mul, mul, mul_div, narrow, sqrt_product, truncate, widen};
+
+/// Swap fees are expressed in basis points of this denominator.
+pub const FEE_DENOMINATOR: u64 = 10_000;
+/// Liquidity locked forever by the first mint of a pair.
+pub const MINIMUM_LIQUIDITY: u64 = 1_000;
+/// Swap fee of a newly created pair, 0.2%.
+pub const DEFAULT_SWAP_FEE_BPS: u32 = 20;
+/// Highest swap fee a pair can be set to, 1%.
+pub const MAX_SWAP_FEE_BPS: u32 = 100;
+/// Share of fee growth minted to the protocol fee recipient until changed, a quarter.
+pub const DEFAULT_PROTOCOL_FEE_SHARE_BPS: u32 = 2_500;
+/// Highest share of fee growth the protocol can take, a half.
+pub const MAX_PROTOCOL_FEE_SHARE_BPS: u32 = 5_000;
+
+/// Failure of an AMM computation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MathError {
+    /// A result does not fit its type.
+    Overflow,
+    /// [`quote`] was asked to price nothing.
+    InsufficientAmount,
+    /// [`get_amount_out`] was asked to price nothing.
+    InsufficientInputAmount,
+    /// [`get_amount_in`] was asked for nothing.
+    InsufficientOutputAmount,
+    /// A reserve is empty, or cannot pay out what is asked of it.
+    InsufficientLiquidity,
+    /// A fee or fee share is not below [`FEE_DENOMINATOR`].
+    InvalidFee,
+}
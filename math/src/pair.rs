@@ -0,0 +1,195 @@
+//! Liquidity and invariant math of a pair.
+
+use casper_types::{U256, U512};
+
+use crate::amm::fee_complement;
+use crate::wide::{checked_div, checked_mul, mul, mul_div, narrow, sqrt_product, truncate, widen};
+use crate::{MathError, FEE_DENOMINATOR, MINIMUM_LIQUIDITY};
+
+/// Returns the liquidity minted for depositing `amount0` and `amount1` into reserves backing
+/// `total_supply`.
+///
+/// The first deposit mints the geometric mean of the amounts less the [`MINIMUM_LIQUIDITY`] the
+/// pair locks; later ones mint in proportion to the smaller share they add, which needs both
+/// reserves to be non-empty. A zero result means the deposit is too small to mint anything.
+pub fn mint_liquidity(
+    amount0: U256,
+    amount1: U256,
+    reserve0: U256,
+    reserve1: U256,
+    total_supply: U256,
+) -> Result<U256, MathError> {
+    if total_supply.is_zero() {
+        return Ok(sqrt_product(amount0, amount1).saturating_sub(U256::from(MINIMUM_LIQUIDITY)));
+    }
+    if reserve0.is_zero() || reserve1.is_zero() {
+        return Err(MathError::InsufficientLiquidity);
+    }
+    Ok(U256::min(
+        mul_div(amount0, total_supply, reserve0)?,
+        mul_div(amount1, total_supply, reserve1)?,
+    ))
+}
+
+/// Returns the amounts of `balance0` and `balance1` that burning `liquidity` out of `total_supply`
+/// pays out. Fails while nothing has been minted.
+pub fn burn_amounts(
+    liquidity: U256,
+    balance0: U256,
+    balance1: U256,
+    total_supply: U256,
+) -> Result<(U256, U256), MathError> {
+    if total_supply.is_zero() {
+        return Err(MathError::InsufficientLiquidity);
+    }
+    Ok((
+        mul_div(liquidity, balance0, total_supply)?,
+        mul_div(liquidity, balance1, total_supply)?,
+    ))
+}
+
+/// Returns the liquidity owed to the protocol out of the growth in `sqrt(k)` since `klast`.
+///
+/// The protocol gets `protocol_fee_share_bps` of the growth, which must be below
+/// [`FEE_DENOMINATOR`]; a 2_500 bps share reproduces Uniswap V2's `rootk * 3 + rootklast`
+/// denominator. Nothing is owed while `klast` is unset.
+pub fn fee_liquidity(
+    total_supply: U256,
+    reserve0: U256,
+    reserve1: U256,
+    klast: U512,
+    protocol_fee_share_bps: u32,
+) -> Result<U256, MathError> {
+    if klast.is_zero() {
+        return Ok(U256::zero());
+    }
+    let rootk: U256 = sqrt_product(reserve0, reserve1);
+    let rootklast: U256 = truncate(klast.integer_sqrt());
+    if rootk <= rootklast {
+        return Ok(U256::zero());
+    }
+
+    let share_bps: U512 = U512::from(protocol_fee_share_bps);
+    let numerator: U512 = checked_mul(mul(total_supply, rootk - rootklast), share_bps)?;
+    let denominator: U512 =
+        widen(rootk) * fee_complement(protocol_fee_share_bps)? + widen(rootklast) * share_bps;
+    narrow(checked_div(numerator, denominator)?)
+}
+
+/// Returns how much of `balance` was paid in on top of what the reserve keeps after paying out
+/// `amount_out`, failing if the reserve cannot pay it out.
+pub fn amount_in(balance: U256, reserve: U256, amount_out: U256) -> Result<U256, MathError> {
+    let kept: U256 = reserve.checked_sub(amount_out).ok_or(MathError::InsufficientLiquidity)?;
+    Ok(balance.saturating_sub(kept))
+}
+
+/// Returns whether balances after a swap keep the product of the reserves, net of a fee of
+/// `swap_fee_bps` on the amounts paid in.
+///
+/// The fee must be below [`FEE_DENOMINATOR`], and the fee on an amount paid in cannot exceed its
+/// balance. Fits [`U512`] as long as balances stay below `U256::MAX / FEE_DENOMINATOR`.
+pub fn k_holds(
+    balance0: U256,
+    balance1: U256,
+    amount0_in: U256,
+    amount1_in: U256,
+    reserve0: U256,
+    reserve1: U256,
+    swap_fee_bps: u32,
+) -> Result<bool, MathError> {
+    fee_complement(swap_fee_bps)?;
+    let fee_denominator: U512 = U512::from(FEE_DENOMINATOR);
+    let swap_fee_bps: U512 = U512::from(swap_fee_bps);
+
+    let adjust = |balance: U256, amount_in: U256| {
+        (widen(balance) * fee_denominator)
+            .checked_sub(widen(amount_in) * swap_fee_bps)
+            .ok_or(MathError::InsufficientInputAmount)
+    };
+    let balance0_adjusted: U512 = adjust(balance0, amount0_in)?;
+    let balance1_adjusted: U512 = adjust(balance1, amount1_in)?;
+
+    let k_adjusted: U512 = checked_mul(balance0_adjusted, balance1_adjusted)?;
+    let k_required: U512 =
+        checked_mul(mul(reserve0, reserve1), fee_denominator * fee_denominator)?;
+    Ok(k_adjusted >= k_required)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_mint_initial_liquidity() {
+        let liquidity = mint_liquidity(30_000.into(), 50_000.into(), 0.into(), 0.into(), 0.into());
+        assert_eq!(liquidity, Ok(U256::from(37_729)));
+        let liquidity = mint_liquidity(1_000.into(), 1_000.into(), 0.into(), 0.into(), 0.into());
+        assert_eq!(liquidity, Ok(U256::zero()));
+    }
+
+    #[test]
+    fn should_mint_the_smaller_share() {
+        let liquidity =
+            mint_liquidity(3_000.into(), 6_000.into(), 30_000.into(), 50_000.into(), 38_729.into());
+        assert_eq!(liquidity, Ok(U256::from(3_872)));
+    }
+
+    #[test]
+    fn should_not_mint_into_empty_reserves() {
+        let liquidity =
+            mint_liquidity(3_000.into(), 6_000.into(), 0.into(), 50_000.into(), 38_729.into());
+        assert_eq!(liquidity, Err(MathError::InsufficientLiquidity));
+    }
+
+    #[test]
+    fn should_burn_pro_rata() {
+        let amounts = burn_amounts(3_872.into(), 33_000.into(), 56_000.into(), 42_601.into());
+        assert_eq!(amounts, Ok((U256::from(2_999), U256::from(5_089))));
+        let amounts = burn_amounts(1.into(), 33_000.into(), 56_000.into(), 0.into());
+        assert_eq!(amounts, Err(MathError::InsufficientLiquidity));
+    }
+
+    #[test]
+    fn should_owe_no_fee_without_growth() {
+        let klast: U512 = mul(30_000.into(), 50_000.into());
+        let fee = fee_liquidity(38_729.into(), 30_000.into(), 50_000.into(), klast, 2_500);
+        assert_eq!(fee, Ok(U256::zero()));
+        let fee = fee_liquidity(38_729.into(), 40_000.into(), 50_000.into(), U512::zero(), 2_500);
+        assert_eq!(fee, Ok(U256::zero()));
+    }
+
+    #[test]
+    fn should_owe_share_of_growth() {
+        let klast: U512 = mul(30_000.into(), 50_000.into());
+        let fee = fee_liquidity(38_729.into(), 40_000.into(), 40_000.into(), klast, 2_500);
+        // rootk 40_000, rootklast 38_729: 38_729 * 1_271 / (40_000 * 3 + 38_729)
+        assert_eq!(fee, Ok(U256::from(310)));
+        let fee = fee_liquidity(38_729.into(), 40_000.into(), 40_000.into(), klast, 10_000);
+        assert_eq!(fee, Err(MathError::InvalidFee));
+    }
+
+    #[test]
+    fn should_get_amount_paid_in() {
+        assert_eq!(amount_in(40_000.into(), 30_000.into(), 0.into()), Ok(U256::from(10_000)));
+        assert_eq!(amount_in(20_000.into(), 30_000.into(), 5_000.into()), Ok(U256::zero()));
+        assert_eq!(
+            amount_in(40_000.into(), 30_000.into(), 30_001.into()),
+            Err(MathError::InsufficientLiquidity)
+        );
+    }
+
+    #[test]
+    fn should_check_k() {
+        let (reserve0, reserve1) = (U256::from(30_000), U256::from(50_000));
+        let holds = |balance1: u64| {
+            let (amount0_in, amount1_in) = (U256::from(10_000), U256::zero());
+            k_holds(40_000.into(), balance1.into(), amount0_in, amount1_in, reserve0, reserve1, 20)
+        };
+        assert_eq!(holds(50_000 - 12_481), Ok(true));
+        assert_eq!(holds(50_000 - 12_482), Ok(false));
+        let holds = k_holds(1.into(), 1.into(), 1_000.into(), 0.into(), reserve0, reserve1, 20);
+        assert_eq!(holds, Err(MathError::InsufficientInputAmount));
+        let holds = k_holds(1.into(), 1.into(), 0.into(), 0.into(), reserve0, reserve1, 10_000);
+        assert_eq!(holds, Err(MathError::InvalidFee));
+    }
+}
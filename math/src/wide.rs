@@ -0,0 +1,81 @@
+//! Overflow-safe arithmetic on [`U256`] amounts.
+//!
+//! Products of two amounts are carried out in [`U512`], which holds any product of two [`U256`]s.
+//! Anything that may still exceed its type, or divide by zero, is checked and fails with
+//! [`MathError::Overflow`].
+
+use casper_types::{U256, U512};
+
+use crate::MathError;
+
+/// Widens a [`U256`] into a [`U512`].
+pub fn widen(value: U256) -> U512 {
+    let mut bytes = [0u8; 64];
+    value.to_little_endian(&mut bytes[..32]);
+    U512::from_little_endian(&bytes)
+}
+
+/// Narrows a [`U512`] back into a [`U256`], failing if it does not fit.
+pub fn narrow(value: U512) -> Result<U256, MathError> {
+    if value > widen(U256::MAX) {
+        return Err(MathError::Overflow);
+    }
+    Ok(truncate(value))
+}
+
+/// Keeps the low 256 bits of a [`U512`].
+pub fn truncate(value: U512) -> U256 {
+    let mut bytes = [0u8; 64];
+    value.to_little_endian(&mut bytes);
+    U256::from_little_endian(&bytes[..32])
+}
+
+/// Returns `a * b` without overflow.
+pub fn mul(a: U256, b: U256) -> U512 {
+    widen(a) * widen(b)
+}
+
+/// Returns `a * b`, failing if it exceeds [`U512`].
+pub fn checked_mul(a: U512, b: U512) -> Result<U512, MathError> {
+    a.checked_mul(b).ok_or(MathError::Overflow)
+}
+
+/// Returns `a / b` rounded down, failing if `b` is zero.
+pub(crate) fn checked_div(a: U512, b: U512) -> Result<U512, MathError> {
+    a.checked_div(b).ok_or(MathError::Overflow)
+}
+
+/// Returns `a * b / denominator` rounded down, failing if the quotient exceeds [`U256`] or
+/// `denominator` is zero.
+pub fn mul_div(a: U256, b: U256, denominator: U256) -> Result<U256, MathError> {
+    narrow(checked_div(mul(a, b), widen(denominator))?)
+}
+
+/// Returns the integer square root of `a * b`, which always fits in [`U256`].
+pub fn sqrt_product(a: U256, b: U256) -> U256 {
+    truncate(mul(a, b).integer_sqrt())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_narrow_what_fits() {
+        assert_eq!(narrow(widen(U256::MAX)), Ok(U256::MAX));
+        assert_eq!(narrow(widen(U256::MAX) + 1), Err(MathError::Overflow));
+    }
+
+    #[test]
+    fn should_mul_div_past_u256() {
+        assert_eq!(mul_div(U256::MAX, U256::MAX, U256::MAX), Ok(U256::MAX));
+        assert_eq!(mul_div(U256::MAX, 2.into(), 1.into()), Err(MathError::Overflow));
+        assert_eq!(mul_div(1.into(), 1.into(), 0.into()), Err(MathError::Overflow));
+    }
+
+    #[test]
+    fn should_sqrt_product() {
+        assert_eq!(sqrt_product(30_000.into(), 50_000.into()), U256::from(38_729));
+        assert_eq!(sqrt_product(U256::MAX, U256::MAX), U256::MAX);
+    }
+}
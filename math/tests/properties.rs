@@ -0,0 +1,177 @@
+//! Properties tying the router's quotes to the pair's checks.
+
+use casper_types::U256;
+use proptest::prelude::*;
+
+use swappery_math::{
+    amount_in, burn_amounts, fee_liquidity, get_amount_in, get_amount_out, k_holds,
+    mint_liquidity, mul, narrow, quote, widen, MathError, FEE_DENOMINATOR,
+};
+
+/// Amounts small enough for the fee-adjusted K check to fit.
+fn amount() -> impl Strategy<Value = U256> {
+    (1u128..=u128::MAX).prop_map(U256::from)
+}
+
+/// Any amount, weighted towards empty reserves and amounts near [`U256::MAX`].
+fn any_amount() -> impl Strategy<Value = U256> {
+    prop_oneof![
+        Just(U256::zero()),
+        amount(),
+        any::<u64>().prop_map(|below| U256::MAX - U256::from(below)),
+    ]
+}
+
+/// Fees a pair may charge.
+fn swap_fee_bps() -> impl Strategy<Value = u32> {
+    0u32..FEE_DENOMINATOR as u32
+}
+
+/// Fees and fee shares, in range or not.
+fn any_bps() -> impl Strategy<Value = u32> {
+    prop_oneof![swap_fee_bps(), any::<u32>()]
+}
+
+proptest! {
+    #[test]
+    fn widen_narrow_round_trips(low in any::<u128>(), high in any::<u128>()) {
+        let value = (U256::from(high) << 128) | U256::from(low);
+        prop_assert_eq!(narrow(widen(value)), Ok(value));
+    }
+
+    #[test]
+    fn amount_out_never_drains_reserve(
+        amount_in in amount(),
+        reserve_in in amount(),
+        reserve_out in amount(),
+        fee in swap_fee_bps(),
+    ) {
+        let amount_out = get_amount_out(amount_in, reserve_in, reserve_out, fee).unwrap();
+        prop_assert!(amount_out < reserve_out);
+    }
+
+    #[test]
+    fn amount_out_passes_k_check(
+        amount_in in amount(),
+        reserve_in in amount(),
+        reserve_out in amount(),
+        fee in swap_fee_bps(),
+    ) {
+        let amount_out = get_amount_out(amount_in, reserve_in, reserve_out, fee).unwrap();
+        let balance_in = reserve_in + amount_in;
+        let balance_out = reserve_out - amount_out;
+        let holds = k_holds(
+            balance_in, balance_out, amount_in, U256::zero(), reserve_in, reserve_out, fee,
+        );
+        prop_assert_eq!(holds, Ok(true));
+        if !amount_out.is_zero() {
+            // Asking for one more than quoted is refused, so the quote is exact.
+            let holds = k_holds(
+                balance_in, balance_out - 1, amount_in, U256::zero(), reserve_in, reserve_out, fee,
+            );
+            prop_assert_eq!(holds, Ok(false));
+        }
+    }
+
+    #[test]
+    fn amount_in_buys_amount_out(
+        amount_out in amount(),
+        reserve_in in amount(),
+        reserve_out in amount(),
+        fee in swap_fee_bps(),
+    ) {
+        match get_amount_in(amount_out, reserve_in, reserve_out, fee) {
+            Ok(amount_in) => {
+                let bought = get_amount_out(amount_in, reserve_in, reserve_out, fee).unwrap();
+                prop_assert!(bought >= amount_out);
+            }
+            Err(error) => {
+                prop_assert!(amount_out >= reserve_out || error == MathError::Overflow);
+            }
+        }
+    }
+
+    #[test]
+    fn quote_round_trip_never_gains(
+        amount_a in amount(),
+        reserve_a in amount(),
+        reserve_b in amount(),
+    ) {
+        if let Ok(amount_b) = quote(amount_a, reserve_a, reserve_b) {
+            if !amount_b.is_zero() {
+                prop_assert!(quote(amount_b, reserve_b, reserve_a).unwrap() <= amount_a);
+            }
+        }
+    }
+
+    #[test]
+    fn amount_in_is_what_exceeds_reserve(
+        reserve in amount(),
+        paid in any::<u64>(),
+        out_share in 0u64..=100,
+    ) {
+        let amount_out = reserve * out_share / 100;
+        let balance = reserve - amount_out + paid;
+        prop_assert_eq!(amount_in(balance, reserve, amount_out), Ok(U256::from(paid)));
+    }
+
+    #[test]
+    fn burning_minted_liquidity_never_gains(
+        reserve0 in amount(),
+        reserve1 in amount(),
+        total_supply in amount(),
+        amount0 in amount(),
+        amount1 in amount(),
+    ) {
+        let liquidity = mint_liquidity(amount0, amount1, reserve0, reserve1, total_supply);
+        if let Ok(liquidity) = liquidity {
+            let (out0, out1) = burn_amounts(
+                liquidity,
+                reserve0 + amount0,
+                reserve1 + amount1,
+                total_supply + liquidity,
+            )
+            .unwrap();
+            prop_assert!(out0 <= amount0 && out1 <= amount1);
+        }
+    }
+
+    #[test]
+    fn quoting_never_panics(
+        amount in any_amount(),
+        reserve_in in any_amount(),
+        reserve_out in any_amount(),
+        fee in any_bps(),
+    ) {
+        let amount_out = get_amount_out(amount, reserve_in, reserve_out, fee);
+        let cost = get_amount_in(amount, reserve_in, reserve_out, fee);
+        let _ = quote(amount, reserve_in, reserve_out);
+        if u64::from(fee) >= FEE_DENOMINATOR {
+            prop_assert!(amount_out.is_err() && cost.is_err());
+        }
+    }
+
+    #[test]
+    fn pair_math_never_panics(
+        amount0 in any_amount(),
+        amount1 in any_amount(),
+        reserve0 in any_amount(),
+        reserve1 in any_amount(),
+        total_supply in any_amount(),
+        fee in any_bps(),
+        share in any_bps(),
+    ) {
+        let _ = mint_liquidity(amount0, amount1, reserve0, reserve1, total_supply);
+        let _ = amount_in(reserve0, reserve1, amount0);
+        let _ = fee_liquidity(total_supply, reserve0, reserve1, mul(amount0, amount1), share);
+
+        let burned = burn_amounts(amount0, reserve0, reserve1, total_supply);
+        if total_supply.is_zero() {
+            prop_assert_eq!(burned, Err(MathError::InsufficientLiquidity));
+        }
+        let holds = k_holds(reserve0, reserve1, amount0, amount1, reserve0, reserve1, fee);
+        if u64::from(fee) >= FEE_DENOMINATOR {
+            prop_assert_eq!(holds, Err(MathError::InvalidFee));
+        }
+    }
+}
@@ -0,0 +1,123 @@
+//! Names of the entry points and runtime arguments of the router and pair contracts.
+//!
+//! The contracts and the host-side crates read these from here, so a name cannot drift between
+//! what a contract declares and what its callers send.
+#![no_std]
+
+// router entry points
+pub const CREATE_PAIR_ENTRY_POINT_NAME: &str = "create_pair";
+pub const GET_PAIR_ENTRY_POINT_NAME: &str = "get_pair";
+pub const ALL_PAIRS_LENGTH_ENTRY_POINT_NAME: &str = "all_pairs_length";
+pub const PAIR_AT_ENTRY_POINT_NAME: &str = "pair_at";
+pub const PAIRS_FOR_TOKEN_ENTRY_POINT_NAME: &str = "pairs_for_token";
+pub const GET_BASE_TOKENS_ENTRY_POINT_NAME: &str = "get_base_tokens";
+pub const SET_BASE_TOKENS_ENTRY_POINT_NAME: &str = "set_base_tokens";
+pub const GET_BEST_AMOUNT_OUT_ENTRY_POINT_NAME: &str = "get_best_amount_out";
+pub const GET_FEETO_ENTRY_POINT_NAME: &str = "get_feeto";
+pub const SET_FEETO_ENTRY_POINT_NAME: &str = "set_feeto";
+pub const SET_FEETO_SETTER_ENTRY_POINT_NAME: &str = "set_feeto_setter";
+pub const GET_PROTOCOL_FEE_SHARE_ENTRY_POINT_NAME: &str = "get_protocol_fee_share";
+pub const SET_PROTOCOL_FEE_SHARE_ENTRY_POINT_NAME: &str = "set_protocol_fee_share";
+pub const SET_PAIR_SWAP_FEE_ENTRY_POINT_NAME: &str = "set_pair_swap_fee";
+pub const GET_AMOUNTS_OUT_ENTRY_POINT_NAME: &str = "get_amounts_out";
+pub const GET_AMOUNTS_IN_ENTRY_POINT_NAME: &str = "get_amounts_in";
+pub const QUOTE_ENTRY_POINT_NAME: &str = "quote";
+pub const ADD_LIQUIDITY_ENTRY_POINT_NAME: &str = "add_liquidity";
+pub const REMOVE_LIQUIDITY_ENTRY_POINT_NAME: &str = "remove_liquidity";
+pub const ADD_LIQUIDITY_CSPR_ENTRY_POINT_NAME: &str = "add_liquidity_cspr";
+pub const REMOVE_LIQUIDITY_CSPR_ENTRY_POINT_NAME: &str = "remove_liquidity_cspr";
+pub const REMOVE_LIQUIDITY_SUPPORTING_FEE_ENTRY_POINT_NAME: &str =
+    "remove_liquidity_supporting_fee";
+pub const REMOVE_LIQUIDITY_CSPR_SUPPORTING_FEE_ENTRY_POINT_NAME: &str =
+    "remove_liquidity_cspr_supporting_fee";
+pub const SWAP_EXACT_TOKENS_FOR_TOKENS_ENTRY_POINT_NAME: &str = "swap_exact_tokens_for_tokens";
+pub const SWAP_EXACT_TOKENS_FOR_TOKENS_SUPPORTING_FEE_ENTRY_POINT_NAME: &str =
+    "swap_exact_tokens_for_tokens_supporting_fee";
+pub const SWAP_EXACT_TOKENS_FOR_TOKENS_SPLIT_ENTRY_POINT_NAME: &str =
+    "swap_exact_tokens_for_tokens_split";
+pub const SWAP_EXACT_TOKENS_FOR_TOKENS_BEST_ENTRY_POINT_NAME: &str =
+    "swap_exact_tokens_for_tokens_best";
+pub const SWAP_TOKENS_FOR_EXACT_TOKENS_ENTRY_POINT_NAME: &str = "swap_tokens_for_exact_tokens";
+pub const SWAP_EXACT_CSPR_FOR_TOKENS_ENTRY_POINT_NAME: &str = "swap_exact_cspr_for_tokens";
+pub const SWAP_CSPR_FOR_EXACT_TOKENS_ENTRY_POINT_NAME: &str = "swap_cspr_for_exact_tokens";
+pub const SWAP_EXACT_TOKENS_FOR_CSPR_ENTRY_POINT_NAME: &str = "swap_exact_tokens_for_cspr";
+pub const SWAP_TOKENS_FOR_EXACT_CSPR_ENTRY_POINT_NAME: &str = "swap_tokens_for_exact_cspr";
+
+// pair entry points
+pub const NAME_ENTRY_POINT_NAME: &str = "name";
+pub const SYMBOL_ENTRY_POINT_NAME: &str = "symbol";
+pub const DECIMALS_ENTRY_POINT_NAME: &str = "decimals";
+pub const TOTAL_SUPPLY_ENTRY_POINT_NAME: &str = "total_supply";
+pub const BALANCE_OF_ENTRY_POINT_NAME: &str = "balance_of";
+pub const TRANSFER_ENTRY_POINT_NAME: &str = "transfer";
+pub const APPROVE_ENTRY_POINT_NAME: &str = "approve";
+pub const ALLOWANCE_ENTRY_POINT_NAME: &str = "allowance";
+pub const TRANSFER_FROM_ENTRY_POINT_NAME: &str = "transfer_from";
+pub const MINT_ENTRY_POINT_NAME: &str = "mint";
+pub const BURN_ENTRY_POINT_NAME: &str = "burn";
+pub const SWAP_ENTRY_POINT_NAME: &str = "swap";
+pub const SKIM_ENTRY_POINT_NAME: &str = "skim";
+pub const SYNC_ENTRY_POINT_NAME: &str = "sync";
+pub const GET_RESERVES_ENTRY_POINT_NAME: &str = "get_reserves";
+pub const GET_CUMULATIVE_PRICES_ENTRY_POINT_NAME: &str = "get_cumulative_prices";
+pub const GET_SWAP_FEE_ENTRY_POINT_NAME: &str = "get_swap_fee";
+pub const SET_SWAP_FEE_ENTRY_POINT_NAME: &str = "set_swap_fee";
+/// Entry point called on the `to` contract package of a flash swap before the K check.
+pub const SWAPPERY_CALL_ENTRY_POINT_NAME: &str = "swappery_call";
+
+// runtime args
+pub const TOKEN0_RUNTIME_ARG_NAME: &str = "token0";
+pub const TOKEN1_RUNTIME_ARG_NAME: &str = "token1";
+pub const TOKEN_RUNTIME_ARG_NAME: &str = "token";
+pub const TOKEN_IN_RUNTIME_ARG_NAME: &str = "token_in";
+pub const TOKEN_OUT_RUNTIME_ARG_NAME: &str = "token_out";
+pub const AMOUNT0_DESIRED_RUNTIME_ARG_NAME: &str = "amount0_desired";
+pub const AMOUNT1_DESIRED_RUNTIME_ARG_NAME: &str = "amount1_desired";
+pub const AMOUNT0_MIN_RUNTIME_ARG_NAME: &str = "amount0_min";
+pub const AMOUNT1_MIN_RUNTIME_ARG_NAME: &str = "amount1_min";
+pub const AMOUNT_TOKEN_DESIRED_RUNTIME_ARG_NAME: &str = "amount_token_desired";
+pub const AMOUNT_CSPR_DESIRED_RUNTIME_ARG_NAME: &str = "amount_cspr_desired";
+pub const AMOUNT_TOKEN_MIN_RUNTIME_ARG_NAME: &str = "amount_token_min";
+pub const AMOUNT_CSPR_MIN_RUNTIME_ARG_NAME: &str = "amount_cspr_min";
+pub const AMOUNT0_RUNTIME_ARG_NAME: &str = "amount0";
+pub const AMOUNT1_RUNTIME_ARG_NAME: &str = "amount1";
+pub const AMOUNT_RUNTIME_ARG_NAME: &str = "amount";
+pub const AMOUNT_IN_RUNTIME_ARG_NAME: &str = "amount_in";
+pub const AMOUNT_OUT_RUNTIME_ARG_NAME: &str = "amount_out";
+pub const AMOUNT_IN_MAX_RUNTIME_ARG_NAME: &str = "amount_in_max";
+pub const AMOUNT_OUT_MIN_RUNTIME_ARG_NAME: &str = "amount_out_min";
+pub const AMOUNT_A_RUNTIME_ARG_NAME: &str = "amount_a";
+pub const RESERVE_A_RUNTIME_ARG_NAME: &str = "reserve_a";
+pub const RESERVE_B_RUNTIME_ARG_NAME: &str = "reserve_b";
+pub const LIQUIDITY_RUNTIME_ARG_NAME: &str = "liquidity";
+pub const PATH_RUNTIME_ARG_NAME: &str = "path";
+pub const LEGS_RUNTIME_ARG_NAME: &str = "legs";
+pub const INTERMEDIATES_RUNTIME_ARG_NAME: &str = "intermediates";
+pub const BASE_TOKENS_RUNTIME_ARG_NAME: &str = "base_tokens";
+pub const INDEX_RUNTIME_ARG_NAME: &str = "index";
+pub const OFFSET_RUNTIME_ARG_NAME: &str = "offset";
+pub const LIMIT_RUNTIME_ARG_NAME: &str = "limit";
+pub const TO_RUNTIME_ARG_NAME: &str = "to";
+pub const DEAD_LINE_RUNTIME_ARG_NAME: &str = "dead_line";
+pub const PURSE_RUNTIME_ARG_NAME: &str = "purse";
+pub const SWAP_FEE_BPS_RUNTIME_ARG_NAME: &str = "swap_fee_bps";
+pub const PROTOCOL_FEE_SHARE_BPS_RUNTIME_ARG_NAME: &str = "protocol_fee_share_bps";
+pub const FEETO_RUNTIME_ARG_NAME: &str = "feeto";
+pub const FEETO_SETTER_RUNTIME_ARG_NAME: &str = "feeto_setter";
+pub const WCSPR_TOKEN_RUNTIME_ARG_NAME: &str = "wcspr_token";
+pub const CONTRACT_KEY_NAME_RUNTIME_ARG_NAME: &str = "contract_key_name";
+pub const ADDRESS_RUNTIME_ARG_NAME: &str = "address";
+pub const OWNER_RUNTIME_ARG_NAME: &str = "owner";
+pub const SPENDER_RUNTIME_ARG_NAME: &str = "spender";
+pub const RECIPIENT_RUNTIME_ARG_NAME: &str = "recipient";
+pub const DATA_RUNTIME_ARG_NAME: &str = "data";
+pub const SENDER_RUNTIME_ARG_NAME: &str = "sender";
+pub const FACTORY_RUNTIME_ARG_NAME: &str = "factory";
+pub const NAME_RUNTIME_ARG_NAME: &str = "name";
+pub const SYMBOL_RUNTIME_ARG_NAME: &str = "symbol";
+pub const DECIMALS_RUNTIME_ARG_NAME: &str = "decimals";
+pub const TOTAL_SUPPLY_RUNTIME_ARG_NAME: &str = "total_supply";
+
+// cspr session args
+pub const ROUTER_RUNTIME_ARG_NAME: &str = "router";
+pub const ENTRY_POINT_RUNTIME_ARG_NAME: &str = "entry_point";
@@ -0,0 +1,63 @@
+//! Conversion of call fields into the values the contracts read.
+
+use casper_types::{
+    bytesrepr::Bytes, bytesrepr::ToBytes, CLTyped, ContractHash, Key, RuntimeArgs, URef, U256,
+};
+
+/// A call field, passed to the contract as [`ToArg::Arg`].
+pub trait ToArg {
+    type Arg: CLTyped + ToBytes;
+
+    fn to_arg(&self) -> Self::Arg;
+}
+
+macro_rules! impl_to_arg_as_is {
+    ($($ty:ty),*) => {
+        $(
+            impl ToArg for $ty {
+                type Arg = $ty;
+
+                fn to_arg(&self) -> $ty {
+                    self.clone()
+                }
+            }
+        )*
+    };
+}
+
+impl_to_arg_as_is!(u32, u64, U256, String, Key, URef, Bytes);
+
+/// Token contracts are passed to the router as [`Key::Hash`].
+impl ToArg for ContractHash {
+    type Arg = Key;
+
+    fn to_arg(&self) -> Key {
+        Key::from(*self)
+    }
+}
+
+impl<T: ToArg> ToArg for Vec<T> {
+    type Arg = Vec<T::Arg>;
+
+    fn to_arg(&self) -> Self::Arg {
+        self.iter().map(ToArg::to_arg).collect()
+    }
+}
+
+impl<A: ToArg, B: ToArg> ToArg for (A, B) {
+    type Arg = (A::Arg, B::Arg);
+
+    fn to_arg(&self) -> Self::Arg {
+        (self.0.to_arg(), self.1.to_arg())
+    }
+}
+
+/// Inserts `value` into `args` under `name`.
+///
+/// # Panics
+///
+/// Panics if `value` fails to serialize, which none of the argument types above do.
+pub fn insert<T: ToArg>(args: &mut RuntimeArgs, name: &str, value: &T) {
+    args.insert(name, value.to_arg())
+        .expect("runtime arg should serialize");
+}
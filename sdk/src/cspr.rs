@@ -0,0 +1,82 @@
+//! Router calls paying native CSPR in, made through the `swappery_cspr_session` wasm.
+//!
+//! A contract cannot pull CSPR out of an account, so these entry points take a `purse` the session
+//! funds from the caller's main purse. Their call structs leave `purse` out; run them with
+//! [`CsprSessionCall::session_args`] instead of calling the router directly.
+
+#[cfg(feature = "engine")]
+use casper_execution_engine::core::engine_state::executable_deploy_item::ExecutableDeployItem;
+#[cfg(feature = "engine")]
+use casper_types::bytesrepr::Bytes;
+use casper_types::{ContractHash, ContractPackageHash, Key, RuntimeArgs, U256};
+
+use crate::args;
+use crate::names::*;
+use crate::EntryPointCall;
+
+/// A router call funded with CSPR by the session wasm.
+pub trait CsprSessionCall: EntryPointCall {
+    /// Returns the session's arguments for making this call against `router`.
+    fn session_args(&self, router: ContractPackageHash) -> RuntimeArgs {
+        let mut runtime_args = self.runtime_args();
+        args::insert(
+            &mut runtime_args,
+            ROUTER_RUNTIME_ARG_NAME,
+            &Key::Hash(router.value()),
+        );
+        args::insert(
+            &mut runtime_args,
+            ENTRY_POINT_RUNTIME_ARG_NAME,
+            &String::from(Self::ENTRY_POINT),
+        );
+        runtime_args
+    }
+
+    /// Returns the session running `session_wasm` to make this call against `router`.
+    #[cfg(feature = "engine")]
+    fn session(&self, router: ContractPackageHash, session_wasm: Bytes) -> ExecutableDeployItem {
+        ExecutableDeployItem::ModuleBytes {
+            module_bytes: session_wasm,
+            args: self.session_args(router),
+        }
+    }
+}
+
+entry_point_call! {
+    /// Adds liquidity to the pair of `token` and WCSPR, wrapping `amount_cspr_desired` CSPR.
+    AddLiquidityCspr => ADD_LIQUIDITY_CSPR_ENTRY_POINT_NAME {
+        token: ContractHash => TOKEN_RUNTIME_ARG_NAME,
+        amount_token_desired: U256 => AMOUNT_TOKEN_DESIRED_RUNTIME_ARG_NAME,
+        amount_cspr_desired: U256 => AMOUNT_CSPR_DESIRED_RUNTIME_ARG_NAME,
+        amount_token_min: U256 => AMOUNT_TOKEN_MIN_RUNTIME_ARG_NAME,
+        amount_cspr_min: U256 => AMOUNT_CSPR_MIN_RUNTIME_ARG_NAME,
+        to: Key => TO_RUNTIME_ARG_NAME,
+        dead_line: U256 => DEAD_LINE_RUNTIME_ARG_NAME,
+    }
+}
+
+entry_point_call! {
+    /// Wraps exactly `amount_in` CSPR and swaps it along `path`, starting at WCSPR.
+    SwapExactCsprForTokens => SWAP_EXACT_CSPR_FOR_TOKENS_ENTRY_POINT_NAME {
+        amount_in: U256 => AMOUNT_IN_RUNTIME_ARG_NAME,
+        amount_out_min: U256 => AMOUNT_OUT_MIN_RUNTIME_ARG_NAME,
+        path: Vec<ContractHash> => PATH_RUNTIME_ARG_NAME,
+        to: Key => TO_RUNTIME_ARG_NAME,
+        dead_line: U256 => DEAD_LINE_RUNTIME_ARG_NAME,
+    }
+}
+
+entry_point_call! {
+    /// Wraps at most `amount_in_max` CSPR and swaps it along `path` for exactly `amount_out`.
+    SwapCsprForExactTokens => SWAP_CSPR_FOR_EXACT_TOKENS_ENTRY_POINT_NAME {
+        amount_out: U256 => AMOUNT_OUT_RUNTIME_ARG_NAME,
+        amount_in_max: U256 => AMOUNT_IN_MAX_RUNTIME_ARG_NAME,
+        path: Vec<ContractHash> => PATH_RUNTIME_ARG_NAME,
+        to: Key => TO_RUNTIME_ARG_NAME,
+        dead_line: U256 => DEAD_LINE_RUNTIME_ARG_NAME,
+    }
+}
+
+impl CsprSessionCall for AddLiquidityCspr {}
+impl CsprSessionCall for SwapExactCsprForTokens {}
+impl CsprSessionCall for SwapCsprForExactTokens {}
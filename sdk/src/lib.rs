@@ -0,0 +1,58 @@
+//! Host-side builders for calls into the Swappery router and pair contracts.
+//!
+//! Every entry point has a struct in [`router`] or [`pair`] whose fields are the entry point's
+//! arguments, typed the way callers hold them. [`EntryPointCall::runtime_args`] turns it into the
+//! [`RuntimeArgs`] the contract reads, converting token hashes into the [`Key`](casper_types::Key)s
+//! the router expects, so argument names and encodings live in one place.
+
+#[macro_use]
+mod macros;
+
+pub mod args;
+pub mod cspr;
+pub mod pair;
+pub mod router;
+
+pub use swappery_names as names;
+
+#[cfg(feature = "engine")]
+use casper_execution_engine::core::engine_state::executable_deploy_item::ExecutableDeployItem;
+use casper_types::{ContractPackageHash, RuntimeArgs};
+
+/// A call to a contract entry point.
+pub trait EntryPointCall {
+    /// Name of the entry point.
+    const ENTRY_POINT: &'static str;
+
+    /// Returns the arguments of the call.
+    fn runtime_args(&self) -> RuntimeArgs;
+
+    /// Returns the call against the latest version of the contract `package`.
+    fn stored_call(&self, package: ContractPackageHash) -> StoredCall {
+        StoredCall {
+            package,
+            entry_point: String::from(Self::ENTRY_POINT),
+            args: self.runtime_args(),
+        }
+    }
+}
+
+/// An entry point call bound to the contract package it is made against.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StoredCall {
+    pub package: ContractPackageHash,
+    pub entry_point: String,
+    pub args: RuntimeArgs,
+}
+
+#[cfg(feature = "engine")]
+impl From<StoredCall> for ExecutableDeployItem {
+    fn from(call: StoredCall) -> Self {
+        Self::StoredVersionedContractByHash {
+            hash: call.package,
+            version: None,
+            entry_point: call.entry_point,
+            args: call.args,
+        }
+    }
+}
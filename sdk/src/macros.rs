@@ -0,0 +1,26 @@
+/// Declares a call struct for an entry point, with one field per runtime argument.
+macro_rules! entry_point_call {
+    (
+        $(#[$meta:meta])*
+        $name:ident => $entry_point:path {
+            $( $(#[$field_meta:meta])* $field:ident: $ty:ty => $arg:path ),* $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, PartialEq, Eq)]
+        pub struct $name {
+            $( $(#[$field_meta])* pub $field: $ty, )*
+        }
+
+        impl $crate::EntryPointCall for $name {
+            const ENTRY_POINT: &'static str = $entry_point;
+
+            fn runtime_args(&self) -> casper_types::RuntimeArgs {
+                #[allow(unused_mut)]
+                let mut args = casper_types::RuntimeArgs::new();
+                $( $crate::args::insert(&mut args, $arg, &self.$field); )*
+                args
+            }
+        }
+    };
+}
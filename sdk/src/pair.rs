@@ -0,0 +1,128 @@
+//! Calls into a pair contract.
+//!
+//! The pair is an ERC-20 of its liquidity, so holders are given as a [`Key`]: `Key::Account` for
+//! an account and `Key::Hash` for a contract package, which is how the pair encodes its addresses.
+//! `mint`, `burn` and `swap` expect the tokens to have been transferred to the pair first, which
+//! the router does for regular users.
+
+use casper_types::{bytesrepr::Bytes, Key, U256};
+
+use crate::names::*;
+
+entry_point_call! {
+    /// Returns the name of the liquidity token.
+    Name => NAME_ENTRY_POINT_NAME {}
+}
+
+entry_point_call! {
+    /// Returns the symbol of the liquidity token.
+    Symbol => SYMBOL_ENTRY_POINT_NAME {}
+}
+
+entry_point_call! {
+    /// Returns the decimals of the liquidity token.
+    Decimals => DECIMALS_ENTRY_POINT_NAME {}
+}
+
+entry_point_call! {
+    /// Returns the liquidity minted so far.
+    TotalSupply => TOTAL_SUPPLY_ENTRY_POINT_NAME {}
+}
+
+entry_point_call! {
+    /// Returns the liquidity held by `address`.
+    BalanceOf => BALANCE_OF_ENTRY_POINT_NAME {
+        address: Key => ADDRESS_RUNTIME_ARG_NAME,
+    }
+}
+
+entry_point_call! {
+    /// Transfers `amount` of the caller's liquidity to `recipient`.
+    Transfer => TRANSFER_ENTRY_POINT_NAME {
+        recipient: Key => RECIPIENT_RUNTIME_ARG_NAME,
+        amount: U256 => AMOUNT_RUNTIME_ARG_NAME,
+    }
+}
+
+entry_point_call! {
+    /// Lets `spender` transfer up to `amount` of the caller's liquidity.
+    Approve => APPROVE_ENTRY_POINT_NAME {
+        spender: Key => SPENDER_RUNTIME_ARG_NAME,
+        amount: U256 => AMOUNT_RUNTIME_ARG_NAME,
+    }
+}
+
+entry_point_call! {
+    /// Returns how much of `owner`'s liquidity `spender` may transfer.
+    Allowance => ALLOWANCE_ENTRY_POINT_NAME {
+        owner: Key => OWNER_RUNTIME_ARG_NAME,
+        spender: Key => SPENDER_RUNTIME_ARG_NAME,
+    }
+}
+
+entry_point_call! {
+    /// Transfers `amount` of `owner`'s liquidity to `recipient` out of the caller's allowance.
+    TransferFrom => TRANSFER_FROM_ENTRY_POINT_NAME {
+        owner: Key => OWNER_RUNTIME_ARG_NAME,
+        recipient: Key => RECIPIENT_RUNTIME_ARG_NAME,
+        amount: U256 => AMOUNT_RUNTIME_ARG_NAME,
+    }
+}
+
+entry_point_call! {
+    /// Mints liquidity to `to` for the tokens transferred in since the last update.
+    Mint => MINT_ENTRY_POINT_NAME {
+        to: Key => TO_RUNTIME_ARG_NAME,
+    }
+}
+
+entry_point_call! {
+    /// Burns the liquidity transferred in and pays both tokens out to `to`.
+    Burn => BURN_ENTRY_POINT_NAME {
+        to: Key => TO_RUNTIME_ARG_NAME,
+    }
+}
+
+entry_point_call! {
+    /// Pays `amount0` and `amount1` out to `to`, calling it back with `data` when not empty.
+    Swap => SWAP_ENTRY_POINT_NAME {
+        amount0: U256 => AMOUNT0_RUNTIME_ARG_NAME,
+        amount1: U256 => AMOUNT1_RUNTIME_ARG_NAME,
+        to: Key => TO_RUNTIME_ARG_NAME,
+        data: Bytes => DATA_RUNTIME_ARG_NAME,
+    }
+}
+
+entry_point_call! {
+    /// Transfers the balances above the reserves to `to`.
+    Skim => SKIM_ENTRY_POINT_NAME {
+        to: Key => TO_RUNTIME_ARG_NAME,
+    }
+}
+
+entry_point_call! {
+    /// Sets the reserves to the balances.
+    Sync => SYNC_ENTRY_POINT_NAME {}
+}
+
+entry_point_call! {
+    /// Returns the reserves and the block time of their last update.
+    GetReserves => GET_RESERVES_ENTRY_POINT_NAME {}
+}
+
+entry_point_call! {
+    /// Returns the price accumulators and the block time of their last update.
+    GetCumulativePrices => GET_CUMULATIVE_PRICES_ENTRY_POINT_NAME {}
+}
+
+entry_point_call! {
+    /// Returns the swap fee, in basis points.
+    GetSwapFee => GET_SWAP_FEE_ENTRY_POINT_NAME {}
+}
+
+entry_point_call! {
+    /// Sets the swap fee; the factory only.
+    SetSwapFee => SET_SWAP_FEE_ENTRY_POINT_NAME {
+        swap_fee_bps: u32 => SWAP_FEE_BPS_RUNTIME_ARG_NAME,
+    }
+}
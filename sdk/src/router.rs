@@ -0,0 +1,302 @@
+//! Calls into the router contract.
+//!
+//! Tokens are given as the [`ContractHash`] of their contract and passed as [`Key::Hash`], and
+//! recipients as the [`Key`] of an account or contract package. Entry points taking native CSPR are
+//! in [`crate::cspr`].
+
+use casper_types::{ContractHash, Key, RuntimeArgs, U256};
+
+use crate::args;
+use crate::names::*;
+
+/// Arguments of the router's installer session.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Install {
+    pub wcspr_token: ContractHash,
+    /// Account or contract package receiving the protocol fee.
+    pub feeto: Key,
+    pub feeto_setter: Key,
+    /// Named key the router package is stored under in the installing account.
+    pub contract_key_name: String,
+}
+
+impl Install {
+    pub fn runtime_args(&self) -> RuntimeArgs {
+        let mut runtime_args = RuntimeArgs::new();
+        args::insert(&mut runtime_args, WCSPR_TOKEN_RUNTIME_ARG_NAME, &self.wcspr_token);
+        args::insert(&mut runtime_args, FEETO_RUNTIME_ARG_NAME, &self.feeto);
+        args::insert(&mut runtime_args, FEETO_SETTER_RUNTIME_ARG_NAME, &self.feeto_setter);
+        args::insert(
+            &mut runtime_args,
+            CONTRACT_KEY_NAME_RUNTIME_ARG_NAME,
+            &self.contract_key_name,
+        );
+        runtime_args
+    }
+}
+
+entry_point_call! {
+    /// Deploys the pair of `token0` and `token1`.
+    CreatePair => CREATE_PAIR_ENTRY_POINT_NAME {
+        token0: ContractHash => TOKEN0_RUNTIME_ARG_NAME,
+        token1: ContractHash => TOKEN1_RUNTIME_ARG_NAME,
+    }
+}
+
+entry_point_call! {
+    /// Returns the pair of `token0` and `token1`.
+    GetPair => GET_PAIR_ENTRY_POINT_NAME {
+        token0: ContractHash => TOKEN0_RUNTIME_ARG_NAME,
+        token1: ContractHash => TOKEN1_RUNTIME_ARG_NAME,
+    }
+}
+
+entry_point_call! {
+    /// Returns the number of pairs the router deployed.
+    AllPairsLength => ALL_PAIRS_LENGTH_ENTRY_POINT_NAME {}
+}
+
+entry_point_call! {
+    /// Returns the `index`-th pair the router deployed.
+    PairAt => PAIR_AT_ENTRY_POINT_NAME {
+        index: u64 => INDEX_RUNTIME_ARG_NAME,
+    }
+}
+
+entry_point_call! {
+    /// Returns up to `limit` pairs of `token`, skipping the first `offset`.
+    PairsForToken => PAIRS_FOR_TOKEN_ENTRY_POINT_NAME {
+        token: ContractHash => TOKEN_RUNTIME_ARG_NAME,
+        offset: u64 => OFFSET_RUNTIME_ARG_NAME,
+        limit: u64 => LIMIT_RUNTIME_ARG_NAME,
+    }
+}
+
+entry_point_call! {
+    /// Returns the tokens every best-path search routes through.
+    GetBaseTokens => GET_BASE_TOKENS_ENTRY_POINT_NAME {}
+}
+
+entry_point_call! {
+    /// Sets the tokens every best-path search routes through; `feeto_setter` only.
+    SetBaseTokens => SET_BASE_TOKENS_ENTRY_POINT_NAME {
+        base_tokens: Vec<ContractHash> => BASE_TOKENS_RUNTIME_ARG_NAME,
+    }
+}
+
+entry_point_call! {
+    /// Returns the best amount out for `amount_in` and the path yielding it.
+    GetBestAmountOut => GET_BEST_AMOUNT_OUT_ENTRY_POINT_NAME {
+        token_in: ContractHash => TOKEN_IN_RUNTIME_ARG_NAME,
+        token_out: ContractHash => TOKEN_OUT_RUNTIME_ARG_NAME,
+        amount_in: U256 => AMOUNT_IN_RUNTIME_ARG_NAME,
+        intermediates: Vec<ContractHash> => INTERMEDIATES_RUNTIME_ARG_NAME,
+    }
+}
+
+entry_point_call! {
+    /// Returns the protocol fee recipient.
+    GetFeeto => GET_FEETO_ENTRY_POINT_NAME {}
+}
+
+entry_point_call! {
+    /// Sets the protocol fee recipient; `feeto_setter` only.
+    SetFeeto => SET_FEETO_ENTRY_POINT_NAME {
+        feeto: Key => FEETO_RUNTIME_ARG_NAME,
+    }
+}
+
+entry_point_call! {
+    /// Hands the fee settings over to `feeto_setter`; `feeto_setter` only.
+    SetFeetoSetter => SET_FEETO_SETTER_ENTRY_POINT_NAME {
+        feeto_setter: Key => FEETO_SETTER_RUNTIME_ARG_NAME,
+    }
+}
+
+entry_point_call! {
+    /// Returns the protocol's share of fee growth, in basis points.
+    GetProtocolFeeShare => GET_PROTOCOL_FEE_SHARE_ENTRY_POINT_NAME {}
+}
+
+entry_point_call! {
+    /// Sets the protocol's share of fee growth; `feeto_setter` only.
+    SetProtocolFeeShare => SET_PROTOCOL_FEE_SHARE_ENTRY_POINT_NAME {
+        protocol_fee_share_bps: u32 => PROTOCOL_FEE_SHARE_BPS_RUNTIME_ARG_NAME,
+    }
+}
+
+entry_point_call! {
+    /// Sets the swap fee of the pair of `token0` and `token1`; `feeto_setter` only.
+    SetPairSwapFee => SET_PAIR_SWAP_FEE_ENTRY_POINT_NAME {
+        token0: ContractHash => TOKEN0_RUNTIME_ARG_NAME,
+        token1: ContractHash => TOKEN1_RUNTIME_ARG_NAME,
+        swap_fee_bps: u32 => SWAP_FEE_BPS_RUNTIME_ARG_NAME,
+    }
+}
+
+entry_point_call! {
+    /// Returns the amounts every hop along `path` yields for `amount_in`.
+    GetAmountsOut => GET_AMOUNTS_OUT_ENTRY_POINT_NAME {
+        amount_in: U256 => AMOUNT_IN_RUNTIME_ARG_NAME,
+        path: Vec<ContractHash> => PATH_RUNTIME_ARG_NAME,
+    }
+}
+
+entry_point_call! {
+    /// Returns the amounts every hop along `path` needs to yield `amount_out`.
+    GetAmountsIn => GET_AMOUNTS_IN_ENTRY_POINT_NAME {
+        amount_out: U256 => AMOUNT_OUT_RUNTIME_ARG_NAME,
+        path: Vec<ContractHash> => PATH_RUNTIME_ARG_NAME,
+    }
+}
+
+entry_point_call! {
+    /// Returns the amount of B matching `amount_a` at the given reserves.
+    Quote => QUOTE_ENTRY_POINT_NAME {
+        amount_a: U256 => AMOUNT_A_RUNTIME_ARG_NAME,
+        reserve_a: U256 => RESERVE_A_RUNTIME_ARG_NAME,
+        reserve_b: U256 => RESERVE_B_RUNTIME_ARG_NAME,
+    }
+}
+
+entry_point_call! {
+    /// Adds liquidity to the pair of `token0` and `token1`, which must already exist.
+    AddLiquidity => ADD_LIQUIDITY_ENTRY_POINT_NAME {
+        token0: ContractHash => TOKEN0_RUNTIME_ARG_NAME,
+        token1: ContractHash => TOKEN1_RUNTIME_ARG_NAME,
+        amount0_desired: U256 => AMOUNT0_DESIRED_RUNTIME_ARG_NAME,
+        amount1_desired: U256 => AMOUNT1_DESIRED_RUNTIME_ARG_NAME,
+        amount0_min: U256 => AMOUNT0_MIN_RUNTIME_ARG_NAME,
+        amount1_min: U256 => AMOUNT1_MIN_RUNTIME_ARG_NAME,
+        to: Key => TO_RUNTIME_ARG_NAME,
+        dead_line: U256 => DEAD_LINE_RUNTIME_ARG_NAME,
+    }
+}
+
+entry_point_call! {
+    /// Burns `liquidity` of the pair of `token0` and `token1`.
+    RemoveLiquidity => REMOVE_LIQUIDITY_ENTRY_POINT_NAME {
+        token0: ContractHash => TOKEN0_RUNTIME_ARG_NAME,
+        token1: ContractHash => TOKEN1_RUNTIME_ARG_NAME,
+        liquidity: U256 => LIQUIDITY_RUNTIME_ARG_NAME,
+        amount0_min: U256 => AMOUNT0_MIN_RUNTIME_ARG_NAME,
+        amount1_min: U256 => AMOUNT1_MIN_RUNTIME_ARG_NAME,
+        to: Key => TO_RUNTIME_ARG_NAME,
+        dead_line: U256 => DEAD_LINE_RUNTIME_ARG_NAME,
+    }
+}
+
+entry_point_call! {
+    /// [`RemoveLiquidity`] for tokens taking a fee on transfer.
+    RemoveLiquiditySupportingFee => REMOVE_LIQUIDITY_SUPPORTING_FEE_ENTRY_POINT_NAME {
+        token0: ContractHash => TOKEN0_RUNTIME_ARG_NAME,
+        token1: ContractHash => TOKEN1_RUNTIME_ARG_NAME,
+        liquidity: U256 => LIQUIDITY_RUNTIME_ARG_NAME,
+        amount0_min: U256 => AMOUNT0_MIN_RUNTIME_ARG_NAME,
+        amount1_min: U256 => AMOUNT1_MIN_RUNTIME_ARG_NAME,
+        to: Key => TO_RUNTIME_ARG_NAME,
+        dead_line: U256 => DEAD_LINE_RUNTIME_ARG_NAME,
+    }
+}
+
+entry_point_call! {
+    /// Burns `liquidity` of the pair of `token` and WCSPR, paying the WCSPR out as CSPR.
+    RemoveLiquidityCspr => REMOVE_LIQUIDITY_CSPR_ENTRY_POINT_NAME {
+        token: ContractHash => TOKEN_RUNTIME_ARG_NAME,
+        liquidity: U256 => LIQUIDITY_RUNTIME_ARG_NAME,
+        amount_token_min: U256 => AMOUNT_TOKEN_MIN_RUNTIME_ARG_NAME,
+        amount_cspr_min: U256 => AMOUNT_CSPR_MIN_RUNTIME_ARG_NAME,
+        to: Key => TO_RUNTIME_ARG_NAME,
+        dead_line: U256 => DEAD_LINE_RUNTIME_ARG_NAME,
+    }
+}
+
+entry_point_call! {
+    /// [`RemoveLiquidityCspr`] for tokens taking a fee on transfer.
+    RemoveLiquidityCsprSupportingFee => REMOVE_LIQUIDITY_CSPR_SUPPORTING_FEE_ENTRY_POINT_NAME {
+        token: ContractHash => TOKEN_RUNTIME_ARG_NAME,
+        liquidity: U256 => LIQUIDITY_RUNTIME_ARG_NAME,
+        amount_token_min: U256 => AMOUNT_TOKEN_MIN_RUNTIME_ARG_NAME,
+        amount_cspr_min: U256 => AMOUNT_CSPR_MIN_RUNTIME_ARG_NAME,
+        to: Key => TO_RUNTIME_ARG_NAME,
+        dead_line: U256 => DEAD_LINE_RUNTIME_ARG_NAME,
+    }
+}
+
+entry_point_call! {
+    /// Swaps exactly `amount_in` along `path`.
+    SwapExactTokensForTokens => SWAP_EXACT_TOKENS_FOR_TOKENS_ENTRY_POINT_NAME {
+        amount_in: U256 => AMOUNT_IN_RUNTIME_ARG_NAME,
+        amount_out_min: U256 => AMOUNT_OUT_MIN_RUNTIME_ARG_NAME,
+        path: Vec<ContractHash> => PATH_RUNTIME_ARG_NAME,
+        to: Key => TO_RUNTIME_ARG_NAME,
+        dead_line: U256 => DEAD_LINE_RUNTIME_ARG_NAME,
+    }
+}
+
+entry_point_call! {
+    /// [`SwapExactTokensForTokens`] for tokens taking a fee on transfer.
+    SwapExactTokensForTokensSupportingFee => SWAP_EXACT_TOKENS_FOR_TOKENS_SUPPORTING_FEE_ENTRY_POINT_NAME {
+        amount_in: U256 => AMOUNT_IN_RUNTIME_ARG_NAME,
+        amount_out_min: U256 => AMOUNT_OUT_MIN_RUNTIME_ARG_NAME,
+        path: Vec<ContractHash> => PATH_RUNTIME_ARG_NAME,
+        to: Key => TO_RUNTIME_ARG_NAME,
+        dead_line: U256 => DEAD_LINE_RUNTIME_ARG_NAME,
+    }
+}
+
+entry_point_call! {
+    /// Swaps each leg's amount along its path, checking `amount_out_min` against the total.
+    SwapExactTokensForTokensSplit => SWAP_EXACT_TOKENS_FOR_TOKENS_SPLIT_ENTRY_POINT_NAME {
+        legs: Vec<(Vec<ContractHash>, U256)> => LEGS_RUNTIME_ARG_NAME,
+        amount_out_min: U256 => AMOUNT_OUT_MIN_RUNTIME_ARG_NAME,
+        to: Key => TO_RUNTIME_ARG_NAME,
+        dead_line: U256 => DEAD_LINE_RUNTIME_ARG_NAME,
+    }
+}
+
+entry_point_call! {
+    /// Swaps exactly `amount_in` along the best path the router finds at execution time.
+    SwapExactTokensForTokensBest => SWAP_EXACT_TOKENS_FOR_TOKENS_BEST_ENTRY_POINT_NAME {
+        token_in: ContractHash => TOKEN_IN_RUNTIME_ARG_NAME,
+        token_out: ContractHash => TOKEN_OUT_RUNTIME_ARG_NAME,
+        amount_in: U256 => AMOUNT_IN_RUNTIME_ARG_NAME,
+        amount_out_min: U256 => AMOUNT_OUT_MIN_RUNTIME_ARG_NAME,
+        intermediates: Vec<ContractHash> => INTERMEDIATES_RUNTIME_ARG_NAME,
+        to: Key => TO_RUNTIME_ARG_NAME,
+        dead_line: U256 => DEAD_LINE_RUNTIME_ARG_NAME,
+    }
+}
+
+entry_point_call! {
+    /// Swaps at most `amount_in_max` along `path` for exactly `amount_out`.
+    SwapTokensForExactTokens => SWAP_TOKENS_FOR_EXACT_TOKENS_ENTRY_POINT_NAME {
+        amount_out: U256 => AMOUNT_OUT_RUNTIME_ARG_NAME,
+        amount_in_max: U256 => AMOUNT_IN_MAX_RUNTIME_ARG_NAME,
+        path: Vec<ContractHash> => PATH_RUNTIME_ARG_NAME,
+        to: Key => TO_RUNTIME_ARG_NAME,
+        dead_line: U256 => DEAD_LINE_RUNTIME_ARG_NAME,
+    }
+}
+
+entry_point_call! {
+    /// Swaps exactly `amount_in` along `path`, ending in WCSPR, and pays the WCSPR out as CSPR.
+    SwapExactTokensForCspr => SWAP_EXACT_TOKENS_FOR_CSPR_ENTRY_POINT_NAME {
+        amount_in: U256 => AMOUNT_IN_RUNTIME_ARG_NAME,
+        amount_out_min: U256 => AMOUNT_OUT_MIN_RUNTIME_ARG_NAME,
+        path: Vec<ContractHash> => PATH_RUNTIME_ARG_NAME,
+        to: Key => TO_RUNTIME_ARG_NAME,
+        dead_line: U256 => DEAD_LINE_RUNTIME_ARG_NAME,
+    }
+}
+
+entry_point_call! {
+    /// Swaps at most `amount_in_max` along `path`, ending in WCSPR, for exactly `amount_out` CSPR.
+    SwapTokensForExactCspr => SWAP_TOKENS_FOR_EXACT_CSPR_ENTRY_POINT_NAME {
+        amount_out: U256 => AMOUNT_OUT_RUNTIME_ARG_NAME,
+        amount_in_max: U256 => AMOUNT_IN_MAX_RUNTIME_ARG_NAME,
+        path: Vec<ContractHash> => PATH_RUNTIME_ARG_NAME,
+        to: Key => TO_RUNTIME_ARG_NAME,
+        dead_line: U256 => DEAD_LINE_RUNTIME_ARG_NAME,
+    }
+}
@@ -0,0 +1,91 @@
+//! Operations a script replays, one struct per router entry point it models.
+//!
+//! Fields follow the router's arguments, minus the recipient and deadline: outputs leave the
+//! simulated pairs and time does not pass.
+
+use casper_types::U256;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CreatePair<T> {
+    pub token_a: T,
+    pub token_b: T,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AddLiquidity<T, A> {
+    pub provider: A,
+    pub token_a: T,
+    pub token_b: T,
+    pub amount_a_desired: U256,
+    pub amount_b_desired: U256,
+    pub amount_a_min: U256,
+    pub amount_b_min: U256,
+}
+
+/// Removes liquidity for `provider`.
+///
+/// Like the router, checks `amount0_min` and `amount1_min` against the amounts of the pair's
+/// token0 and token1, whatever the order of `token_a` and `token_b`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RemoveLiquidity<T, A> {
+    pub provider: A,
+    pub token_a: T,
+    pub token_b: T,
+    pub liquidity: U256,
+    pub amount0_min: U256,
+    pub amount1_min: U256,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SwapExactTokensForTokens<T> {
+    pub amount_in: U256,
+    pub amount_out_min: U256,
+    pub path: Vec<T>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SwapTokensForExactTokens<T> {
+    pub amount_out: U256,
+    pub amount_in_max: U256,
+    pub path: Vec<T>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SetPairSwapFee<T> {
+    pub token_a: T,
+    pub token_b: T,
+    pub swap_fee_bps: u32,
+}
+
+/// A step of a script.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Action<T, A> {
+    CreatePair(CreatePair<T>),
+    AddLiquidity(AddLiquidity<T, A>),
+    RemoveLiquidity(RemoveLiquidity<T, A>),
+    SwapExactTokensForTokens(SwapExactTokensForTokens<T>),
+    SwapTokensForExactTokens(SwapTokensForExactTokens<T>),
+    /// Sets the protocol fee recipient; `None` turns the protocol fee off.
+    SetFeeto(Option<A>),
+    SetProtocolFeeShare(u32),
+    SetPairSwapFee(SetPairSwapFee<T>),
+}
+
+/// What a successful [`Action`] did.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    /// Amounts of `token_a` and `token_b` deposited and the liquidity minted for them.
+    AddedLiquidity {
+        amount_a: U256,
+        amount_b: U256,
+        liquidity: U256,
+    },
+    /// Amounts of the pair's token0 and token1 paid out.
+    RemovedLiquidity {
+        amount0: U256,
+        amount1: U256,
+    },
+    /// Amounts along the swap's path, from what went in to what came out.
+    Swapped(Vec<U256>),
+    Done,
+}
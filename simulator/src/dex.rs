@@ -0,0 +1,544 @@
+//! Router semantics over a set of simulated pairs.
+
+use std::collections::BTreeMap;
+
+use casper_types::U256;
+use swappery_math as math;
+use swappery_math::{
+    DEFAULT_PROTOCOL_FEE_SHARE_BPS, DEFAULT_SWAP_FEE_BPS, MAX_PROTOCOL_FEE_SHARE_BPS,
+    MAX_SWAP_FEE_BPS,
+};
+
+use crate::action::{
+    Action, AddLiquidity, CreatePair, Outcome, RemoveLiquidity, SetPairSwapFee,
+    SwapExactTokensForTokens, SwapTokensForExactTokens,
+};
+use crate::pair::{Pair, ProtocolFee};
+use crate::Error;
+
+/// Returns `amount_a` and `amount_b` of `token_a` and `token_b` in the order of their pair.
+fn in_pair_order<T: Ord>(token_a: &T, token_b: &T, amount_a: U256, amount_b: U256) -> (U256, U256) {
+    if token_a < token_b {
+        (amount_a, amount_b)
+    } else {
+        (amount_b, amount_a)
+    }
+}
+
+/// A router and the pairs it created, over tokens `T` and accounts `A`.
+///
+/// Every operation either applies in full or, like a reverted deploy, leaves the `Dex` untouched
+/// and returns the reason the contracts would revert with. Operations are priced and settled with
+/// the same `swappery-math` functions as the contracts, so amounts match them to the unit.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Dex<T, A> {
+    pairs: BTreeMap<(T, T), Pair<A>>,
+    feeto: Option<A>,
+    protocol_fee_share_bps: u32,
+}
+
+impl<T: Ord + Clone, A: Ord + Clone> Dex<T, A> {
+    /// Returns a router without pairs, minting the protocol fee to `feeto`.
+    pub fn new(feeto: Option<A>) -> Self {
+        Dex {
+            pairs: BTreeMap::new(),
+            feeto,
+            protocol_fee_share_bps: DEFAULT_PROTOCOL_FEE_SHARE_BPS,
+        }
+    }
+
+    pub fn feeto(&self) -> Option<&A> {
+        self.feeto.as_ref()
+    }
+
+    pub fn protocol_fee_share_bps(&self) -> u32 {
+        self.protocol_fee_share_bps
+    }
+
+    pub fn pair(&self, token_a: &T, token_b: &T) -> Option<&Pair<A>> {
+        self.pairs
+            .get(&math::sort_tokens(token_a.clone(), token_b.clone()))
+    }
+
+    /// Returns every pair with its token0 and token1.
+    pub fn pairs(&self) -> impl Iterator<Item = (&T, &T, &Pair<A>)> {
+        self.pairs
+            .iter()
+            .map(|((token0, token1), pair)| (token0, token1, pair))
+    }
+
+    fn pair_mut(&mut self, token_a: &T, token_b: &T) -> Result<&mut Pair<A>, Error> {
+        self.pairs
+            .get_mut(&math::sort_tokens(token_a.clone(), token_b.clone()))
+            .ok_or(Error::PairNotFound)
+    }
+
+    /// Returns the pair of `token_a` and `token_b` with the protocol fee its liquidity events
+    /// mint.
+    fn pair_with_fee(
+        &mut self,
+        token_a: &T,
+        token_b: &T,
+    ) -> Result<(&mut Pair<A>, ProtocolFee<'_, A>), Error> {
+        let fee = ProtocolFee {
+            feeto: self.feeto.as_ref(),
+            share_bps: self.protocol_fee_share_bps,
+        };
+        let pair = self
+            .pairs
+            .get_mut(&math::sort_tokens(token_a.clone(), token_b.clone()))
+            .ok_or(Error::PairNotFound)?;
+        Ok((pair, fee))
+    }
+
+    /// Returns the reserves of `token_a` and `token_b`, in that order.
+    pub fn get_reserves(&self, token_a: &T, token_b: &T) -> Result<(U256, U256), Error> {
+        let pair = self.pair(token_a, token_b).ok_or(Error::PairNotFound)?;
+        let (reserve0, reserve1) = pair.reserves();
+        if token_a < token_b {
+            Ok((reserve0, reserve1))
+        } else {
+            Ok((reserve1, reserve0))
+        }
+    }
+
+    pub fn get_amounts_out(&self, amount_in: U256, path: &[T]) -> Result<Vec<U256>, Error> {
+        if path.len() < 2 {
+            return Err(Error::InvalidPath);
+        }
+        let mut amounts: Vec<U256> = Vec::with_capacity(path.len());
+        amounts.push(amount_in);
+        for hop in path.windows(2) {
+            let (reserve_in, reserve_out) = self.get_reserves(&hop[0], &hop[1])?;
+            let swap_fee_bps = self.pair(&hop[0], &hop[1]).map_or(0, Pair::swap_fee_bps);
+            let amount = amounts[amounts.len() - 1];
+            amounts.push(math::get_amount_out(
+                amount,
+                reserve_in,
+                reserve_out,
+                swap_fee_bps,
+            )?);
+        }
+        Ok(amounts)
+    }
+
+    pub fn get_amounts_in(&self, amount_out: U256, path: &[T]) -> Result<Vec<U256>, Error> {
+        if path.len() < 2 {
+            return Err(Error::InvalidPath);
+        }
+        let mut amounts: Vec<U256> = Vec::with_capacity(path.len());
+        amounts.push(amount_out);
+        for hop in path.windows(2).rev() {
+            let (reserve_in, reserve_out) = self.get_reserves(&hop[0], &hop[1])?;
+            let swap_fee_bps = self.pair(&hop[0], &hop[1]).map_or(0, Pair::swap_fee_bps);
+            let amount = amounts[amounts.len() - 1];
+            amounts.push(math::get_amount_in(
+                amount,
+                reserve_in,
+                reserve_out,
+                swap_fee_bps,
+            )?);
+        }
+        amounts.reverse();
+        Ok(amounts)
+    }
+
+    /// Runs `operation`, rolling back whatever it changed if it fails.
+    fn atomically<R>(
+        &mut self,
+        operation: impl FnOnce(&mut Self) -> Result<R, Error>,
+    ) -> Result<R, Error> {
+        let snapshot = self.clone();
+        let result = operation(self);
+        if result.is_err() {
+            *self = snapshot;
+        }
+        result
+    }
+
+    pub fn create_pair(&mut self, call: &CreatePair<T>) -> Result<(), Error> {
+        if call.token_a == call.token_b {
+            return Err(Error::IdenticalAddresses);
+        }
+        let key = math::sort_tokens(call.token_a.clone(), call.token_b.clone());
+        if self.pairs.contains_key(&key) {
+            return Err(Error::PairExists);
+        }
+        self.pairs.insert(key, Pair::new(DEFAULT_SWAP_FEE_BPS));
+        Ok(())
+    }
+
+    /// Deposits the desired amounts, less whichever one exceeds the pair's price, and returns
+    /// the amounts of `token_a` and `token_b` deposited and the liquidity minted.
+    pub fn add_liquidity(
+        &mut self,
+        call: &AddLiquidity<T, A>,
+    ) -> Result<(U256, U256, U256), Error> {
+        self.atomically(|dex| {
+            let (reserve_a, reserve_b) = dex.get_reserves(&call.token_a, &call.token_b)?;
+            let (amount_a, amount_b) = if reserve_a.is_zero() && reserve_b.is_zero() {
+                (call.amount_a_desired, call.amount_b_desired)
+            } else {
+                let amount_b_optimal = math::quote(call.amount_a_desired, reserve_a, reserve_b)?;
+                if amount_b_optimal <= call.amount_b_desired {
+                    if amount_b_optimal < call.amount_b_min {
+                        return Err(Error::InsufficientBAmount);
+                    }
+                    (call.amount_a_desired, amount_b_optimal)
+                } else {
+                    let amount_a_optimal =
+                        math::quote(call.amount_b_desired, reserve_b, reserve_a)?;
+                    if amount_a_optimal < call.amount_a_min {
+                        return Err(Error::InsufficientAAmount);
+                    }
+                    (amount_a_optimal, call.amount_b_desired)
+                }
+            };
+
+            let (amount0, amount1) =
+                in_pair_order(&call.token_a, &call.token_b, amount_a, amount_b);
+            let (pair, fee) = dex.pair_with_fee(&call.token_a, &call.token_b)?;
+            pair.deposit(amount0, amount1)?;
+            let liquidity = pair.mint(&call.provider, fee)?;
+            Ok((amount_a, amount_b, liquidity))
+        })
+    }
+
+    /// Burns the provider's liquidity and returns the amounts of the pair's token0 and token1
+    /// paid out.
+    pub fn remove_liquidity(
+        &mut self,
+        call: &RemoveLiquidity<T, A>,
+    ) -> Result<(U256, U256), Error> {
+        self.atomically(|dex| {
+            let (pair, fee) = dex.pair_with_fee(&call.token_a, &call.token_b)?;
+            let (amount0, amount1) = pair.burn(&call.provider, call.liquidity, fee)?;
+            if amount0 < call.amount0_min {
+                return Err(Error::InsufficientAAmount);
+            }
+            if amount1 < call.amount1_min {
+                return Err(Error::InsufficientBAmount);
+            }
+            Ok((amount0, amount1))
+        })
+    }
+
+    pub fn swap_exact_tokens_for_tokens(
+        &mut self,
+        call: &SwapExactTokensForTokens<T>,
+    ) -> Result<Vec<U256>, Error> {
+        self.atomically(|dex| {
+            let amounts = dex.get_amounts_out(call.amount_in, &call.path)?;
+            if amounts[amounts.len() - 1] < call.amount_out_min {
+                return Err(Error::InsufficientOutputAmount);
+            }
+            dex.swap(&amounts, &call.path)?;
+            Ok(amounts)
+        })
+    }
+
+    pub fn swap_tokens_for_exact_tokens(
+        &mut self,
+        call: &SwapTokensForExactTokens<T>,
+    ) -> Result<Vec<U256>, Error> {
+        self.atomically(|dex| {
+            let amounts = dex.get_amounts_in(call.amount_out, &call.path)?;
+            if amounts[0] > call.amount_in_max {
+                return Err(Error::InsufficientInputAmount);
+            }
+            dex.swap(&amounts, &call.path)?;
+            Ok(amounts)
+        })
+    }
+
+    /// Pays `amounts[0]` into the first pair of `path` and swaps hop by hop, each pair sending
+    /// its output straight into the next one.
+    ///
+    /// All amounts are quoted up front, so a path crossing the same pair twice settles its second
+    /// crossing against reserves the quote did not see, as the router's `_swap` does.
+    fn swap(&mut self, amounts: &[U256], path: &[T]) -> Result<(), Error> {
+        let (amount0_in, amount1_in) = in_pair_order(&path[0], &path[1], amounts[0], U256::zero());
+        self.pair_mut(&path[0], &path[1])?
+            .deposit(amount0_in, amount1_in)?;
+
+        for (i, hop) in path.windows(2).enumerate() {
+            let (input, output) = (&hop[0], &hop[1]);
+            let amount_out = amounts[i + 1];
+            let (amount0_out, amount1_out) = in_pair_order(input, output, U256::zero(), amount_out);
+            self.pair_mut(input, output)?
+                .swap_out(amount0_out, amount1_out)?;
+            if let Some(next) = path.get(i + 2) {
+                let (amount0, amount1) = in_pair_order(output, next, amount_out, U256::zero());
+                self.pair_mut(output, next)?.deposit(amount0, amount1)?;
+            }
+            self.pair_mut(input, output)?
+                .settle_swap(amount0_out, amount1_out)?;
+        }
+        Ok(())
+    }
+
+    pub fn set_feeto(&mut self, feeto: Option<A>) {
+        self.feeto = feeto;
+    }
+
+    pub fn set_protocol_fee_share(&mut self, protocol_fee_share_bps: u32) -> Result<(), Error> {
+        if protocol_fee_share_bps > MAX_PROTOCOL_FEE_SHARE_BPS {
+            return Err(Error::InvalidProtocolFeeShare);
+        }
+        self.protocol_fee_share_bps = protocol_fee_share_bps;
+        Ok(())
+    }
+
+    pub fn set_pair_swap_fee(&mut self, call: &SetPairSwapFee<T>) -> Result<(), Error> {
+        if call.swap_fee_bps > MAX_SWAP_FEE_BPS {
+            return Err(Error::InvalidSwapFee);
+        }
+        self.pair_mut(&call.token_a, &call.token_b)?
+            .set_swap_fee_bps(call.swap_fee_bps);
+        Ok(())
+    }
+
+    pub fn apply(&mut self, action: &Action<T, A>) -> Result<Outcome, Error> {
+        match action {
+            Action::CreatePair(call) => self.create_pair(call).map(|()| Outcome::Done),
+            Action::AddLiquidity(call) => {
+                let (amount_a, amount_b, liquidity) = self.add_liquidity(call)?;
+                Ok(Outcome::AddedLiquidity {
+                    amount_a,
+                    amount_b,
+                    liquidity,
+                })
+            }
+            Action::RemoveLiquidity(call) => {
+                let (amount0, amount1) = self.remove_liquidity(call)?;
+                Ok(Outcome::RemovedLiquidity { amount0, amount1 })
+            }
+            Action::SwapExactTokensForTokens(call) => self
+                .swap_exact_tokens_for_tokens(call)
+                .map(Outcome::Swapped),
+            Action::SwapTokensForExactTokens(call) => self
+                .swap_tokens_for_exact_tokens(call)
+                .map(Outcome::Swapped),
+            Action::SetFeeto(feeto) => {
+                self.set_feeto(feeto.clone());
+                Ok(Outcome::Done)
+            }
+            Action::SetProtocolFeeShare(protocol_fee_share_bps) => self
+                .set_protocol_fee_share(*protocol_fee_share_bps)
+                .map(|()| Outcome::Done),
+            Action::SetPairSwapFee(call) => self.set_pair_swap_fee(call).map(|()| Outcome::Done),
+        }
+    }
+
+    /// Applies `actions` in order, carrying on past those that fail.
+    pub fn replay<'a>(
+        &mut self,
+        actions: impl IntoIterator<Item = &'a Action<T, A>>,
+    ) -> Vec<Result<Outcome, Error>>
+    where
+        T: 'a,
+        A: 'a,
+    {
+        actions
+            .into_iter()
+            .map(|action| self.apply(action))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use casper_types::U512;
+
+    use super::*;
+
+    const FEETO: &str = "feeto";
+    const LP: &str = "lp";
+
+    fn amounts(amounts: &[u64]) -> Vec<U256> {
+        amounts.iter().map(|amount| U256::from(*amount)).collect()
+    }
+
+    fn add_liquidity(
+        token_a: &'static str,
+        token_b: &'static str,
+        amount_a: u64,
+        amount_b: u64,
+    ) -> Action<&'static str, &'static str> {
+        Action::AddLiquidity(AddLiquidity {
+            provider: LP,
+            token_a,
+            token_b,
+            amount_a_desired: amount_a.into(),
+            amount_b_desired: amount_b.into(),
+            amount_a_min: U256::zero(),
+            amount_b_min: U256::zero(),
+        })
+    }
+
+    fn swap_exact_in(amount_in: u64, path: &[&'static str]) -> Action<&'static str, &'static str> {
+        Action::SwapExactTokensForTokens(SwapExactTokensForTokens {
+            amount_in: amount_in.into(),
+            amount_out_min: U256::zero(),
+            path: path.to_vec(),
+        })
+    }
+
+    /// The router tests' fixture: a `b`/`a` pair holding 30_000 `a` and 50_000 `b`.
+    fn setup() -> Dex<&'static str, &'static str> {
+        let mut dex = Dex::new(Some(FEETO));
+        let outcomes = dex.replay(&[
+            Action::CreatePair(CreatePair {
+                token_a: "b",
+                token_b: "a",
+            }),
+            add_liquidity("a", "b", 30_000, 50_000),
+        ]);
+        assert_eq!(
+            outcomes[1],
+            Ok(Outcome::AddedLiquidity {
+                amount_a: 30_000.into(),
+                amount_b: 50_000.into(),
+                liquidity: 37_729.into(),
+            })
+        );
+        dex
+    }
+
+    #[test]
+    fn should_mint_initial_liquidity() {
+        let dex = setup();
+        let pair = dex.pair(&"a", &"b").unwrap();
+        assert_eq!(pair.reserves(), (30_000.into(), 50_000.into()));
+        assert_eq!(pair.total_supply(), U256::from(38_729));
+        assert_eq!(pair.balance_of(&LP), U256::from(37_729));
+        assert_eq!(pair.klast(), U512::from(1_500_000_000u64));
+        assert_eq!(
+            dex.get_reserves(&"b", &"a"),
+            Ok((50_000.into(), 30_000.into()))
+        );
+    }
+
+    #[test]
+    fn should_swap_as_quoted() {
+        let mut dex = setup();
+        assert_eq!(
+            dex.apply(&swap_exact_in(10_000, &["a", "b"])),
+            Ok(Outcome::Swapped(amounts(&[10_000, 12_481])))
+        );
+        let pair = dex.pair(&"a", &"b").unwrap();
+        assert_eq!(pair.reserves(), (40_000.into(), 37_519.into()));
+        assert_eq!(pair.volumes(), (10_000.into(), U256::zero()));
+        assert_eq!(pair.fees(), (20.into(), U256::zero()));
+
+        let swap_exact_out = Action::SwapTokensForExactTokens(SwapTokensForExactTokens {
+            amount_out: 10_000.into(),
+            amount_in_max: U256::MAX,
+            path: vec!["b", "a"],
+        });
+        let quoted = dex.get_amounts_in(10_000.into(), &["b", "a"]).unwrap();
+        assert_eq!(
+            dex.apply(&swap_exact_out),
+            Ok(Outcome::Swapped(quoted.clone()))
+        );
+        assert_eq!(
+            dex.pair(&"a", &"b").unwrap().reserves(),
+            (30_000.into(), U256::from(37_519) + quoted[0])
+        );
+    }
+
+    #[test]
+    fn should_revert_without_changes() {
+        let mut dex = setup();
+        let before = dex.clone();
+        let swap = Action::SwapExactTokensForTokens(SwapExactTokensForTokens {
+            amount_in: 10_000.into(),
+            amount_out_min: 12_482.into(),
+            path: vec!["a", "b"],
+        });
+        assert_eq!(dex.apply(&swap), Err(Error::InsufficientOutputAmount));
+        assert_eq!(
+            dex.apply(&swap_exact_in(10_000, &["a", "c"])),
+            Err(Error::PairNotFound)
+        );
+        assert_eq!(
+            dex.apply(&swap_exact_in(10_000, &["a"])),
+            Err(Error::InvalidPath)
+        );
+        // The second crossing of the pair finds the output of the first one never left it.
+        assert_eq!(
+            dex.apply(&swap_exact_in(10_000, &["a", "b", "a"])),
+            Err(Error::InsufficientInputAmount)
+        );
+        assert_eq!(dex, before);
+    }
+
+    #[test]
+    fn should_mint_protocol_fee_on_liquidity_events() {
+        let mut dex = setup();
+        dex.apply(&swap_exact_in(10_000, &["a", "b"])).unwrap();
+        // quote(30_000, 40_000, 37_519) caps b at 28_139, minted against a supply grown by the fee.
+        assert_eq!(
+            dex.apply(&add_liquidity("a", "b", 30_000, 30_000)),
+            Ok(Outcome::AddedLiquidity {
+                amount_a: 30_000.into(),
+                amount_b: 28_139.into(),
+                liquidity: 29_047.into(),
+            })
+        );
+        let pair = dex.pair(&"a", &"b").unwrap();
+        assert_eq!(pair.balance_of(&FEETO), U256::from(2));
+        assert_eq!(pair.protocol_liquidity(), U256::from(2));
+        assert_eq!(pair.total_supply(), U256::from(38_729 + 2 + 29_047));
+        assert_eq!(pair.klast(), math::mul(70_000.into(), 65_658.into()));
+
+        dex.apply(&Action::SetFeeto(None)).unwrap();
+        let remove = Action::RemoveLiquidity(RemoveLiquidity {
+            provider: LP,
+            token_a: "a",
+            token_b: "b",
+            liquidity: 29_047.into(),
+            amount0_min: U256::zero(),
+            amount1_min: U256::zero(),
+        });
+        assert!(dex.apply(&remove).is_ok());
+        assert_eq!(dex.pair(&"a", &"b").unwrap().klast(), U512::zero());
+        assert_eq!(dex.apply(&remove), Err(Error::InsufficientBalance));
+    }
+
+    #[test]
+    fn should_validate_configuration() {
+        let mut dex = setup();
+        assert_eq!(
+            dex.apply(&Action::CreatePair(CreatePair {
+                token_a: "a",
+                token_b: "a"
+            })),
+            Err(Error::IdenticalAddresses)
+        );
+        assert_eq!(
+            dex.apply(&Action::CreatePair(CreatePair {
+                token_a: "a",
+                token_b: "b"
+            })),
+            Err(Error::PairExists)
+        );
+        assert_eq!(
+            dex.apply(&Action::SetProtocolFeeShare(5_001)),
+            Err(Error::InvalidProtocolFeeShare)
+        );
+        let set_swap_fee = |swap_fee_bps| {
+            Action::SetPairSwapFee(SetPairSwapFee {
+                token_a: "b",
+                token_b: "a",
+                swap_fee_bps,
+            })
+        };
+        assert_eq!(dex.apply(&set_swap_fee(101)), Err(Error::InvalidSwapFee));
+        assert_eq!(dex.apply(&set_swap_fee(0)), Ok(Outcome::Done));
+        assert_eq!(
+            dex.get_amounts_out(10_000.into(), &["a", "b"]),
+            Ok(amounts(&[10_000, 12_500]))
+        );
+    }
+}
@@ -0,0 +1,46 @@
+use std::fmt;
+
+use swappery_math::MathError;
+
+/// Why an operation reverts, named after the router or pair error the contracts revert with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Error {
+    IdenticalAddresses,
+    PairExists,
+    PairNotFound,
+    InvalidPath,
+    InvalidSwapFee,
+    InvalidProtocolFeeShare,
+    InsufficientAAmount,
+    InsufficientBAmount,
+    InsufficientInputAmount,
+    InsufficientOutputAmount,
+    InsufficientLiquidity,
+    InsufficientLiquidityMinted,
+    InsufficientLiquidityBurned,
+    /// The provider holds fewer LP tokens than it tries to remove.
+    InsufficientBalance,
+    K,
+    Overflow,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self, f)
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<MathError> for Error {
+    fn from(error: MathError) -> Self {
+        match error {
+            MathError::Overflow => Error::Overflow,
+            MathError::InsufficientAmount => Error::InsufficientAAmount,
+            MathError::InsufficientInputAmount => Error::InsufficientInputAmount,
+            MathError::InsufficientOutputAmount => Error::InsufficientOutputAmount,
+            MathError::InsufficientLiquidity => Error::InsufficientLiquidity,
+            MathError::InvalidFee => Error::InvalidSwapFee,
+        }
+    }
+}
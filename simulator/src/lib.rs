@@ -0,0 +1,22 @@
+//! A deterministic model of the Swappery router and its pairs, for backtesting.
+//!
+//! A [`Dex`] holds pairs the way the contracts do: reserves and balances, LP tokens, `klast` and
+//! the protocol fee it mints, and swaps along multi-hop paths settled against the pair's K check.
+//! All amounts come out of `swappery-math`, the code the contracts run, so a replayed script of
+//! [`Action`]s lands on the very reserves and supplies the chain would. [`report`] turns the
+//! result into LP returns, fees and impermanent loss.
+
+pub mod action;
+mod dex;
+mod error;
+mod pair;
+pub mod report;
+
+pub use action::{Action, Outcome};
+pub use dex::Dex;
+pub use error::Error;
+pub use pair::{Pair, Position};
+pub use swappery_math::{
+    DEFAULT_PROTOCOL_FEE_SHARE_BPS, DEFAULT_SWAP_FEE_BPS, MAX_PROTOCOL_FEE_SHARE_BPS,
+    MAX_SWAP_FEE_BPS,
+};
@@ -0,0 +1,297 @@
+//! A pair's reserves, token balances and LP tokens, updated the way the pair contract does.
+
+use std::collections::BTreeMap;
+
+use casper_types::{U256, U512};
+use swappery_math as math;
+
+use crate::Error;
+
+/// Where the pair's protocol fee goes, as configured on the router.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct ProtocolFee<'a, A> {
+    /// `None` turns the protocol fee off.
+    pub feeto: Option<&'a A>,
+    pub share_bps: u32,
+}
+
+/// What a provider put into and took out of a pair, in the pair's token order.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Position {
+    pub deposited0: U256,
+    pub deposited1: U256,
+    pub withdrawn0: U256,
+    pub withdrawn1: U256,
+}
+
+/// A pair of `token0` and `token1`, whose LP tokens are held by `A`s.
+///
+/// Balances are the tokens the pair holds and reserves what it last synced them to; they only
+/// differ in the middle of an operation, between the transfers into the pair and the pair
+/// settling them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pair<A> {
+    balance0: U256,
+    balance1: U256,
+    reserve0: U256,
+    reserve1: U256,
+    total_supply: U256,
+    klast: U512,
+    swap_fee_bps: u32,
+    lp_balances: BTreeMap<A, U256>,
+    positions: BTreeMap<A, Position>,
+    volume0: U256,
+    volume1: U256,
+    fees0: U256,
+    fees1: U256,
+    protocol_liquidity: U256,
+}
+
+impl<A: Ord + Clone> Pair<A> {
+    pub(crate) fn new(swap_fee_bps: u32) -> Self {
+        Pair {
+            balance0: U256::zero(),
+            balance1: U256::zero(),
+            reserve0: U256::zero(),
+            reserve1: U256::zero(),
+            total_supply: U256::zero(),
+            klast: U512::zero(),
+            swap_fee_bps,
+            lp_balances: BTreeMap::new(),
+            positions: BTreeMap::new(),
+            volume0: U256::zero(),
+            volume1: U256::zero(),
+            fees0: U256::zero(),
+            fees1: U256::zero(),
+            protocol_liquidity: U256::zero(),
+        }
+    }
+
+    pub fn reserves(&self) -> (U256, U256) {
+        (self.reserve0, self.reserve1)
+    }
+
+    /// Returns the LP token supply, including the [`MINIMUM_LIQUIDITY`](math::MINIMUM_LIQUIDITY)
+    /// locked by the first mint.
+    pub fn total_supply(&self) -> U256 {
+        self.total_supply
+    }
+
+    /// Returns `reserve0 * reserve1` as of the last liquidity event with the protocol fee on.
+    pub fn klast(&self) -> U512 {
+        self.klast
+    }
+
+    pub fn swap_fee_bps(&self) -> u32 {
+        self.swap_fee_bps
+    }
+
+    pub fn balance_of(&self, owner: &A) -> U256 {
+        self.lp_balances.get(owner).copied().unwrap_or_default()
+    }
+
+    pub fn position(&self, provider: &A) -> Option<&Position> {
+        self.positions.get(provider)
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item = (&A, &Position)> {
+        self.positions.iter()
+    }
+
+    /// Returns the amounts of each token swapped into the pair.
+    pub fn volumes(&self) -> (U256, U256) {
+        (self.volume0, self.volume1)
+    }
+
+    /// Returns the swap fees charged on [`volumes`](Self::volumes), rounded down.
+    pub fn fees(&self) -> (U256, U256) {
+        (self.fees0, self.fees1)
+    }
+
+    /// Returns the LP tokens minted to the protocol fee recipient.
+    pub fn protocol_liquidity(&self) -> U256 {
+        self.protocol_liquidity
+    }
+
+    pub(crate) fn set_swap_fee_bps(&mut self, swap_fee_bps: u32) {
+        self.swap_fee_bps = swap_fee_bps;
+    }
+
+    /// Transfers tokens into the pair without settling them.
+    pub(crate) fn deposit(&mut self, amount0: U256, amount1: U256) -> Result<(), Error> {
+        self.balance0 = self.balance0.checked_add(amount0).ok_or(Error::Overflow)?;
+        self.balance1 = self.balance1.checked_add(amount1).ok_or(Error::Overflow)?;
+        Ok(())
+    }
+
+    fn credit(&mut self, owner: &A, liquidity: U256) -> Result<(), Error> {
+        self.total_supply = self
+            .total_supply
+            .checked_add(liquidity)
+            .ok_or(Error::Overflow)?;
+        let balance = self.lp_balances.entry(owner.clone()).or_default();
+        *balance = balance.checked_add(liquidity).ok_or(Error::Overflow)?;
+        Ok(())
+    }
+
+    fn sync(&mut self) {
+        self.reserve0 = self.balance0;
+        self.reserve1 = self.balance1;
+    }
+
+    fn mint_fee(&mut self, fee: ProtocolFee<'_, A>) -> Result<bool, Error> {
+        let feeto = match fee.feeto {
+            Some(feeto) => feeto,
+            None => {
+                self.klast = U512::zero();
+                return Ok(false);
+            }
+        };
+        let liquidity = math::fee_liquidity(
+            self.total_supply,
+            self.reserve0,
+            self.reserve1,
+            self.klast,
+            fee.share_bps,
+        )?;
+        if !liquidity.is_zero() {
+            self.credit(feeto, liquidity)?;
+            self.protocol_liquidity = self
+                .protocol_liquidity
+                .checked_add(liquidity)
+                .ok_or(Error::Overflow)?;
+        }
+        Ok(true)
+    }
+
+    /// Mints LP tokens to `to` for the tokens deposited since the last sync.
+    pub(crate) fn mint(&mut self, to: &A, fee: ProtocolFee<'_, A>) -> Result<U256, Error> {
+        let amount0 = self
+            .balance0
+            .checked_sub(self.reserve0)
+            .ok_or(Error::InsufficientLiquidityMinted)?;
+        let amount1 = self
+            .balance1
+            .checked_sub(self.reserve1)
+            .ok_or(Error::InsufficientLiquidityMinted)?;
+
+        let fee_on = self.mint_fee(fee)?;
+        let total_supply = self.total_supply;
+        let liquidity =
+            math::mint_liquidity(amount0, amount1, self.reserve0, self.reserve1, total_supply)?;
+        if total_supply.is_zero() {
+            self.total_supply = U256::from(math::MINIMUM_LIQUIDITY);
+        }
+        if liquidity.is_zero() {
+            return Err(Error::InsufficientLiquidityMinted);
+        }
+        self.credit(to, liquidity)?;
+
+        self.sync();
+        if fee_on {
+            self.klast = math::mul(self.reserve0, self.reserve1);
+        }
+        let position = self.positions.entry(to.clone()).or_default();
+        position.deposited0 = position
+            .deposited0
+            .checked_add(amount0)
+            .ok_or(Error::Overflow)?;
+        position.deposited1 = position
+            .deposited1
+            .checked_add(amount1)
+            .ok_or(Error::Overflow)?;
+        Ok(liquidity)
+    }
+
+    /// Burns `liquidity` of `from`'s LP tokens for their share of the balances.
+    pub(crate) fn burn(
+        &mut self,
+        from: &A,
+        liquidity: U256,
+        fee: ProtocolFee<'_, A>,
+    ) -> Result<(U256, U256), Error> {
+        if self.balance_of(from) < liquidity {
+            return Err(Error::InsufficientBalance);
+        }
+
+        let fee_on = self.mint_fee(fee)?;
+        let (amount0, amount1) =
+            math::burn_amounts(liquidity, self.balance0, self.balance1, self.total_supply)?;
+        if amount0.is_zero() || amount1.is_zero() {
+            return Err(Error::InsufficientLiquidityBurned);
+        }
+        if let Some(balance) = self.lp_balances.get_mut(from) {
+            *balance -= liquidity;
+        }
+        self.total_supply -= liquidity;
+        self.balance0 -= amount0;
+        self.balance1 -= amount1;
+
+        self.sync();
+        if fee_on {
+            self.klast = math::mul(self.reserve0, self.reserve1);
+        }
+        let position = self.positions.entry(from.clone()).or_default();
+        position.withdrawn0 = position
+            .withdrawn0
+            .checked_add(amount0)
+            .ok_or(Error::Overflow)?;
+        position.withdrawn1 = position
+            .withdrawn1
+            .checked_add(amount1)
+            .ok_or(Error::Overflow)?;
+        Ok((amount0, amount1))
+    }
+
+    /// Sends the outputs of a swap out of the pair, as the first half of the pair's `swap`.
+    pub(crate) fn swap_out(&mut self, amount0_out: U256, amount1_out: U256) -> Result<(), Error> {
+        if amount0_out.is_zero() && amount1_out.is_zero() {
+            return Err(Error::InsufficientOutputAmount);
+        }
+        if amount0_out >= self.reserve0 || amount1_out >= self.reserve1 {
+            return Err(Error::InsufficientLiquidity);
+        }
+        self.balance0 -= amount0_out;
+        self.balance1 -= amount1_out;
+        Ok(())
+    }
+
+    /// Settles a swap whose outputs left through [`swap_out`](Self::swap_out), checking that
+    /// what came in since keeps K.
+    pub(crate) fn settle_swap(
+        &mut self,
+        amount0_out: U256,
+        amount1_out: U256,
+    ) -> Result<(), Error> {
+        let amount0_in = math::amount_in(self.balance0, self.reserve0, amount0_out)?;
+        let amount1_in = math::amount_in(self.balance1, self.reserve1, amount1_out)?;
+        if amount0_in.is_zero() && amount1_in.is_zero() {
+            return Err(Error::InsufficientInputAmount);
+        }
+        let k_holds = math::k_holds(
+            self.balance0,
+            self.balance1,
+            amount0_in,
+            amount1_in,
+            self.reserve0,
+            self.reserve1,
+            self.swap_fee_bps,
+        )?;
+        if !k_holds {
+            return Err(Error::K);
+        }
+
+        self.sync();
+        let swap_fee_bps = U256::from(self.swap_fee_bps);
+        let fee_denominator = U256::from(math::FEE_DENOMINATOR);
+        self.volume0 = self.volume0.saturating_add(amount0_in);
+        self.volume1 = self.volume1.saturating_add(amount1_in);
+        self.fees0 =
+            self.fees0
+                .saturating_add(math::mul_div(amount0_in, swap_fee_bps, fee_denominator)?);
+        self.fees1 =
+            self.fees1
+                .saturating_add(math::mul_div(amount1_in, swap_fee_bps, fee_denominator)?);
+        Ok(())
+    }
+}
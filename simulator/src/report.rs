@@ -0,0 +1,266 @@
+//! Returns, fees and impermanent loss of the simulated pairs and their providers.
+//!
+//! Values are `f64`s in units of a pair's token1 at the pair's current price, which is all a
+//! backtest compares them with; amounts stay exact.
+
+use casper_types::U256;
+use swappery_math as math;
+
+use crate::dex::Dex;
+use crate::pair::{Pair, Position};
+
+/// Returns `value` as an `f64`, rounded to its 53 most significant bits.
+pub fn to_f64(value: U256) -> f64 {
+    value.0.iter().rev().fold(0.0, |acc, limb| {
+        acc * 18_446_744_073_709_551_616.0 + *limb as f64
+    })
+}
+
+/// Returns the loss of providing liquidity against holding the tokens when the price moves by
+/// `price_ratio`, fees aside: `2 * sqrt(r) / (1 + r) - 1`.
+pub fn impermanent_loss(price_ratio: f64) -> f64 {
+    2.0 * price_ratio.sqrt() / (1.0 + price_ratio) - 1.0
+}
+
+/// Returns the price of token0 in token1, or `None` while the pair is empty.
+fn price<A>(pair: &Pair<A>) -> Option<f64> {
+    let (reserve0, reserve1) = pair.reserves();
+    if reserve0.is_zero() || reserve1.is_zero() {
+        return None;
+    }
+    Some(to_f64(reserve1) / to_f64(reserve0))
+}
+
+/// A pair's state and trading activity.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PairReport<T> {
+    pub token0: T,
+    pub token1: T,
+    pub reserve0: U256,
+    pub reserve1: U256,
+    pub total_supply: U256,
+    /// Price of token0 in token1, `None` while the pair is empty.
+    pub price: Option<f64>,
+    pub volume0: U256,
+    pub volume1: U256,
+    pub fees0: U256,
+    pub fees1: U256,
+    /// LP tokens minted to the protocol fee recipient.
+    pub protocol_liquidity: U256,
+}
+
+/// How a provider's liquidity in a pair fared against holding what it deposited.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PositionReport<T, A> {
+    pub provider: A,
+    pub token0: T,
+    pub token1: T,
+    pub liquidity: U256,
+    /// What burning `liquidity` pays out now, before any protocol fee the burn would mint.
+    pub claim0: U256,
+    pub claim1: U256,
+    pub position: Position,
+    /// Value of the deposits, had they been held.
+    pub hold_value: f64,
+    /// Value of the claim and of the withdrawals.
+    pub lp_value: f64,
+    /// `lp_value / hold_value - 1`: the fees earned net of impermanent loss, `None` without
+    /// deposits.
+    pub return_vs_hold: Option<f64>,
+    /// [`impermanent_loss`] of the move from the average price of the deposits to the current
+    /// one, `None` unless both tokens were deposited.
+    pub impermanent_loss: Option<f64>,
+}
+
+impl<T: Ord + Clone, A: Ord + Clone> Dex<T, A> {
+    pub fn pair_reports(&self) -> Vec<PairReport<T>> {
+        self.pairs()
+            .map(|(token0, token1, pair)| {
+                let (reserve0, reserve1) = pair.reserves();
+                let (volume0, volume1) = pair.volumes();
+                let (fees0, fees1) = pair.fees();
+                PairReport {
+                    token0: token0.clone(),
+                    token1: token1.clone(),
+                    reserve0,
+                    reserve1,
+                    total_supply: pair.total_supply(),
+                    price: price(pair),
+                    volume0,
+                    volume1,
+                    fees0,
+                    fees1,
+                    protocol_liquidity: pair.protocol_liquidity(),
+                }
+            })
+            .collect()
+    }
+
+    /// Reports every position in a non-empty pair.
+    pub fn position_reports(&self) -> Vec<PositionReport<T, A>> {
+        let mut reports = Vec::new();
+        for (token0, token1, pair) in self.pairs() {
+            let price = match price(pair) {
+                Some(price) => price,
+                None => continue,
+            };
+            let (reserve0, reserve1) = pair.reserves();
+            for (provider, position) in pair.positions() {
+                let liquidity = pair.balance_of(provider);
+                let (claim0, claim1) =
+                    math::burn_amounts(liquidity, reserve0, reserve1, pair.total_supply())
+                        .unwrap_or_default();
+
+                let value =
+                    |amount0: U256, amount1: U256| to_f64(amount0) * price + to_f64(amount1);
+                let hold_value = value(position.deposited0, position.deposited1);
+                let lp_value =
+                    value(claim0, claim1) + value(position.withdrawn0, position.withdrawn1);
+                // The protocol fee recipient, for one, can withdraw without having deposited.
+                let return_vs_hold = (hold_value > 0.0).then(|| lp_value / hold_value - 1.0);
+                let entry_price = if position.deposited0.is_zero() || position.deposited1.is_zero()
+                {
+                    None
+                } else {
+                    Some(to_f64(position.deposited1) / to_f64(position.deposited0))
+                };
+
+                reports.push(PositionReport {
+                    provider: provider.clone(),
+                    token0: token0.clone(),
+                    token1: token1.clone(),
+                    liquidity,
+                    claim0,
+                    claim1,
+                    position: position.clone(),
+                    hold_value,
+                    lp_value,
+                    return_vs_hold,
+                    impermanent_loss: entry_price
+                        .map(|entry_price| impermanent_loss(price / entry_price)),
+                });
+            }
+        }
+        reports
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::action::{
+        Action, AddLiquidity, CreatePair, RemoveLiquidity, SwapExactTokensForTokens,
+    };
+
+    #[test]
+    fn should_convert_to_f64() {
+        assert_eq!(to_f64(U256::from(12_481)), 12_481.0);
+        assert_eq!(
+            to_f64(U256::from(u64::MAX) + 1),
+            18_446_744_073_709_551_616.0
+        );
+    }
+
+    #[test]
+    fn should_compute_impermanent_loss() {
+        assert_eq!(impermanent_loss(1.0), 0.0);
+        assert!((impermanent_loss(4.0) + 0.2).abs() < 1e-12);
+        assert!((impermanent_loss(0.25) + 0.2).abs() < 1e-12);
+    }
+
+    #[test]
+    fn should_report_fees_and_losses() {
+        let mut dex: Dex<&str, &str> = Dex::new(None);
+        let outcomes = dex.replay(&[
+            Action::CreatePair(CreatePair {
+                token_a: "a",
+                token_b: "b",
+            }),
+            Action::AddLiquidity(AddLiquidity {
+                provider: "lp",
+                token_a: "a",
+                token_b: "b",
+                amount_a_desired: 1_000_000_000.into(),
+                amount_b_desired: 1_000_000_000.into(),
+                amount_a_min: U256::zero(),
+                amount_b_min: U256::zero(),
+            }),
+            Action::SwapExactTokensForTokens(SwapExactTokensForTokens {
+                amount_in: 1_000_000_000.into(),
+                amount_out_min: U256::zero(),
+                path: vec!["a", "b"],
+            }),
+        ]);
+        assert!(outcomes.iter().all(Result::is_ok));
+
+        let pairs = dex.pair_reports();
+        assert_eq!(pairs.len(), 1);
+        assert_eq!(
+            (pairs[0].volume0, pairs[0].fees0),
+            (1_000_000_000.into(), 2_000_000.into())
+        );
+
+        let positions = dex.position_reports();
+        assert_eq!(positions.len(), 1);
+        let report = &positions[0];
+        assert_eq!(report.provider, "lp");
+        assert_eq!(report.liquidity, U256::from(999_999_000));
+        // The price of a fell about fourfold: the position lost about a fifth against holding,
+        // a little less thanks to the fee.
+        let impermanent_loss = report.impermanent_loss.unwrap();
+        let return_vs_hold = report.return_vs_hold.unwrap();
+        assert!(impermanent_loss < -0.19);
+        assert!(return_vs_hold < 0.0 && return_vs_hold > impermanent_loss);
+    }
+
+    #[test]
+    fn should_not_compare_withdrawals_without_deposits() {
+        let mut dex: Dex<&str, &str> = Dex::new(Some("feeto"));
+        let add_liquidity = Action::AddLiquidity(AddLiquidity {
+            provider: "lp",
+            token_a: "a",
+            token_b: "b",
+            amount_a_desired: 1_000_000_000.into(),
+            amount_b_desired: 1_000_000_000.into(),
+            amount_a_min: U256::zero(),
+            amount_b_min: U256::zero(),
+        });
+        let outcomes = dex.replay(&[
+            Action::CreatePair(CreatePair {
+                token_a: "a",
+                token_b: "b",
+            }),
+            add_liquidity.clone(),
+            Action::SwapExactTokensForTokens(SwapExactTokensForTokens {
+                amount_in: 1_000_000_000.into(),
+                amount_out_min: U256::zero(),
+                path: vec!["a", "b"],
+            }),
+            add_liquidity,
+        ]);
+        assert!(outcomes.iter().all(Result::is_ok));
+
+        let protocol_liquidity = dex.pair(&"a", &"b").unwrap().balance_of(&"feeto");
+        assert!(!protocol_liquidity.is_zero());
+        let outcome = dex.apply(&Action::RemoveLiquidity(RemoveLiquidity {
+            provider: "feeto",
+            token_a: "a",
+            token_b: "b",
+            liquidity: protocol_liquidity,
+            amount0_min: U256::zero(),
+            amount1_min: U256::zero(),
+        }));
+        assert!(outcome.is_ok());
+
+        let positions = dex.position_reports();
+        let report = positions
+            .iter()
+            .find(|report| report.provider == "feeto")
+            .unwrap();
+        assert!(report.lp_value > 0.0);
+        assert_eq!(
+            (report.return_vs_hold, report.impermanent_loss),
+            (None, None)
+        );
+    }
+}
@@ -0,0 +1,281 @@
+//! Fee-on-transfer token used by the test suite.
+//!
+//! An ERC-20 with the usual entry points, except that `transfer` and `transfer_from` burn 1% of
+//! every amount moved, so the recipient receives less than the sender is charged.
+#![no_std]
+#![no_main]
+#![feature(default_alloc_error_handler)]
+
+extern crate alloc;
+
+use alloc::{format, string::String, vec, vec::Vec};
+
+use casper_contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use casper_types::{
+    bytesrepr::{FromBytes, ToBytes},
+    contracts::NamedKeys,
+    system::CallStackElement,
+    ApiError, CLType, CLTyped, CLValue, EntryPoint, EntryPointAccess, EntryPointType, EntryPoints,
+    Key, Parameter, URef, U256,
+};
+
+const NAME_KEY_NAME: &str = "name";
+const SYMBOL_KEY_NAME: &str = "symbol";
+const DECIMALS_KEY_NAME: &str = "decimals";
+const TOTAL_SUPPLY_KEY_NAME: &str = "total_supply";
+const BALANCES_KEY_NAME: &str = "balances";
+const ALLOWANCES_KEY_NAME: &str = "allowances";
+const NAME_RUNTIME_ARG_NAME: &str = "name";
+const SYMBOL_RUNTIME_ARG_NAME: &str = "symbol";
+const DECIMALS_RUNTIME_ARG_NAME: &str = "decimals";
+const TOTAL_SUPPLY_RUNTIME_ARG_NAME: &str = "total_supply";
+const ADDRESS_RUNTIME_ARG_NAME: &str = "address";
+const OWNER_RUNTIME_ARG_NAME: &str = "owner";
+const SPENDER_RUNTIME_ARG_NAME: &str = "spender";
+const RECIPIENT_RUNTIME_ARG_NAME: &str = "recipient";
+const AMOUNT_RUNTIME_ARG_NAME: &str = "amount";
+const CONTRACT_KEY_NAME: &str = "fee_on_transfer_token";
+const CONTRACT_HASH_KEY_NAME: &str = "fee_on_transfer_token_contract_hash";
+/// Share of every transfer that is burnt, in percent.
+const TRANSFER_FEE_PERCENT: u64 = 1;
+
+const ERROR_INSUFFICIENT_BALANCE: u16 = u16::MAX - 1;
+const ERROR_INSUFFICIENT_ALLOWANCE: u16 = u16::MAX - 2;
+
+fn get_uref(name: &str) -> URef {
+    runtime::get_key(name)
+        .and_then(Key::into_uref)
+        .ok_or(ApiError::MissingKey)
+        .unwrap_or_revert()
+}
+
+fn immediate_caller() -> Key {
+    let call_stack = runtime::get_call_stack();
+    match call_stack.into_iter().rev().nth(1).unwrap_or_revert() {
+        CallStackElement::Session { account_hash } => Key::Account(account_hash),
+        CallStackElement::StoredSession { account_hash, .. } => Key::Account(account_hash),
+        CallStackElement::StoredContract {
+            contract_package_hash,
+            ..
+        } => Key::Hash(contract_package_hash.value()),
+    }
+}
+
+/// Dictionary item keys are limited in length, so items are keyed by the hex of a hash.
+fn dictionary_item_key<T: ToBytes>(value: &T) -> String {
+    let bytes: Vec<u8> = value.to_bytes().unwrap_or_revert();
+    runtime::blake2b(bytes)
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+fn read_balance(owner: Key) -> U256 {
+    storage::dictionary_get(get_uref(BALANCES_KEY_NAME), &dictionary_item_key(&owner))
+        .unwrap_or_revert()
+        .unwrap_or_default()
+}
+
+fn write_balance(owner: Key, amount: U256) {
+    storage::dictionary_put(get_uref(BALANCES_KEY_NAME), &dictionary_item_key(&owner), amount);
+}
+
+fn read_allowance(owner: Key, spender: Key) -> U256 {
+    storage::dictionary_get(
+        get_uref(ALLOWANCES_KEY_NAME),
+        &dictionary_item_key(&(owner, spender)),
+    )
+    .unwrap_or_revert()
+    .unwrap_or_default()
+}
+
+fn write_allowance(owner: Key, spender: Key, amount: U256) {
+    storage::dictionary_put(
+        get_uref(ALLOWANCES_KEY_NAME),
+        &dictionary_item_key(&(owner, spender)),
+        amount,
+    );
+}
+
+/// Moves `amount` from `sender`, crediting `recipient` with it less the burnt fee.
+fn transfer_balance(sender: Key, recipient: Key, amount: U256) {
+    let sender_balance = read_balance(sender);
+    if sender_balance < amount {
+        runtime::revert(ApiError::User(ERROR_INSUFFICIENT_BALANCE));
+    }
+    let fee = amount * TRANSFER_FEE_PERCENT / 100;
+    write_balance(sender, sender_balance - amount);
+    write_balance(recipient, read_balance(recipient) + amount - fee);
+
+    let total_supply_uref = get_uref(TOTAL_SUPPLY_KEY_NAME);
+    let total_supply: U256 = storage::read(total_supply_uref)
+        .unwrap_or_revert()
+        .unwrap_or_revert();
+    storage::write(total_supply_uref, total_supply - fee);
+}
+
+fn ret_named_value<T: CLTyped + ToBytes + FromBytes>(name: &str) {
+    let value: T = storage::read(get_uref(name))
+        .unwrap_or_revert()
+        .unwrap_or_revert();
+    runtime::ret(CLValue::from_t(value).unwrap_or_revert());
+}
+
+#[no_mangle]
+pub extern "C" fn name() {
+    ret_named_value::<String>(NAME_KEY_NAME);
+}
+
+#[no_mangle]
+pub extern "C" fn symbol() {
+    ret_named_value::<String>(SYMBOL_KEY_NAME);
+}
+
+#[no_mangle]
+pub extern "C" fn decimals() {
+    ret_named_value::<u8>(DECIMALS_KEY_NAME);
+}
+
+#[no_mangle]
+pub extern "C" fn total_supply() {
+    ret_named_value::<U256>(TOTAL_SUPPLY_KEY_NAME);
+}
+
+#[no_mangle]
+pub extern "C" fn balance_of() {
+    let address: Key = runtime::get_named_arg(ADDRESS_RUNTIME_ARG_NAME);
+    runtime::ret(CLValue::from_t(read_balance(address)).unwrap_or_revert());
+}
+
+#[no_mangle]
+pub extern "C" fn allowance() {
+    let owner: Key = runtime::get_named_arg(OWNER_RUNTIME_ARG_NAME);
+    let spender: Key = runtime::get_named_arg(SPENDER_RUNTIME_ARG_NAME);
+    runtime::ret(CLValue::from_t(read_allowance(owner, spender)).unwrap_or_revert());
+}
+
+#[no_mangle]
+pub extern "C" fn approve() {
+    let spender: Key = runtime::get_named_arg(SPENDER_RUNTIME_ARG_NAME);
+    let amount: U256 = runtime::get_named_arg(AMOUNT_RUNTIME_ARG_NAME);
+    write_allowance(immediate_caller(), spender, amount);
+}
+
+#[no_mangle]
+pub extern "C" fn transfer() {
+    let recipient: Key = runtime::get_named_arg(RECIPIENT_RUNTIME_ARG_NAME);
+    let amount: U256 = runtime::get_named_arg(AMOUNT_RUNTIME_ARG_NAME);
+    transfer_balance(immediate_caller(), recipient, amount);
+}
+
+#[no_mangle]
+pub extern "C" fn transfer_from() {
+    let owner: Key = runtime::get_named_arg(OWNER_RUNTIME_ARG_NAME);
+    let recipient: Key = runtime::get_named_arg(RECIPIENT_RUNTIME_ARG_NAME);
+    let amount: U256 = runtime::get_named_arg(AMOUNT_RUNTIME_ARG_NAME);
+    let spender = immediate_caller();
+    let allowance = read_allowance(owner, spender);
+    if allowance < amount {
+        runtime::revert(ApiError::User(ERROR_INSUFFICIENT_ALLOWANCE));
+    }
+    write_allowance(owner, spender, allowance - amount);
+    transfer_balance(owner, recipient, amount);
+}
+
+fn entry_point(name: &str, params: Vec<Parameter>, ret: CLType) -> EntryPoint {
+    EntryPoint::new(
+        String::from(name),
+        params,
+        ret,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    )
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let name: String = runtime::get_named_arg(NAME_RUNTIME_ARG_NAME);
+    let symbol: String = runtime::get_named_arg(SYMBOL_RUNTIME_ARG_NAME);
+    let decimals: u8 = runtime::get_named_arg(DECIMALS_RUNTIME_ARG_NAME);
+    let total_supply: U256 = runtime::get_named_arg(TOTAL_SUPPLY_RUNTIME_ARG_NAME);
+
+    let mut entry_points = EntryPoints::new();
+    entry_points.add_entry_point(entry_point("name", vec![], String::cl_type()));
+    entry_points.add_entry_point(entry_point("symbol", vec![], String::cl_type()));
+    entry_points.add_entry_point(entry_point("decimals", vec![], u8::cl_type()));
+    entry_points.add_entry_point(entry_point("total_supply", vec![], U256::cl_type()));
+    entry_points.add_entry_point(entry_point(
+        "balance_of",
+        vec![Parameter::new(ADDRESS_RUNTIME_ARG_NAME, Key::cl_type())],
+        U256::cl_type(),
+    ));
+    entry_points.add_entry_point(entry_point(
+        "allowance",
+        vec![
+            Parameter::new(OWNER_RUNTIME_ARG_NAME, Key::cl_type()),
+            Parameter::new(SPENDER_RUNTIME_ARG_NAME, Key::cl_type()),
+        ],
+        U256::cl_type(),
+    ));
+    entry_points.add_entry_point(entry_point(
+        "approve",
+        vec![
+            Parameter::new(SPENDER_RUNTIME_ARG_NAME, Key::cl_type()),
+            Parameter::new(AMOUNT_RUNTIME_ARG_NAME, U256::cl_type()),
+        ],
+        CLType::Unit,
+    ));
+    entry_points.add_entry_point(entry_point(
+        "transfer",
+        vec![
+            Parameter::new(RECIPIENT_RUNTIME_ARG_NAME, Key::cl_type()),
+            Parameter::new(AMOUNT_RUNTIME_ARG_NAME, U256::cl_type()),
+        ],
+        CLType::Unit,
+    ));
+    entry_points.add_entry_point(entry_point(
+        "transfer_from",
+        vec![
+            Parameter::new(OWNER_RUNTIME_ARG_NAME, Key::cl_type()),
+            Parameter::new(RECIPIENT_RUNTIME_ARG_NAME, Key::cl_type()),
+            Parameter::new(AMOUNT_RUNTIME_ARG_NAME, U256::cl_type()),
+        ],
+        CLType::Unit,
+    ));
+
+    let mut named_keys = NamedKeys::new();
+    named_keys.insert(String::from(NAME_KEY_NAME), storage::new_uref(name).into());
+    named_keys.insert(String::from(SYMBOL_KEY_NAME), storage::new_uref(symbol).into());
+    named_keys.insert(String::from(DECIMALS_KEY_NAME), storage::new_uref(decimals).into());
+    named_keys.insert(
+        String::from(TOTAL_SUPPLY_KEY_NAME),
+        storage::new_uref(total_supply).into(),
+    );
+    let balances = storage::new_dictionary(BALANCES_KEY_NAME).unwrap_or_revert();
+    let allowances = storage::new_dictionary(ALLOWANCES_KEY_NAME).unwrap_or_revert();
+    storage::dictionary_put(
+        balances,
+        &dictionary_item_key(&Key::Account(runtime::get_caller())),
+        total_supply,
+    );
+    named_keys.insert(String::from(BALANCES_KEY_NAME), balances.into());
+    named_keys.insert(String::from(ALLOWANCES_KEY_NAME), allowances.into());
+    runtime::remove_key(BALANCES_KEY_NAME);
+    runtime::remove_key(ALLOWANCES_KEY_NAME);
+
+    let (contract_hash, _version) = storage::new_contract(
+        entry_points,
+        Some(named_keys),
+        Some(String::from(CONTRACT_KEY_NAME)),
+        None,
+    );
+    runtime::put_key(CONTRACT_HASH_KEY_NAME, Key::from(contract_hash));
+}
+
+#[panic_handler]
+fn my_panic(_info: &core::panic::PanicInfo) -> ! {
+    loop {}
+}
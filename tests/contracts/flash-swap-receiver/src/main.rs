@@ -0,0 +1,77 @@
+//! Flash swap receiver used by the test suite.
+//!
+//! On `swappery_call` it decodes `(token, pair, amount)` from `data` and pays `amount` of `token`
+//! back to `pair`, so tests can drive both a repaid and an underpaid flash swap.
+#![no_std]
+#![no_main]
+#![feature(default_alloc_error_handler)]
+
+extern crate alloc;
+
+use alloc::{string::String, vec};
+
+use casper_contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use casper_types::{
+    bytesrepr::{self, Bytes},
+    runtime_args, CLType, CLTyped, ContractHash, EntryPoint, EntryPointAccess, EntryPointType,
+    EntryPoints, Key, Parameter, RuntimeArgs, U256,
+};
+
+const SWAPPERY_CALL_ENTRY_POINT_NAME: &str = "swappery_call";
+const TRANSFER_ENTRY_POINT_NAME: &str = "transfer";
+const SENDER_RUNTIME_ARG_NAME: &str = "sender";
+const AMOUNT0_RUNTIME_ARG_NAME: &str = "amount0";
+const AMOUNT1_RUNTIME_ARG_NAME: &str = "amount1";
+const DATA_RUNTIME_ARG_NAME: &str = "data";
+const RECIPIENT_RUNTIME_ARG_NAME: &str = "recipient";
+const AMOUNT_RUNTIME_ARG_NAME: &str = "amount";
+const CONTRACT_KEY_NAME: &str = "flash_swap_receiver";
+const CONTRACT_HASH_KEY_NAME: &str = "flash_swap_receiver_contract_hash";
+
+#[no_mangle]
+pub extern "C" fn swappery_call() {
+    let data: Bytes = runtime::get_named_arg(DATA_RUNTIME_ARG_NAME);
+    let (token, pair, amount): (ContractHash, Key, U256) =
+        bytesrepr::deserialize(data.to_vec()).unwrap_or_revert();
+    runtime::call_contract::<()>(
+        token,
+        TRANSFER_ENTRY_POINT_NAME,
+        runtime_args! {
+            RECIPIENT_RUNTIME_ARG_NAME => pair,
+            AMOUNT_RUNTIME_ARG_NAME => amount
+        },
+    );
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let mut entry_points = EntryPoints::new();
+    entry_points.add_entry_point(EntryPoint::new(
+        String::from(SWAPPERY_CALL_ENTRY_POINT_NAME),
+        vec![
+            Parameter::new(SENDER_RUNTIME_ARG_NAME, Key::cl_type()),
+            Parameter::new(AMOUNT0_RUNTIME_ARG_NAME, U256::cl_type()),
+            Parameter::new(AMOUNT1_RUNTIME_ARG_NAME, U256::cl_type()),
+            Parameter::new(DATA_RUNTIME_ARG_NAME, Bytes::cl_type()),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    let (contract_hash, _version) = storage::new_contract(
+        entry_points,
+        None,
+        Some(String::from(CONTRACT_KEY_NAME)),
+        None,
+    );
+    runtime::put_key(CONTRACT_HASH_KEY_NAME, Key::from(contract_hash));
+}
+
+#[panic_handler]
+fn my_panic(_info: &core::panic::PanicInfo) -> ! {
+    loop {}
+}
@@ -0,0 +1,294 @@
+//! Router test call contract used by the test suite.
+//!
+//! Calls the router's read-only quote and registry entry points, and a pair's cumulative prices,
+//! and stores their return value under `result`, where tests can read it back.
+#![no_std]
+#![no_main]
+#![feature(default_alloc_error_handler)]
+
+extern crate alloc;
+
+use alloc::{boxed::Box, string::String, vec, vec::Vec};
+
+use casper_contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use casper_types::{
+    bytesrepr::ToBytes, runtime_args, CLType, CLTyped, ContractHash, ContractPackageHash,
+    EntryPoint, EntryPointAccess, EntryPointType, EntryPoints, Key, Parameter, RuntimeArgs, U256,
+};
+
+const CHECK_AMOUNTS_OUT_ENTRY_POINT_NAME: &str = "check_amounts_out";
+const CHECK_AMOUNTS_IN_ENTRY_POINT_NAME: &str = "check_amounts_in";
+const CHECK_QUOTE_ENTRY_POINT_NAME: &str = "check_quote";
+const CHECK_ALL_PAIRS_LENGTH_ENTRY_POINT_NAME: &str = "check_all_pairs_length";
+const CHECK_PAIR_AT_ENTRY_POINT_NAME: &str = "check_pair_at";
+const CHECK_PAIRS_FOR_TOKEN_ENTRY_POINT_NAME: &str = "check_pairs_for_token";
+const CHECK_BEST_AMOUNT_OUT_ENTRY_POINT_NAME: &str = "check_best_amount_out";
+const CHECK_CUMULATIVE_PRICES_ENTRY_POINT_NAME: &str = "check_cumulative_prices";
+const GET_AMOUNTS_OUT_ENTRY_POINT_NAME: &str = "get_amounts_out";
+const GET_AMOUNTS_IN_ENTRY_POINT_NAME: &str = "get_amounts_in";
+const QUOTE_ENTRY_POINT_NAME: &str = "quote";
+const ALL_PAIRS_LENGTH_ENTRY_POINT_NAME: &str = "all_pairs_length";
+const PAIR_AT_ENTRY_POINT_NAME: &str = "pair_at";
+const PAIRS_FOR_TOKEN_ENTRY_POINT_NAME: &str = "pairs_for_token";
+const GET_BEST_AMOUNT_OUT_ENTRY_POINT_NAME: &str = "get_best_amount_out";
+const GET_CUMULATIVE_PRICES_ENTRY_POINT_NAME: &str = "get_cumulative_prices";
+const ROUTER_RUNTIME_ARG_NAME: &str = "router";
+const PAIR_RUNTIME_ARG_NAME: &str = "pair";
+const AMOUNT_IN_RUNTIME_ARG_NAME: &str = "amount_in";
+const AMOUNT_OUT_RUNTIME_ARG_NAME: &str = "amount_out";
+const PATH_RUNTIME_ARG_NAME: &str = "path";
+const AMOUNT_A_RUNTIME_ARG_NAME: &str = "amount_a";
+const RESERVE_A_RUNTIME_ARG_NAME: &str = "reserve_a";
+const RESERVE_B_RUNTIME_ARG_NAME: &str = "reserve_b";
+const INDEX_RUNTIME_ARG_NAME: &str = "index";
+const TOKEN_RUNTIME_ARG_NAME: &str = "token";
+const OFFSET_RUNTIME_ARG_NAME: &str = "offset";
+const LIMIT_RUNTIME_ARG_NAME: &str = "limit";
+const TOKEN_IN_RUNTIME_ARG_NAME: &str = "token_in";
+const TOKEN_OUT_RUNTIME_ARG_NAME: &str = "token_out";
+const INTERMEDIATES_RUNTIME_ARG_NAME: &str = "intermediates";
+const RESULT_KEY_NAME: &str = "result";
+const CONTRACT_KEY_NAME: &str = "router_test_call";
+const CONTRACT_HASH_KEY_NAME: &str = "router_test_call_contract_hash";
+
+fn get_router() -> ContractPackageHash {
+    let router_key: Key = runtime::get_named_arg(ROUTER_RUNTIME_ARG_NAME);
+    router_key.into_hash().unwrap_or_revert().into()
+}
+
+fn store_result<T: CLTyped + ToBytes>(result: T) {
+    runtime::put_key(RESULT_KEY_NAME, storage::new_uref(result).into());
+}
+
+#[no_mangle]
+pub extern "C" fn check_amounts_out() {
+    let amount_in: U256 = runtime::get_named_arg(AMOUNT_IN_RUNTIME_ARG_NAME);
+    let path: Vec<Key> = runtime::get_named_arg(PATH_RUNTIME_ARG_NAME);
+    let amounts: Vec<U256> = runtime::call_versioned_contract(
+        get_router(),
+        None,
+        GET_AMOUNTS_OUT_ENTRY_POINT_NAME,
+        runtime_args! {
+            AMOUNT_IN_RUNTIME_ARG_NAME => amount_in,
+            PATH_RUNTIME_ARG_NAME => path,
+        },
+    );
+    store_result(amounts);
+}
+
+#[no_mangle]
+pub extern "C" fn check_amounts_in() {
+    let amount_out: U256 = runtime::get_named_arg(AMOUNT_OUT_RUNTIME_ARG_NAME);
+    let path: Vec<Key> = runtime::get_named_arg(PATH_RUNTIME_ARG_NAME);
+    let amounts: Vec<U256> = runtime::call_versioned_contract(
+        get_router(),
+        None,
+        GET_AMOUNTS_IN_ENTRY_POINT_NAME,
+        runtime_args! {
+            AMOUNT_OUT_RUNTIME_ARG_NAME => amount_out,
+            PATH_RUNTIME_ARG_NAME => path,
+        },
+    );
+    store_result(amounts);
+}
+
+#[no_mangle]
+pub extern "C" fn check_quote() {
+    let amount_a: U256 = runtime::get_named_arg(AMOUNT_A_RUNTIME_ARG_NAME);
+    let reserve_a: U256 = runtime::get_named_arg(RESERVE_A_RUNTIME_ARG_NAME);
+    let reserve_b: U256 = runtime::get_named_arg(RESERVE_B_RUNTIME_ARG_NAME);
+    let amount_b: U256 = runtime::call_versioned_contract(
+        get_router(),
+        None,
+        QUOTE_ENTRY_POINT_NAME,
+        runtime_args! {
+            AMOUNT_A_RUNTIME_ARG_NAME => amount_a,
+            RESERVE_A_RUNTIME_ARG_NAME => reserve_a,
+            RESERVE_B_RUNTIME_ARG_NAME => reserve_b,
+        },
+    );
+    store_result(amount_b);
+}
+
+#[no_mangle]
+pub extern "C" fn check_all_pairs_length() {
+    let length: u64 = runtime::call_versioned_contract(
+        get_router(),
+        None,
+        ALL_PAIRS_LENGTH_ENTRY_POINT_NAME,
+        runtime_args! {},
+    );
+    store_result(length);
+}
+
+#[no_mangle]
+pub extern "C" fn check_pair_at() {
+    let index: u64 = runtime::get_named_arg(INDEX_RUNTIME_ARG_NAME);
+    let pair: Key = runtime::call_versioned_contract(
+        get_router(),
+        None,
+        PAIR_AT_ENTRY_POINT_NAME,
+        runtime_args! {
+            INDEX_RUNTIME_ARG_NAME => index,
+        },
+    );
+    store_result(pair);
+}
+
+#[no_mangle]
+pub extern "C" fn check_pairs_for_token() {
+    let token: Key = runtime::get_named_arg(TOKEN_RUNTIME_ARG_NAME);
+    let offset: u64 = runtime::get_named_arg(OFFSET_RUNTIME_ARG_NAME);
+    let limit: u64 = runtime::get_named_arg(LIMIT_RUNTIME_ARG_NAME);
+    let pairs: Vec<Key> = runtime::call_versioned_contract(
+        get_router(),
+        None,
+        PAIRS_FOR_TOKEN_ENTRY_POINT_NAME,
+        runtime_args! {
+            TOKEN_RUNTIME_ARG_NAME => token,
+            OFFSET_RUNTIME_ARG_NAME => offset,
+            LIMIT_RUNTIME_ARG_NAME => limit,
+        },
+    );
+    store_result(pairs);
+}
+
+#[no_mangle]
+pub extern "C" fn check_best_amount_out() {
+    let token_in: Key = runtime::get_named_arg(TOKEN_IN_RUNTIME_ARG_NAME);
+    let token_out: Key = runtime::get_named_arg(TOKEN_OUT_RUNTIME_ARG_NAME);
+    let amount_in: U256 = runtime::get_named_arg(AMOUNT_IN_RUNTIME_ARG_NAME);
+    let intermediates: Vec<Key> = runtime::get_named_arg(INTERMEDIATES_RUNTIME_ARG_NAME);
+    let best: (U256, Vec<Key>) = runtime::call_versioned_contract(
+        get_router(),
+        None,
+        GET_BEST_AMOUNT_OUT_ENTRY_POINT_NAME,
+        runtime_args! {
+            TOKEN_IN_RUNTIME_ARG_NAME => token_in,
+            TOKEN_OUT_RUNTIME_ARG_NAME => token_out,
+            AMOUNT_IN_RUNTIME_ARG_NAME => amount_in,
+            INTERMEDIATES_RUNTIME_ARG_NAME => intermediates,
+        },
+    );
+    store_result(best);
+}
+
+#[no_mangle]
+pub extern "C" fn check_cumulative_prices() {
+    let pair: ContractHash = runtime::get_named_arg(PAIR_RUNTIME_ARG_NAME);
+    let cumulative_prices: (U256, U256, u64) = runtime::call_contract(
+        pair,
+        GET_CUMULATIVE_PRICES_ENTRY_POINT_NAME,
+        runtime_args! {},
+    );
+    store_result(cumulative_prices);
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let path_parameter =
+        || Parameter::new(PATH_RUNTIME_ARG_NAME, CLType::List(Box::new(CLType::Key)));
+    let mut entry_points = EntryPoints::new();
+    entry_points.add_entry_point(EntryPoint::new(
+        String::from(CHECK_AMOUNTS_OUT_ENTRY_POINT_NAME),
+        vec![
+            Parameter::new(ROUTER_RUNTIME_ARG_NAME, Key::cl_type()),
+            Parameter::new(AMOUNT_IN_RUNTIME_ARG_NAME, U256::cl_type()),
+            path_parameter(),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        String::from(CHECK_AMOUNTS_IN_ENTRY_POINT_NAME),
+        vec![
+            Parameter::new(ROUTER_RUNTIME_ARG_NAME, Key::cl_type()),
+            Parameter::new(AMOUNT_OUT_RUNTIME_ARG_NAME, U256::cl_type()),
+            path_parameter(),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        String::from(CHECK_QUOTE_ENTRY_POINT_NAME),
+        vec![
+            Parameter::new(ROUTER_RUNTIME_ARG_NAME, Key::cl_type()),
+            Parameter::new(AMOUNT_A_RUNTIME_ARG_NAME, U256::cl_type()),
+            Parameter::new(RESERVE_A_RUNTIME_ARG_NAME, U256::cl_type()),
+            Parameter::new(RESERVE_B_RUNTIME_ARG_NAME, U256::cl_type()),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        String::from(CHECK_ALL_PAIRS_LENGTH_ENTRY_POINT_NAME),
+        vec![Parameter::new(ROUTER_RUNTIME_ARG_NAME, Key::cl_type())],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        String::from(CHECK_PAIR_AT_ENTRY_POINT_NAME),
+        vec![
+            Parameter::new(ROUTER_RUNTIME_ARG_NAME, Key::cl_type()),
+            Parameter::new(INDEX_RUNTIME_ARG_NAME, u64::cl_type()),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        String::from(CHECK_PAIRS_FOR_TOKEN_ENTRY_POINT_NAME),
+        vec![
+            Parameter::new(ROUTER_RUNTIME_ARG_NAME, Key::cl_type()),
+            Parameter::new(TOKEN_RUNTIME_ARG_NAME, Key::cl_type()),
+            Parameter::new(OFFSET_RUNTIME_ARG_NAME, u64::cl_type()),
+            Parameter::new(LIMIT_RUNTIME_ARG_NAME, u64::cl_type()),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        String::from(CHECK_BEST_AMOUNT_OUT_ENTRY_POINT_NAME),
+        vec![
+            Parameter::new(ROUTER_RUNTIME_ARG_NAME, Key::cl_type()),
+            Parameter::new(TOKEN_IN_RUNTIME_ARG_NAME, Key::cl_type()),
+            Parameter::new(TOKEN_OUT_RUNTIME_ARG_NAME, Key::cl_type()),
+            Parameter::new(AMOUNT_IN_RUNTIME_ARG_NAME, U256::cl_type()),
+            Parameter::new(
+                INTERMEDIATES_RUNTIME_ARG_NAME,
+                CLType::List(Box::new(CLType::Key)),
+            ),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        String::from(CHECK_CUMULATIVE_PRICES_ENTRY_POINT_NAME),
+        vec![Parameter::new(PAIR_RUNTIME_ARG_NAME, ContractHash::cl_type())],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    let (contract_hash, _version) = storage::new_contract(
+        entry_points,
+        None,
+        Some(String::from(CONTRACT_KEY_NAME)),
+        None,
+    );
+    runtime::put_key(CONTRACT_HASH_KEY_NAME, Key::from(contract_hash));
+}
+
+#[panic_handler]
+fn my_panic(_info: &core::panic::PanicInfo) -> ! {
+    loop {}
+}
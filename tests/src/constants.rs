@@ -1,4 +1,5 @@
 use once_cell::sync::Lazy;
+use swappery_sdk::names;
 use casper_types::{
     account::AccountHash, PublicKey, SecretKey,
 };
@@ -9,43 +10,79 @@ pub const CONTRACT_SWAPPERY_PAIR: &str = "swappery_pair.wasm";
 pub const CONTRACT_ERC20_TEST_CALL: &str = "erc20_test_call.wasm";
 pub const CONTRACT_SWAPPERY_ROUTER: &str = "swappery_router.wasm";
 pub const CONTRACT_WCSPR_TOKEN: &str = "wcspr.wasm";
+pub const CONTRACT_FLASH_SWAP_RECEIVER: &str = "flash_swap_receiver.wasm";
+pub const CONTRACT_ROUTER_TEST_CALL: &str = "router_test_call.wasm";
+pub const CONTRACT_FEE_ON_TRANSFER_TOKEN: &str = "fee_on_transfer_token.wasm";
+pub const CONTRACT_CSPR_SESSION: &str = "swappery_cspr_session.wasm";
 
 //arguments
 pub const ARG_NAME: &str = "name";
 pub const ARG_SYMBOL: &str = "symbol";
 pub const ARG_DECIMALS: &str = "decimals";
 pub const ARG_TOTAL_SUPPLY: &str = "total_supply";
-pub const ARG_CONTRACT_KEY_NAME: &str = "contract_key_name";
-pub const ARG_TOKEN0: &str = "token0";
-pub const ARG_TOKEN1: &str = "token1";
-pub const ARG_TO: &str = "to";
-pub const ARG_AMOUNT0: &str = "amount0";
-pub const ARG_AMOUNT1: &str = "amount1";
-pub const ARG_AMOUNT: &str = "amount";
-pub const ARG_RECIPIENT: &str = "recipient";
+pub const ARG_CONTRACT_KEY_NAME: &str = names::CONTRACT_KEY_NAME_RUNTIME_ARG_NAME;
+pub const ARG_TOKEN0: &str = names::TOKEN0_RUNTIME_ARG_NAME;
+pub const ARG_TOKEN1: &str = names::TOKEN1_RUNTIME_ARG_NAME;
+pub const ARG_TO: &str = names::TO_RUNTIME_ARG_NAME;
+pub const ARG_AMOUNT0: &str = names::AMOUNT0_RUNTIME_ARG_NAME;
+pub const ARG_AMOUNT1: &str = names::AMOUNT1_RUNTIME_ARG_NAME;
+pub const ARG_AMOUNT: &str = names::AMOUNT_RUNTIME_ARG_NAME;
+pub const ARG_RECIPIENT: &str = names::RECIPIENT_RUNTIME_ARG_NAME;
 pub const ARG_TOKEN_CONTRACT: &str = "token_contract";
-pub const ARG_ADDRESS: &str = "address";
+pub const ARG_ADDRESS: &str = names::ADDRESS_RUNTIME_ARG_NAME;
 pub const ARG_PAIR: &str = "pair";
-pub const ARG_OWNER: &str = "owner";
-pub const ARG_SPENDER: &str = "spender";
-pub const ARG_AMOUNT0_DESIRED: &str = "amount0_desired";
-pub const ARG_AMOUNT1_DESIRED: &str = "amount1_desired";
-pub const ARG_AMOUNT0_MIN: &str = "amount0_min";
-pub const ARG_AMOUNT1_MIN: &str = "amount1_min";
-pub const ARG_LIQUIDITY: &str = "liquidity";
-pub const ARG_PATH: &str = "path";
-pub const ARG_AMOUNT_OUT_MIN: &str = "amount_out_min";
-pub const ARG_AMOUNT_IN_MAX: &str = "amount_in_max";
-pub const ARG_AMOUNT_IN: &str = "amount_in";
-pub const ARG_AMOUNT_OUT: &str = "amount_out";
-pub const ARG_DEAD_LINE: &str = "dead_line";
-pub const ARG_FEETO: &str = "feeto";
+pub const ARG_OWNER: &str = names::OWNER_RUNTIME_ARG_NAME;
+pub const ARG_SPENDER: &str = names::SPENDER_RUNTIME_ARG_NAME;
+pub const ARG_AMOUNT0_DESIRED: &str = names::AMOUNT0_DESIRED_RUNTIME_ARG_NAME;
+pub const ARG_AMOUNT1_DESIRED: &str = names::AMOUNT1_DESIRED_RUNTIME_ARG_NAME;
+pub const ARG_AMOUNT0_MIN: &str = names::AMOUNT0_MIN_RUNTIME_ARG_NAME;
+pub const ARG_AMOUNT1_MIN: &str = names::AMOUNT1_MIN_RUNTIME_ARG_NAME;
+pub const ARG_LIQUIDITY: &str = names::LIQUIDITY_RUNTIME_ARG_NAME;
+pub const ARG_PATH: &str = names::PATH_RUNTIME_ARG_NAME;
+pub const ARG_AMOUNT_OUT_MIN: &str = names::AMOUNT_OUT_MIN_RUNTIME_ARG_NAME;
+pub const ARG_AMOUNT_IN_MAX: &str = names::AMOUNT_IN_MAX_RUNTIME_ARG_NAME;
+pub const ARG_AMOUNT_IN: &str = names::AMOUNT_IN_RUNTIME_ARG_NAME;
+pub const ARG_AMOUNT_OUT: &str = names::AMOUNT_OUT_RUNTIME_ARG_NAME;
+pub const ARG_DEAD_LINE: &str = names::DEAD_LINE_RUNTIME_ARG_NAME;
+pub const ARG_FEETO: &str = names::FEETO_RUNTIME_ARG_NAME;
+pub const ARG_DATA: &str = names::DATA_RUNTIME_ARG_NAME;
+pub const ARG_FACTORY: &str = "factory";
+pub const ARG_SWAP_FEE_BPS: &str = names::SWAP_FEE_BPS_RUNTIME_ARG_NAME;
+pub const ARG_PROTOCOL_FEE_SHARE_BPS: &str = names::PROTOCOL_FEE_SHARE_BPS_RUNTIME_ARG_NAME;
+pub const ARG_ROUTER: &str = names::ROUTER_RUNTIME_ARG_NAME;
+pub const ARG_ENTRY_POINT: &str = names::ENTRY_POINT_RUNTIME_ARG_NAME;
+pub const ARG_TOKEN: &str = names::TOKEN_RUNTIME_ARG_NAME;
+pub const ARG_AMOUNT_A: &str = names::AMOUNT_A_RUNTIME_ARG_NAME;
+pub const ARG_RESERVE_A: &str = names::RESERVE_A_RUNTIME_ARG_NAME;
+pub const ARG_RESERVE_B: &str = names::RESERVE_B_RUNTIME_ARG_NAME;
+pub const ARG_INDEX: &str = names::INDEX_RUNTIME_ARG_NAME;
+pub const ARG_OFFSET: &str = names::OFFSET_RUNTIME_ARG_NAME;
+pub const ARG_LIMIT: &str = names::LIMIT_RUNTIME_ARG_NAME;
+pub const ARG_TOKEN_IN: &str = names::TOKEN_IN_RUNTIME_ARG_NAME;
+pub const ARG_TOKEN_OUT: &str = names::TOKEN_OUT_RUNTIME_ARG_NAME;
+pub const ARG_INTERMEDIATES: &str = names::INTERMEDIATES_RUNTIME_ARG_NAME;
+pub const ARG_PURSE: &str = names::PURSE_RUNTIME_ARG_NAME;
+pub const ARG_AMOUNT_TOKEN_DESIRED: &str = names::AMOUNT_TOKEN_DESIRED_RUNTIME_ARG_NAME;
+pub const ARG_AMOUNT_CSPR_DESIRED: &str = names::AMOUNT_CSPR_DESIRED_RUNTIME_ARG_NAME;
+pub const ARG_AMOUNT_TOKEN_MIN: &str = names::AMOUNT_TOKEN_MIN_RUNTIME_ARG_NAME;
+pub const ARG_AMOUNT_CSPR_MIN: &str = names::AMOUNT_CSPR_MIN_RUNTIME_ARG_NAME;
 
 //key names
 pub const FEETO_KEY_NAME: &str = "feeto";
 pub const FEETO_SETTER_KEY_NAME: &str = "feeto_setter";
 pub const PAIR_LIST_KEY_NAME: &str = "pair_list";
 pub const ROUTER_CONTRACT_KEY_NAME: &str = "swappery_router";
+pub const PAIR_KEY_NAME_PREFIX: &str = "swappery_pair";
+pub const PRICE0_CUMULATIVE_LAST_KEY_NAME: &str = "price0_cumulative_last";
+pub const PRICE1_CUMULATIVE_LAST_KEY_NAME: &str = "price1_cumulative_last";
+pub const BLOCK_TIMESTAMP_LAST_KEY_NAME: &str = "block_timestamp_last";
+pub const RESERVE0_KEY_NAME: &str = "reserve0";
+pub const RESERVE1_KEY_NAME: &str = "reserve1";
+pub const SWAP_FEE_BPS_KEY_NAME: &str = "swap_fee_bps";
+pub const TOTAL_SUPPLY_KEY_NAME: &str = "total_supply";
+pub const KLAST_KEY_NAME: &str = "klast";
+pub const FLASH_SWAP_RECEIVER_KEY_NAME: &str = "flash_swap_receiver";
+pub const FEE_ON_TRANSFER_TOKEN_CONTRACT_HASH_KEY_NAME: &str = "fee_on_transfer_token_contract_hash";
 
 pub const PAIR_NAME: &str = "SwapperyPair";
 pub const PAIR_SYMBOL: &str = "SWP";
@@ -75,33 +112,73 @@ pub const WCSPR_TOTAL_SUPPLY: u64 = 2_000_000;
 pub const WCSPR_CONTRACT_KEY_NAME: &str = "wcspr_token";
 
 //methods
-pub const METHOD_TRANSFER: &str = "transfer";
-pub const METHOD_APPROVE: &str = "approve";
-pub const METHOD_MINT: &str = "mint";
-pub const METHOD_BURN: &str = "burn";
-pub const METHOD_SWAP: &str = "swap";
+pub const METHOD_TRANSFER: &str = names::TRANSFER_ENTRY_POINT_NAME;
+pub const METHOD_APPROVE: &str = names::APPROVE_ENTRY_POINT_NAME;
+pub const METHOD_MINT: &str = names::MINT_ENTRY_POINT_NAME;
+pub const METHOD_BURN: &str = names::BURN_ENTRY_POINT_NAME;
+pub const METHOD_SWAP: &str = names::SWAP_ENTRY_POINT_NAME;
+pub const METHOD_SKIM: &str = names::SKIM_ENTRY_POINT_NAME;
+pub const METHOD_SYNC: &str = names::SYNC_ENTRY_POINT_NAME;
 pub const CHECK_TOTAL_SUPPLY_ENTRYPOINT: &str = "check_total_supply";
 pub const CHECK_BALANCE_OF_ENTRYPOINT: &str = "check_balance_of";
 pub const CHECK_ALLOWANCE_OF_ENTRYPOINT: &str = "check_allowance_of";
+pub const CHECK_AMOUNTS_OUT_ENTRYPOINT: &str = "check_amounts_out";
+pub const CHECK_AMOUNTS_IN_ENTRYPOINT: &str = "check_amounts_in";
+pub const CHECK_QUOTE_ENTRYPOINT: &str = "check_quote";
+pub const CHECK_ALL_PAIRS_LENGTH_ENTRYPOINT: &str = "check_all_pairs_length";
+pub const CHECK_PAIR_AT_ENTRYPOINT: &str = "check_pair_at";
+pub const CHECK_PAIRS_FOR_TOKEN_ENTRYPOINT: &str = "check_pairs_for_token";
+pub const CHECK_BEST_AMOUNT_OUT_ENTRYPOINT: &str = "check_best_amount_out";
+pub const CHECK_CUMULATIVE_PRICES_ENTRYPOINT: &str = "check_cumulative_prices";
 pub const METHOD_TRANSFER_AS_STORED_CONTRACT: &str = "transfer_as_stored_contract";
 pub const METHOD_APPROVE_AS_STORED_CONTRACT: &str = "approve_as_stored_contract";
-pub const METHOD_CREATE_PAIR: &str = "create_pair";
-pub const METHOD_GET_PAIR: &str = "get_pair";
-pub const METHOD_ADD_LIQUIDITY: &str = "add_liquidity";
-pub const METHOD_REMOVE_LIQUIDITY: &str = "remove_liquidity";
-pub const METHOD_SWAP_EXACT_TOKENS_FOR_TOKENS: &str = "swap_exact_tokens_for_tokens";
-pub const METHOD_SWAP_TOKENS_FOR_EXACT_TOKENS: &str = "swap_tokens_for_exact_tokens";
-pub const METHOD_SET_FEETO: &str = "set_feeto";
-pub const METHOD_SET_FEETO_SETTER: &str = "set_feeto_setter";
-pub const METHOD_SWAP_EXACT_TOKENS_FOR_TOKENS_SUPPORTING_FEE: &str = "swap_exact_tokens_for_tokens_supporting_fee";
+pub const METHOD_CREATE_PAIR: &str = names::CREATE_PAIR_ENTRY_POINT_NAME;
+pub const METHOD_GET_PAIR: &str = names::GET_PAIR_ENTRY_POINT_NAME;
+pub const METHOD_PAIR_AT: &str = names::PAIR_AT_ENTRY_POINT_NAME;
+pub const METHOD_ADD_LIQUIDITY: &str = names::ADD_LIQUIDITY_ENTRY_POINT_NAME;
+pub const METHOD_REMOVE_LIQUIDITY: &str = names::REMOVE_LIQUIDITY_ENTRY_POINT_NAME;
+pub const METHOD_SWAP_EXACT_TOKENS_FOR_TOKENS: &str = names::SWAP_EXACT_TOKENS_FOR_TOKENS_ENTRY_POINT_NAME;
+pub const METHOD_SWAP_TOKENS_FOR_EXACT_TOKENS: &str = names::SWAP_TOKENS_FOR_EXACT_TOKENS_ENTRY_POINT_NAME;
+pub const METHOD_SET_FEETO: &str = names::SET_FEETO_ENTRY_POINT_NAME;
+pub const METHOD_SET_FEETO_SETTER: &str = names::SET_FEETO_SETTER_ENTRY_POINT_NAME;
+pub const METHOD_SET_PAIR_SWAP_FEE: &str = names::SET_PAIR_SWAP_FEE_ENTRY_POINT_NAME;
+pub const METHOD_SET_SWAP_FEE: &str = names::SET_SWAP_FEE_ENTRY_POINT_NAME;
+pub const METHOD_SET_PROTOCOL_FEE_SHARE: &str = names::SET_PROTOCOL_FEE_SHARE_ENTRY_POINT_NAME;
+pub const METHOD_SWAP_EXACT_TOKENS_FOR_TOKENS_SUPPORTING_FEE: &str = names::SWAP_EXACT_TOKENS_FOR_TOKENS_SUPPORTING_FEE_ENTRY_POINT_NAME;
+pub const METHOD_ADD_LIQUIDITY_CSPR: &str = names::ADD_LIQUIDITY_CSPR_ENTRY_POINT_NAME;
+pub const METHOD_REMOVE_LIQUIDITY_CSPR: &str = names::REMOVE_LIQUIDITY_CSPR_ENTRY_POINT_NAME;
+pub const METHOD_REMOVE_LIQUIDITY_SUPPORTING_FEE: &str = names::REMOVE_LIQUIDITY_SUPPORTING_FEE_ENTRY_POINT_NAME;
+pub const METHOD_REMOVE_LIQUIDITY_CSPR_SUPPORTING_FEE: &str = names::REMOVE_LIQUIDITY_CSPR_SUPPORTING_FEE_ENTRY_POINT_NAME;
+pub const METHOD_SWAP_EXACT_CSPR_FOR_TOKENS: &str = names::SWAP_EXACT_CSPR_FOR_TOKENS_ENTRY_POINT_NAME;
+pub const METHOD_SWAP_CSPR_FOR_EXACT_TOKENS: &str = names::SWAP_CSPR_FOR_EXACT_TOKENS_ENTRY_POINT_NAME;
+pub const METHOD_SWAP_EXACT_TOKENS_FOR_CSPR: &str = names::SWAP_EXACT_TOKENS_FOR_CSPR_ENTRY_POINT_NAME;
+pub const METHOD_SWAP_TOKENS_FOR_EXACT_CSPR: &str = names::SWAP_TOKENS_FOR_EXACT_CSPR_ENTRY_POINT_NAME;
 
 pub const RESULT_KEY: &str = "result";
 pub const ERC20_TEST_CALL_KEY: &str = "erc20_test_call";
+pub const ROUTER_TEST_CALL_KEY: &str = "router_test_call";
+
+//limits
+pub const MAX_INTERMEDIATES: usize = 4;
+/// Gas a best-path swap may burn at `MAX_INTERMEDIATES`: 300 CSPR.
+pub const MAX_BEST_SWAP_GAS: u64 = 300_000_000_000;
 
 //error
 pub const ERROR_INSUFFICIENT_LIQUIDITY: u16 = u16::MAX - 6;
+pub const ERROR_FORBIDDEN: u16 = u16::MAX - 11;
 pub const ERROR_K: u16 = u16::MAX - 13;
+pub const ERROR_INSUFFICIENT_OUTPUT_AMOUNT: u16 = u16::MAX - 18;
+pub const ERROR_INVALID_PATH: u16 = u16::MAX - 23;
+pub const ERROR_EXPIRED: u16 = u16::MAX - 24;
 pub const ERROR_PERMISSION: u16 = u16::MAX - 25;
+pub const ERROR_PAIR_EXISTS: u16 = u16::MAX - 26;
+pub const ERROR_INVALID_SWAP_FEE: u16 = u16::MAX - 27;
+pub const ERROR_INVALID_PROTOCOL_FEE_SHARE: u16 = u16::MAX - 28;
+pub const ERROR_PAIR_NOT_FOUND: u16 = u16::MAX - 29;
+pub const ERROR_IDENTICAL_ADDRESSES: u16 = u16::MAX - 30;
+pub const ERROR_NO_ROUTE: u16 = u16::MAX - 31;
+pub const ERROR_TOO_MANY_INTERMEDIATES: u16 = u16::MAX - 32;
+pub const ERROR_INVALID_RECIPIENT: u16 = u16::MAX - 34;
 
 //accounts
 pub const ACCOUNT_1_SECRET_KEY: Lazy<SecretKey> =
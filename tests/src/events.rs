@@ -0,0 +1,103 @@
+//! Host-side decoding of events stored in the Casper Event Standard (CES) layout.
+//!
+//! Events are decoded as the indexer decodes them, see [`swappery_indexer::ces`]; this module
+//! reads them and their schemas out of an [`InMemoryWasmTestBuilder`]'s global state.
+
+use std::collections::BTreeMap;
+
+use casper_engine_test_support::InMemoryWasmTestBuilder;
+use casper_types::{
+    bytesrepr::{Bytes, FromBytes},
+    CLType, ContractHash, Key, URef,
+};
+
+pub use swappery_indexer::ces::{
+    CES_VERSION_KEY, EVENTS_DICT, EVENTS_LENGTH, EVENTS_SCHEMA, EVENT_PREFIX,
+};
+pub use swappery_indexer::event::{CesEvent, PairEvent, RouterEvent};
+
+/// Field names and types of every event, keyed by event name.
+pub type Schemas = BTreeMap<String, Vec<(String, CLType)>>;
+
+fn named_key(builder: &InMemoryWasmTestBuilder, contract: ContractHash, name: &str) -> Key {
+    *builder
+        .get_contract(contract)
+        .expect("should have contract")
+        .named_keys()
+        .get(name)
+        .expect("should have CES named key")
+}
+
+/// Returns the dictionary holding the events of `contract`.
+pub fn events_uref(builder: &InMemoryWasmTestBuilder, contract: ContractHash) -> URef {
+    named_key(builder, contract, EVENTS_DICT)
+        .into_uref()
+        .expect("should have events dictionary")
+}
+
+/// Returns the number of events `contract` has emitted.
+pub fn events_length(builder: &InMemoryWasmTestBuilder, contract: ContractHash) -> u32 {
+    builder.get_value(contract, EVENTS_LENGTH)
+}
+
+/// Returns the schemas `contract` registered at install.
+pub fn schemas(builder: &InMemoryWasmTestBuilder, contract: ContractHash) -> Schemas {
+    let schema_key = named_key(builder, contract, EVENTS_SCHEMA);
+    let value = builder
+        .query(None, schema_key, &[])
+        .expect("should have schemas");
+    let cl_value = value.as_cl_value().expect("should be a CLValue");
+    let (schemas, remainder) =
+        Schemas::from_bytes(cl_value.inner_bytes()).expect("should decode schemas");
+    assert!(remainder.is_empty(), "trailing bytes after schemas");
+    schemas
+}
+
+/// Returns the name and serialized fields of the event at `index`.
+pub fn raw_event(
+    builder: &InMemoryWasmTestBuilder,
+    contract: ContractHash,
+    index: u32,
+) -> (String, Vec<u8>) {
+    let events_uref = events_uref(builder, contract);
+    let value = builder
+        .query_dictionary_item(None, events_uref, &index.to_string())
+        .expect("should have event");
+    let bytes: Bytes = value
+        .as_cl_value()
+        .cloned()
+        .expect("should be a CLValue")
+        .into_t()
+        .expect("should be bytes");
+
+    let (name, fields) = String::from_bytes(&bytes).expect("should have event name");
+    let name = name
+        .strip_prefix(EVENT_PREFIX)
+        .expect("should have event prefix")
+        .to_string();
+    (name, fields.to_vec())
+}
+
+/// Decodes the event at `index`, checking it against the event's schema.
+pub fn event_at<E: CesEvent>(
+    builder: &InMemoryWasmTestBuilder,
+    contract: ContractHash,
+    index: u32,
+) -> E {
+    let (name, fields) = raw_event(builder, contract, index);
+    assert!(
+        schemas(builder, contract).contains_key(&name),
+        "event {} has no schema",
+        name
+    );
+    let (event, remainder) = E::decode(&name, &fields).expect("should decode event");
+    assert!(remainder.is_empty(), "trailing bytes after event {}", name);
+    event
+}
+
+/// Decodes every event `contract` has emitted, in order.
+pub fn events<E: CesEvent>(builder: &InMemoryWasmTestBuilder, contract: ContractHash) -> Vec<E> {
+    (0..events_length(builder, contract))
+        .map(|index| event_at(builder, contract, index))
+        .collect()
+}
@@ -1,4 +1,5 @@
 pub mod constants;
+pub mod events;
 pub mod test_call;
 #[cfg(test)]
 // mod swappery_pair_tests;
@@ -172,7 +172,6 @@ fn should_mint_and_burn_lp_token() {
         consts::METHOD_MINT,
         runtime_args!{
             consts::ARG_TO => owner_key,
-            consts::ARG_FEETO => Key::Account(*DEFAULT_ACCOUNT_ADDR),
         },
     ).build();
     builder.exec(pair_mint_request).expect_success().commit();
@@ -192,7 +191,6 @@ fn should_mint_and_burn_lp_token() {
         consts::METHOD_BURN,
         runtime_args!{
             consts::ARG_TO => owner_key,
-            consts::ARG_FEETO => Key::Account(*DEFAULT_ACCOUNT_ADDR),
         },
     ).build();
     builder.exec(pair_burn_request).expect_success().commit();
@@ -234,7 +232,6 @@ fn should_swap_tokens_with_pair() {
         consts::METHOD_MINT,
         runtime_args!{
             consts::ARG_TO => owner_key,
-            consts::ARG_FEETO => Key::Account(*DEFAULT_ACCOUNT_ADDR),
         },
     ).build();
     builder.exec(pair_mint_request).expect_success().commit();
@@ -294,7 +291,6 @@ fn should_not_swap_tokens_above_reserves() {
         consts::METHOD_MINT,
         runtime_args!{
             consts::ARG_TO => owner_key,
-            consts::ARG_FEETO => Key::Account(*DEFAULT_ACCOUNT_ADDR),
         },
     ).build();
     builder.exec(pair_mint_request).expect_success().commit();
@@ -348,7 +344,6 @@ fn should_not_swap_over_limits() {
         consts::METHOD_MINT,
         runtime_args!{
             consts::ARG_TO => owner_key,
-            consts::ARG_FEETO => Key::Account(*DEFAULT_ACCOUNT_ADDR),
         },
     ).build();
     builder.exec(pair_mint_request).expect_success().commit();
@@ -409,11 +404,39 @@ fn should_mint_minimum_liquidity_to_zero_address() {
         consts::METHOD_MINT,
         runtime_args!{
             consts::ARG_TO => owner_key,
-            consts::ARG_FEETO => Key::Account(*DEFAULT_ACCOUNT_ADDR),
         },
     ).build();
     builder.exec(pair_mint_request).expect_success().commit();
 
     let owner_balance = erc20_check_balance_of(&mut builder, &pair_contract, zero_key);
     assert_eq!(owner_balance, U256::from(1_000u64));
-}
\ No newline at end of file
+}
+
+#[test]
+fn should_not_set_swap_fee_above_maximum() {
+    let (mut builder, TestContext { pair_package, pair_contract, .. }) = setup();
+
+    // The installing account is the factory of a pair installed on its own.
+    let make_set_swap_fee_request = |swap_fee_bps: u32| {
+        ExecuteRequestBuilder::versioned_contract_call_by_hash(
+            *DEFAULT_ACCOUNT_ADDR,
+            pair_package,
+            None,
+            consts::METHOD_SET_SWAP_FEE,
+            runtime_args!{
+                consts::ARG_SWAP_FEE_BPS => swap_fee_bps,
+            },
+        ).build()
+    };
+    builder.exec(make_set_swap_fee_request(100)).expect_success().commit();
+    builder.exec(make_set_swap_fee_request(101)).commit();
+
+    let error = builder.get_error().expect("should have error");
+    assert!(
+        matches!(error, CoreError::Exec(ExecError::Revert(ApiError::User(user_error))) if user_error == consts::ERROR_INVALID_SWAP_FEE),
+        "{:?}",
+        error
+    );
+    let swap_fee_bps: u32 = builder.get_value(pair_contract, consts::SWAP_FEE_BPS_KEY_NAME);
+    assert_eq!(swap_fee_bps, 100);
+}
@@ -1,3 +1,5 @@
+use std::collections::{BTreeMap, BTreeSet};
+
 use casper_engine_test_support::{
     ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_RUN_GENESIS_REQUEST,
     DEFAULT_ACCOUNT_ADDR, MINIMUM_ACCOUNT_CREATION_BALANCE,
@@ -7,13 +9,22 @@ use casper_execution_engine::core::{
     execution::Error as ExecError,
 };
 use casper_types::{
-    account::AccountHash, runtime_args, system::mint, 
-    ContractHash, ContractPackageHash, Key, RuntimeArgs, U256,
+    account::AccountHash, bytesrepr::{Bytes, ToBytes}, runtime_args, system::mint, 
+    ContractHash, ContractPackageHash, Key, RuntimeArgs, U256, U512,
     ApiError,
 };
 use casper_erc20::Address;
 use crate::constants as consts;
-use crate::test_call::{make_erc20_transfer_request, erc20_check_allowance_of, erc20_check_balance_of};
+use crate::events::{self, PairEvent, RouterEvent};
+use swappery_indexer::{engine, event::Event, store::Store};
+use swappery_sdk::{cspr, pair, router, EntryPointCall};
+use swappery_simulator::{action, Action, Dex, MAX_PROTOCOL_FEE_SHARE_BPS, MAX_SWAP_FEE_BPS};
+use crate::test_call::{
+    make_erc20_transfer_request, make_stored_call_request, erc20_check_allowance_of, erc20_check_balance_of,
+    router_check_all_pairs_length, router_check_amounts_in, router_check_amounts_out,
+    pair_check_cumulative_prices, router_check_best_amount_out, router_check_pair_at,
+    router_check_pairs_for_token, router_check_quote,
+};
 
 #[derive(Copy, Clone)]
 struct TestContext {
@@ -26,6 +37,16 @@ struct TestContext {
 }
 
 fn setup() -> (InMemoryWasmTestBuilder, TestContext) {
+    setup_with_total_supplies(
+        U256::from(consts::TOKEN0_TOTAL_SUPPLY),
+        U256::from(consts::TOKEN1_TOTAL_SUPPLY),
+    )
+}
+
+fn setup_with_total_supplies(
+    token0_total_supply: U256,
+    token1_total_supply: U256,
+) -> (InMemoryWasmTestBuilder, TestContext) {
     let mut builder = InMemoryWasmTestBuilder::default();
     builder.run_genesis(&*DEFAULT_RUN_GENESIS_REQUEST);
 
@@ -46,7 +67,7 @@ fn setup() -> (InMemoryWasmTestBuilder, TestContext) {
             consts::ARG_NAME => consts::TOKEN0_NAME,
             consts::ARG_SYMBOL => consts::TOKEN0_SYMBOL,
             consts::ARG_DECIMALS => consts::TOKEN0_DECIMALS,
-            consts::ARG_TOTAL_SUPPLY => U256::from(consts::TOKEN0_TOTAL_SUPPLY),
+            consts::ARG_TOTAL_SUPPLY => token0_total_supply,
             consts::ARG_CONTRACT_KEY_NAME => consts::TOKEN0_CONTRACT_KEY_NAME,
         },
     )
@@ -59,7 +80,7 @@ fn setup() -> (InMemoryWasmTestBuilder, TestContext) {
             consts::ARG_NAME => consts::TOKEN1_NAME,
             consts::ARG_SYMBOL => consts::TOKEN1_SYMBOL,
             consts::ARG_DECIMALS => consts::TOKEN1_DECIMALS,
-            consts::ARG_TOTAL_SUPPLY => U256::from(consts::TOKEN1_TOTAL_SUPPLY),
+            consts::ARG_TOTAL_SUPPLY => token1_total_supply,
             consts::ARG_CONTRACT_KEY_NAME => consts::TOKEN1_CONTRACT_KEY_NAME,
         },
     )
@@ -84,10 +105,18 @@ fn setup() -> (InMemoryWasmTestBuilder, TestContext) {
     )
     .build();
 
+    let install_request_router_test_call = ExecuteRequestBuilder::standard(
+        *DEFAULT_ACCOUNT_ADDR,
+        consts::CONTRACT_ROUTER_TEST_CALL,
+        RuntimeArgs::default(),
+    )
+    .build();
+
     builder.exec(transfer_request).expect_success().commit();
     builder.exec(install_request_token0).expect_success().commit();
     builder.exec(install_request_token1).expect_success().commit();
     builder.exec(install_request_test_call).expect_success().commit();
+    builder.exec(install_request_router_test_call).expect_success().commit();
     builder.exec(install_request_wcspr_token).expect_success().commit();
 
     let account = builder
@@ -127,22 +156,6 @@ fn setup() -> (InMemoryWasmTestBuilder, TestContext) {
     )
     .build();
 
-    let install_request_pair_0_1 = ExecuteRequestBuilder::standard(
-        *DEFAULT_ACCOUNT_ADDR,
-        consts::CONTRACT_SWAPPERY_PAIR,
-        runtime_args! {
-            consts::ARG_NAME => consts::PAIR_NAME,
-            consts::ARG_SYMBOL => consts::PAIR_SYMBOL,
-            consts::ARG_DECIMALS => consts::PAIR_DECIMALS,
-            consts::ARG_TOTAL_SUPPLY => U256::from(consts::PAIR_TOTAL_SUPPLY),
-            consts::ARG_CONTRACT_KEY_NAME => consts::PAIR_CONTRACT_KEY_NAME,
-            consts::ARG_TOKEN0 => Key::from(token0_contract),
-            consts::ARG_TOKEN1 => Key::from(token1_contract),
-        },
-    )
-    .build();
-
-    builder.exec(install_request_pair_0_1).expect_success().commit();
     builder.exec(install_request_router).expect_success().commit();
 
     let account = builder
@@ -156,20 +169,6 @@ fn setup() -> (InMemoryWasmTestBuilder, TestContext) {
         .map(ContractPackageHash::new)
         .expect("should have contract package hash");
 
-    let pair_0_1_package = account
-        .named_keys()
-        .get(consts::PAIR_CONTRACT_KEY_NAME)
-        .and_then(|key| key.into_hash())
-        .map(ContractPackageHash::new)
-        .expect("should have contract package hash");
-
-    let pair_0_1_contract = account
-        .named_keys()
-        .get(consts::PAIR_CONTRACT_HASH_KEY_NAME)
-        .and_then(|key| key.into_hash())
-        .map(ContractHash::new)
-        .expect("should have contract hash");
-
     let create_pair_0_1_request: ExecuteRequest = ExecuteRequestBuilder::versioned_contract_call_by_hash(
         *DEFAULT_ACCOUNT_ADDR,
         router_package,
@@ -178,13 +177,15 @@ fn setup() -> (InMemoryWasmTestBuilder, TestContext) {
         runtime_args! {
             consts::ARG_TOKEN0 => token0_contract,
             consts::ARG_TOKEN1 => token1_contract,
-            consts::ARG_PAIR => Address::from(pair_0_1_package),
         },
     )
     .build();
 
     builder.exec(create_pair_0_1_request).expect_success().commit();
 
+    let (pair_0_1_package, pair_0_1_contract) =
+        get_pair_hashes(&builder, router_package, token0_contract, token1_contract);
+
     let test_context = TestContext {
         token0_contract,
         token1_contract,
@@ -197,6 +198,111 @@ fn setup() -> (InMemoryWasmTestBuilder, TestContext) {
     (builder, test_context)
 }
 
+/// Returns `test_context` with `token0_contract` and `token1_contract` swapped into the pair's
+/// order, for tests asserting reserves, prices or events by token index.
+fn in_pair_order(test_context: TestContext) -> TestContext {
+    if test_context.token0_contract.lt(&test_context.token1_contract) {
+        return test_context;
+    }
+    TestContext {
+        token0_contract: test_context.token1_contract,
+        token1_contract: test_context.token0_contract,
+        ..test_context
+    }
+}
+
+/// Looks up the pair the router deployed for `token0`/`token1`, in either order, through the
+/// router's `CreatePair` events.
+fn get_pair_hashes(
+    builder: &InMemoryWasmTestBuilder,
+    router_package: ContractPackageHash,
+    token0: ContractHash,
+    token1: ContractHash,
+) -> (ContractPackageHash, ContractHash) {
+    let (token0, token1) = if token0.lt(&token1) { (token0, token1) } else { (token1, token0) };
+    let router_contract = get_router_contract(builder, router_package);
+
+    let pair_package = events::events::<RouterEvent>(builder, router_contract)
+        .into_iter()
+        .find_map(|event| match event {
+            RouterEvent::CreatePair { token0: created0, token1: created1, pair, .. }
+                if (created0, created1) == (token0, token1) =>
+            {
+                pair.as_contract_package_hash().copied()
+            }
+            _ => None,
+        })
+        .expect("should have pair contract package hash");
+
+    let pair_contract = builder
+        .get_contract_package(pair_package)
+        .and_then(|package| package.current_contract_hash())
+        .expect("should have pair contract hash");
+
+    (pair_package, pair_contract)
+}
+
+/// Funds `ACCOUNT_1` with both tokens, approves the router and adds the 30_000/50_000 liquidity
+/// most scenarios start from.
+fn provide_initial_liquidity(
+    builder: &mut InMemoryWasmTestBuilder,
+    test_context: &TestContext,
+    block_time: u64,
+) {
+    let token0_transfer_request = make_erc20_transfer_request(
+        Key::Account(*DEFAULT_ACCOUNT_ADDR),
+        &test_context.token0_contract,
+        Key::Account(*consts::ACCOUNT_1_ADDR),
+        U256::from(100_000u64),
+    );
+
+    let token1_transfer_request = make_erc20_transfer_request(
+        Key::Account(*DEFAULT_ACCOUNT_ADDR),
+        &test_context.token1_contract,
+        Key::Account(*consts::ACCOUNT_1_ADDR),
+        U256::from(100_000u64),
+    );
+
+    builder.exec(token0_transfer_request).expect_success().commit();
+    builder.exec(token1_transfer_request).expect_success().commit();
+
+    for token in [test_context.token0_contract, test_context.token1_contract] {
+        let approve_request = ExecuteRequestBuilder::contract_call_by_hash(
+            *consts::ACCOUNT_1_ADDR,
+            token,
+            consts::METHOD_APPROVE,
+            runtime_args! {
+                consts::ARG_OWNER => Key::Account(*consts::ACCOUNT_1_ADDR),
+                consts::ARG_SPENDER => Key::Hash(test_context.router_package.value()),
+                consts::ARG_AMOUNT => U256::from(100_000u64),
+            }
+        )
+        .build();
+        builder.exec(approve_request).expect_success().commit();
+    }
+
+    let add_liquidity_request: ExecuteRequest = ExecuteRequestBuilder::versioned_contract_call_by_hash(
+        *consts::ACCOUNT_1_ADDR,
+        test_context.router_package,
+        None,
+        consts::METHOD_ADD_LIQUIDITY,
+        runtime_args! {
+            consts::ARG_TOKEN0 => test_context.token0_contract,
+            consts::ARG_TOKEN1 => test_context.token1_contract,
+            consts::ARG_AMOUNT0_DESIRED => U256::from(30_000u64),
+            consts::ARG_AMOUNT1_DESIRED => U256::from(50_000u64),
+            consts::ARG_AMOUNT0_MIN => U256::zero(),
+            consts::ARG_AMOUNT1_MIN => U256::zero(),
+            consts::ARG_TO => Key::Account(*consts::ACCOUNT_1_ADDR),
+            consts::ARG_DEAD_LINE => U256::MAX,
+        },
+    )
+    .with_block_time(block_time)
+    .build();
+
+    builder.exec(add_liquidity_request).expect_success().commit();
+}
+
 #[test]
 fn should_setup_context() {
     let _ = setup();
@@ -1088,6 +1194,12 @@ fn should_mint_fee_to_feeto_address() {
 
     let fee_balance: U256 = erc20_check_balance_of(&mut builder, &test_context.pair_0_1_contract, Key::from(AccountHash::new([10u8; 32])));
     assert_eq!(fee_balance, U256::from(2u64));
+
+    let pair_events: Vec<PairEvent> = events::events(&builder, test_context.pair_0_1_contract);
+    assert!(pair_events.contains(&PairEvent::MintFee {
+        fee_to: Address::from(AccountHash::new([10u8; 32])),
+        liquidity: U256::from(2u64),
+    }));
 }
 
 #[test]
@@ -1140,6 +1252,23 @@ fn should_get_error_set_feeto_setter_without_permission() {
     );
 }
 
+/// Fees can accrue to a contract package, such as a treasury contract, as well as an account.
+#[test]
+fn should_set_feeto_to_contract() {
+    let (mut builder, test_context) = setup();
+
+    let set_feeto_request = make_stored_call_request(
+        *DEFAULT_ACCOUNT_ADDR,
+        test_context.router_package,
+        &router::SetFeeto { feeto: Key::from(test_context.pair_0_1_package) },
+    );
+    builder.exec(set_feeto_request).expect_success().commit();
+
+    let router_contract = get_router_contract(&builder, test_context.router_package);
+    let feeto: Key = builder.get_value(router_contract, consts::FEETO_KEY_NAME);
+    assert_eq!(feeto, Key::from(test_context.pair_0_1_package));
+}
+
 #[test]
 fn should_swap_exact_tokens_for_tokens_supporting_fee() {
     let (mut builder, test_context) = setup();
@@ -1235,3 +1364,2651 @@ fn should_swap_exact_tokens_for_tokens_supporting_fee() {
     let token1_balance: U256 = erc20_check_balance_of(&mut builder, &test_context.token1_contract, Key::Account(*consts::ACCOUNT_1_ADDR));
     assert_eq!(token1_balance, U256::from(62_481u64));
 }
+
+#[test]
+fn should_accumulate_prices_over_time() {
+    let (mut builder, test_context) = setup();
+    let test_context = in_pair_order(test_context);
+
+    provide_initial_liquidity(&mut builder, &test_context, 1_000);
+
+    let price0_cumulative_last: U256 = builder.get_value(test_context.pair_0_1_contract, consts::PRICE0_CUMULATIVE_LAST_KEY_NAME);
+    assert_eq!(price0_cumulative_last, U256::zero());
+    let block_timestamp_last: u64 = builder.get_value(test_context.pair_0_1_contract, consts::BLOCK_TIMESTAMP_LAST_KEY_NAME);
+    assert_eq!(block_timestamp_last, 1_000u64);
+
+    let mut path: Vec<ContractHash> = Vec::new();
+    path.push(test_context.token0_contract);
+    path.push(test_context.token1_contract);
+
+    let swap_request: ExecuteRequest = ExecuteRequestBuilder::versioned_contract_call_by_hash(
+        *consts::ACCOUNT_1_ADDR,
+        test_context.router_package,
+        None,
+        consts::METHOD_SWAP_EXACT_TOKENS_FOR_TOKENS,
+        runtime_args! {
+            consts::ARG_AMOUNT_IN => U256::from(10_000u64),
+            consts::ARG_AMOUNT_OUT_MIN => U256::zero(),
+            consts::ARG_PATH => path,
+            consts::ARG_TO => Key::Account(*consts::ACCOUNT_1_ADDR),
+            consts::ARG_DEAD_LINE => U256::MAX,
+        },
+    )
+    .with_block_time(4_000)
+    .build();
+
+    builder.exec(swap_request).expect_success().commit();
+
+    // Prices are accumulated from the reserves that were in place for the elapsed 3_000 ms.
+    let price0_cumulative_last: U256 = builder.get_value(test_context.pair_0_1_contract, consts::PRICE0_CUMULATIVE_LAST_KEY_NAME);
+    let price1_cumulative_last: U256 = builder.get_value(test_context.pair_0_1_contract, consts::PRICE1_CUMULATIVE_LAST_KEY_NAME);
+    let block_timestamp_last: u64 = builder.get_value(test_context.pair_0_1_contract, consts::BLOCK_TIMESTAMP_LAST_KEY_NAME);
+    assert_eq!(
+        price0_cumulative_last,
+        (U256::from(50_000u64) << 112) / U256::from(30_000u64) * U256::from(3_000u64)
+    );
+    assert_eq!(
+        price1_cumulative_last,
+        (U256::from(30_000u64) << 112) / U256::from(50_000u64) * U256::from(3_000u64)
+    );
+    assert_eq!(block_timestamp_last, 4_000u64);
+
+    let cumulative_prices = pair_check_cumulative_prices(&mut builder, test_context.pair_0_1_contract);
+    assert_eq!(
+        cumulative_prices,
+        (price0_cumulative_last, price1_cumulative_last, block_timestamp_last)
+    );
+}
+
+/// Installs the flash swap receiver, funds it with `token1` and asks it to repay `repay_amount` of
+/// `token1` for a 10_000 `token1` flash swap.
+fn make_flash_swap(
+    builder: &mut InMemoryWasmTestBuilder,
+    test_context: &TestContext,
+    repay_amount: U256,
+) -> (ExecuteRequest, ContractPackageHash) {
+    let install_request_receiver = ExecuteRequestBuilder::standard(
+        *DEFAULT_ACCOUNT_ADDR,
+        consts::CONTRACT_FLASH_SWAP_RECEIVER,
+        RuntimeArgs::default(),
+    )
+    .build();
+    builder.exec(install_request_receiver).expect_success().commit();
+
+    let receiver_package = builder
+        .get_account(*DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account")
+        .named_keys()
+        .get(consts::FLASH_SWAP_RECEIVER_KEY_NAME)
+        .and_then(|key| key.into_hash())
+        .map(ContractPackageHash::new)
+        .expect("should have contract package hash");
+
+    let receiver_funding_request = make_erc20_transfer_request(
+        Key::Account(*DEFAULT_ACCOUNT_ADDR),
+        &test_context.token1_contract,
+        Key::from(receiver_package),
+        U256::from(1_000u64),
+    );
+    builder.exec(receiver_funding_request).expect_success().commit();
+
+    let data = (
+        test_context.token1_contract,
+        Key::from(test_context.pair_0_1_package),
+        repay_amount,
+    )
+        .to_bytes()
+        .unwrap();
+
+    let flash_swap_request: ExecuteRequest = ExecuteRequestBuilder::versioned_contract_call_by_hash(
+        *consts::ACCOUNT_1_ADDR,
+        test_context.pair_0_1_package,
+        None,
+        consts::METHOD_SWAP,
+        runtime_args! {
+            consts::ARG_AMOUNT0 => U256::zero(),
+            consts::ARG_AMOUNT1 => U256::from(10_000u64),
+            consts::ARG_TO => Key::from(receiver_package),
+            consts::ARG_DATA => Bytes::from(data),
+        },
+    )
+    .build();
+
+    (flash_swap_request, receiver_package)
+}
+
+#[test]
+fn should_flash_swap_when_repaid_before_k_check() {
+    let (mut builder, test_context) = setup();
+
+    provide_initial_liquidity(&mut builder, &test_context, 0);
+
+    // 10_000 * 1000 / 998 rounded up covers the 0.2% fee on the borrowed amount.
+    let (flash_swap_request, receiver_package) =
+        make_flash_swap(&mut builder, &test_context, U256::from(10_021u64));
+    builder.exec(flash_swap_request).expect_success().commit();
+
+    let pair_balance: U256 = erc20_check_balance_of(&mut builder, &test_context.token1_contract, Key::from(test_context.pair_0_1_package));
+    assert_eq!(pair_balance, U256::from(50_021u64));
+    let receiver_balance: U256 = erc20_check_balance_of(&mut builder, &test_context.token1_contract, Key::from(receiver_package));
+    assert_eq!(receiver_balance, U256::from(979u64));
+}
+
+#[test]
+fn should_not_flash_swap_without_paying_fee() {
+    let (mut builder, test_context) = setup();
+
+    provide_initial_liquidity(&mut builder, &test_context, 0);
+
+    let (flash_swap_request, _) =
+        make_flash_swap(&mut builder, &test_context, U256::from(10_000u64));
+    builder.exec(flash_swap_request).commit();
+
+    let error = builder.get_error().expect("should have error");
+    assert!(
+        matches!(error, CoreError::Exec(ExecError::Revert(ApiError::User(user_error))) if user_error == consts::ERROR_K),
+        "{:?}",
+        error
+    );
+}
+
+#[test]
+fn should_skim_excess_balance_to_recipient() {
+    let (mut builder, test_context) = setup();
+    let test_context = in_pair_order(test_context);
+
+    provide_initial_liquidity(&mut builder, &test_context, 0);
+
+    let donation_request = make_erc20_transfer_request(
+        Key::Account(*DEFAULT_ACCOUNT_ADDR),
+        &test_context.token0_contract,
+        Key::from(test_context.pair_0_1_package),
+        U256::from(5_000u64),
+    );
+    builder.exec(donation_request).expect_success().commit();
+
+    let skim_request: ExecuteRequest = ExecuteRequestBuilder::versioned_contract_call_by_hash(
+        *consts::ACCOUNT_1_ADDR,
+        test_context.pair_0_1_package,
+        None,
+        consts::METHOD_SKIM,
+        runtime_args! {
+            consts::ARG_TO => Key::Account(*consts::ACCOUNT_1_ADDR),
+        },
+    )
+    .build();
+    builder.exec(skim_request).expect_success().commit();
+
+    let pair_balance: U256 = erc20_check_balance_of(&mut builder, &test_context.token0_contract, Key::from(test_context.pair_0_1_package));
+    assert_eq!(pair_balance, U256::from(30_000u64));
+    let token0_balance: U256 = erc20_check_balance_of(&mut builder, &test_context.token0_contract, Key::Account(*consts::ACCOUNT_1_ADDR));
+    assert_eq!(token0_balance, U256::from(75_000u64));
+    let reserve0: U256 = builder.get_value(test_context.pair_0_1_contract, consts::RESERVE0_KEY_NAME);
+    assert_eq!(reserve0, U256::from(30_000u64));
+}
+
+#[test]
+fn should_sync_reserves_to_balances() {
+    let (mut builder, test_context) = setup();
+    let test_context = in_pair_order(test_context);
+
+    provide_initial_liquidity(&mut builder, &test_context, 0);
+
+    let donation_request = make_erc20_transfer_request(
+        Key::Account(*DEFAULT_ACCOUNT_ADDR),
+        &test_context.token1_contract,
+        Key::from(test_context.pair_0_1_package),
+        U256::from(5_000u64),
+    );
+    builder.exec(donation_request).expect_success().commit();
+
+    let sync_request: ExecuteRequest = ExecuteRequestBuilder::versioned_contract_call_by_hash(
+        *consts::ACCOUNT_1_ADDR,
+        test_context.pair_0_1_package,
+        None,
+        consts::METHOD_SYNC,
+        RuntimeArgs::default(),
+    )
+    .build();
+    builder.exec(sync_request).expect_success().commit();
+
+    let reserve0: U256 = builder.get_value(test_context.pair_0_1_contract, consts::RESERVE0_KEY_NAME);
+    assert_eq!(reserve0, U256::from(30_000u64));
+    let reserve1: U256 = builder.get_value(test_context.pair_0_1_contract, consts::RESERVE1_KEY_NAME);
+    assert_eq!(reserve1, U256::from(55_000u64));
+}
+
+#[test]
+fn should_not_redirect_fee_when_minting_directly() {
+    let (mut builder, test_context) = setup();
+
+    provide_initial_liquidity(&mut builder, &test_context, 0);
+
+    let mut path: Vec<ContractHash> = Vec::new();
+    path.push(test_context.token0_contract);
+    path.push(test_context.token1_contract);
+
+    let swap_request: ExecuteRequest = ExecuteRequestBuilder::versioned_contract_call_by_hash(
+        *consts::ACCOUNT_1_ADDR,
+        test_context.router_package,
+        None,
+        consts::METHOD_SWAP_EXACT_TOKENS_FOR_TOKENS,
+        runtime_args! {
+            consts::ARG_AMOUNT_IN => U256::from(10_000u64),
+            consts::ARG_AMOUNT_OUT_MIN => U256::zero(),
+            consts::ARG_PATH => path,
+            consts::ARG_TO => Key::Account(*consts::ACCOUNT_1_ADDR),
+            consts::ARG_DEAD_LINE => U256::MAX,
+        },
+    )
+    .build();
+    builder.exec(swap_request).expect_success().commit();
+
+    let pair_key = Key::from(test_context.pair_0_1_package);
+    let token0_transfer_request = make_erc20_transfer_request(
+        Key::Account(*consts::ACCOUNT_1_ADDR),
+        &test_context.token0_contract,
+        pair_key,
+        U256::from(3_000u64),
+    );
+    let token1_transfer_request = make_erc20_transfer_request(
+        Key::Account(*consts::ACCOUNT_1_ADDR),
+        &test_context.token1_contract,
+        pair_key,
+        U256::from(3_000u64),
+    );
+    builder.exec(token0_transfer_request).expect_success().commit();
+    builder.exec(token1_transfer_request).expect_success().commit();
+
+    // A direct caller still passing `feeto` must not be able to claim the protocol fee.
+    let attacker_key = Key::from(AccountHash::new([11u8; 32]));
+    let pair_mint_request: ExecuteRequest = ExecuteRequestBuilder::versioned_contract_call_by_hash(
+        *consts::ACCOUNT_1_ADDR,
+        test_context.pair_0_1_package,
+        None,
+        consts::METHOD_MINT,
+        runtime_args! {
+            consts::ARG_TO => Key::Account(*consts::ACCOUNT_1_ADDR),
+            consts::ARG_FEETO => attacker_key,
+        },
+    )
+    .build();
+    builder.exec(pair_mint_request).expect_success().commit();
+
+    let attacker_balance: U256 = erc20_check_balance_of(&mut builder, &test_context.pair_0_1_contract, attacker_key);
+    assert_eq!(attacker_balance, U256::zero());
+    let fee_balance: U256 = erc20_check_balance_of(&mut builder, &test_context.pair_0_1_contract, Key::from(AccountHash::new([10u8; 32])));
+    assert_eq!(fee_balance, U256::from(2u64));
+}
+
+#[test]
+fn should_deploy_pair_from_router() {
+    let (builder, test_context) = setup();
+
+    let test_context = in_pair_order(test_context);
+    let hash_prefix = |token: ContractHash| -> String {
+        token.value()[..4].iter().map(|byte| format!("{:02x}", byte)).collect()
+    };
+    let tokens_tag = format!(
+        "{}-{}",
+        hash_prefix(test_context.token0_contract),
+        hash_prefix(test_context.token1_contract)
+    );
+
+    let pair_name: String = builder.get_value(test_context.pair_0_1_contract, consts::ARG_NAME);
+    let pair_symbol: String = builder.get_value(test_context.pair_0_1_contract, consts::ARG_SYMBOL);
+    let factory: Key = builder.get_value(test_context.pair_0_1_contract, consts::ARG_FACTORY);
+
+    assert_eq!(pair_name, format!("Swappery LP {}", tokens_tag));
+    assert_eq!(pair_symbol, "SLP");
+    assert_eq!(factory, Key::from(test_context.router_package));
+
+    // The pair lives in `pair_list` only, keeping the router's named keys independent of pairs.
+    let router_contract = get_router_contract(&builder, test_context.router_package);
+    let router_named_keys = builder
+        .get_contract(router_contract)
+        .expect("should have router contract")
+        .named_keys()
+        .clone();
+    assert!(
+        router_named_keys.keys().all(|name| !name.starts_with(consts::PAIR_KEY_NAME_PREFIX)),
+        "{:?}",
+        router_named_keys.keys().collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn should_not_create_duplicate_pair() {
+    let (mut builder, test_context) = setup();
+
+    // Reversed token order must still resolve to the existing pair.
+    let create_pair_request = ExecuteRequestBuilder::versioned_contract_call_by_hash(
+        *consts::ACCOUNT_1_ADDR,
+        test_context.router_package,
+        None,
+        consts::METHOD_CREATE_PAIR,
+        runtime_args! {
+            consts::ARG_TOKEN0 => test_context.token1_contract,
+            consts::ARG_TOKEN1 => test_context.token0_contract,
+        },
+    )
+    .build();
+
+    builder.exec(create_pair_request).commit();
+
+    let error = builder.get_error().expect("should have error");
+    assert!(
+        matches!(error, CoreError::Exec(ExecError::Revert(ApiError::User(user_error))) if user_error == consts::ERROR_PAIR_EXISTS),
+        "{:?}",
+        error
+    );
+}
+
+fn make_set_pair_swap_fee_request(
+    sender: AccountHash,
+    test_context: &TestContext,
+    swap_fee_bps: u32,
+) -> ExecuteRequest {
+    ExecuteRequestBuilder::versioned_contract_call_by_hash(
+        sender,
+        test_context.router_package,
+        None,
+        consts::METHOD_SET_PAIR_SWAP_FEE,
+        runtime_args! {
+            consts::ARG_TOKEN0 => test_context.token0_contract,
+            consts::ARG_TOKEN1 => test_context.token1_contract,
+            consts::ARG_SWAP_FEE_BPS => swap_fee_bps,
+        },
+    )
+    .build()
+}
+
+#[test]
+fn should_not_create_pair_of_identical_tokens() {
+    let (mut builder, test_context) = setup();
+
+    let create_pair_request = ExecuteRequestBuilder::versioned_contract_call_by_hash(
+        *DEFAULT_ACCOUNT_ADDR,
+        test_context.router_package,
+        None,
+        consts::METHOD_CREATE_PAIR,
+        runtime_args! {
+            consts::ARG_TOKEN0 => test_context.token0_contract,
+            consts::ARG_TOKEN1 => test_context.token0_contract,
+        },
+    )
+    .build();
+
+    builder.exec(create_pair_request).expect_failure();
+
+    let error = builder.get_error().expect("should have error");
+    assert!(
+        matches!(error, CoreError::Exec(ExecError::Revert(ApiError::User(user_error))) if user_error == consts::ERROR_IDENTICAL_ADDRESSES),
+        "{:?}",
+        error
+    );
+}
+
+#[test]
+fn should_swap_with_pair_swap_fee_set_through_router() {
+    let (mut builder, test_context) = setup();
+
+    let set_swap_fee_request = make_set_pair_swap_fee_request(*DEFAULT_ACCOUNT_ADDR, &test_context, 30);
+    builder.exec(set_swap_fee_request).expect_success().commit();
+
+    let swap_fee_bps: u32 = builder.get_value(test_context.pair_0_1_contract, consts::SWAP_FEE_BPS_KEY_NAME);
+    assert_eq!(swap_fee_bps, 30);
+    let pair_events: Vec<PairEvent> = events::events(&builder, test_context.pair_0_1_contract);
+    assert_eq!(pair_events, vec![PairEvent::SetSwapFee { swap_fee_bps: 30 }]);
+
+    provide_initial_liquidity(&mut builder, &test_context, 0);
+
+    let path: Vec<ContractHash> = vec![test_context.token0_contract, test_context.token1_contract];
+    let swap_request: ExecuteRequest = ExecuteRequestBuilder::versioned_contract_call_by_hash(
+        *consts::ACCOUNT_1_ADDR,
+        test_context.router_package,
+        None,
+        consts::METHOD_SWAP_EXACT_TOKENS_FOR_TOKENS,
+        runtime_args! {
+            consts::ARG_AMOUNT_IN => U256::from(10_000u64),
+            consts::ARG_AMOUNT_OUT_MIN => U256::zero(),
+            consts::ARG_PATH => path,
+            consts::ARG_TO => Key::Account(*consts::ACCOUNT_1_ADDR),
+            consts::ARG_DEAD_LINE => U256::MAX,
+        },
+    )
+    .build();
+
+    builder.exec(swap_request).expect_success().commit();
+
+    // 10_000 * 9_970 * 50_000 / (30_000 * 10_000 + 10_000 * 9_970) = 12_471
+    let token1_balance: U256 = erc20_check_balance_of(&mut builder, &test_context.token1_contract, Key::Account(*consts::ACCOUNT_1_ADDR));
+    assert_eq!(token1_balance, U256::from(62_471u64));
+}
+
+#[test]
+fn should_not_set_pair_swap_fee_without_permission() {
+    let (mut builder, test_context) = setup();
+
+    let set_swap_fee_request = make_set_pair_swap_fee_request(*consts::ACCOUNT_1_ADDR, &test_context, 30);
+    builder.exec(set_swap_fee_request).commit();
+
+    let error = builder.get_error().expect("should have error");
+    assert!(
+        matches!(error, CoreError::Exec(ExecError::Revert(ApiError::User(user_error))) if user_error == consts::ERROR_PERMISSION),
+        "{:?}",
+        error
+    );
+}
+
+#[test]
+fn should_not_set_pair_swap_fee_above_maximum() {
+    let (mut builder, test_context) = setup();
+
+    let set_swap_fee_request = make_set_pair_swap_fee_request(*DEFAULT_ACCOUNT_ADDR, &test_context, 101);
+    builder.exec(set_swap_fee_request).commit();
+
+    let error = builder.get_error().expect("should have error");
+    assert!(
+        matches!(error, CoreError::Exec(ExecError::Revert(ApiError::User(user_error))) if user_error == consts::ERROR_INVALID_SWAP_FEE),
+        "{:?}",
+        error
+    );
+}
+
+#[test]
+fn should_not_set_swap_fee_on_pair_directly() {
+    let (mut builder, test_context) = setup();
+
+    let set_swap_fee_request = ExecuteRequestBuilder::versioned_contract_call_by_hash(
+        *DEFAULT_ACCOUNT_ADDR,
+        test_context.pair_0_1_package,
+        None,
+        consts::METHOD_SET_SWAP_FEE,
+        runtime_args! {
+            consts::ARG_SWAP_FEE_BPS => 0u32,
+        },
+    )
+    .build();
+    builder.exec(set_swap_fee_request).commit();
+
+    let error = builder.get_error().expect("should have error");
+    assert!(
+        matches!(error, CoreError::Exec(ExecError::Revert(ApiError::User(user_error))) if user_error == consts::ERROR_FORBIDDEN),
+        "{:?}",
+        error
+    );
+}
+
+fn make_set_protocol_fee_share_request(
+    sender: AccountHash,
+    test_context: &TestContext,
+    protocol_fee_share_bps: u32,
+) -> ExecuteRequest {
+    ExecuteRequestBuilder::versioned_contract_call_by_hash(
+        sender,
+        test_context.router_package,
+        None,
+        consts::METHOD_SET_PROTOCOL_FEE_SHARE,
+        runtime_args! {
+            consts::ARG_PROTOCOL_FEE_SHARE_BPS => protocol_fee_share_bps,
+        },
+    )
+    .build()
+}
+
+/// Runs the `should_mint_fee_to_feeto_address` scenario under the given protocol fee share and
+/// returns the LP tokens minted to feeto.
+fn mint_fee_with_protocol_fee_share(protocol_fee_share_bps: u32) -> U256 {
+    let (mut builder, test_context) = setup();
+
+    let set_protocol_fee_share_request =
+        make_set_protocol_fee_share_request(*DEFAULT_ACCOUNT_ADDR, &test_context, protocol_fee_share_bps);
+    builder.exec(set_protocol_fee_share_request).expect_success().commit();
+
+    provide_initial_liquidity(&mut builder, &test_context, 0);
+
+    let path: Vec<ContractHash> = vec![test_context.token0_contract, test_context.token1_contract];
+    let swap_request: ExecuteRequest = ExecuteRequestBuilder::versioned_contract_call_by_hash(
+        *consts::ACCOUNT_1_ADDR,
+        test_context.router_package,
+        None,
+        consts::METHOD_SWAP_EXACT_TOKENS_FOR_TOKENS,
+        runtime_args! {
+            consts::ARG_AMOUNT_IN => U256::from(10_000u64),
+            consts::ARG_AMOUNT_OUT_MIN => U256::zero(),
+            consts::ARG_PATH => path,
+            consts::ARG_TO => Key::Account(*consts::ACCOUNT_1_ADDR),
+            consts::ARG_DEAD_LINE => U256::MAX,
+        },
+    )
+    .build();
+    builder.exec(swap_request).expect_success().commit();
+
+    let add_liquidity_request: ExecuteRequest = ExecuteRequestBuilder::versioned_contract_call_by_hash(
+        *consts::ACCOUNT_1_ADDR,
+        test_context.router_package,
+        None,
+        consts::METHOD_ADD_LIQUIDITY,
+        runtime_args! {
+            consts::ARG_TOKEN0 => test_context.token0_contract,
+            consts::ARG_TOKEN1 => test_context.token1_contract,
+            consts::ARG_AMOUNT0_DESIRED => U256::from(30_000u64),
+            consts::ARG_AMOUNT1_DESIRED => U256::from(30_000u64),
+            consts::ARG_AMOUNT0_MIN => U256::zero(),
+            consts::ARG_AMOUNT1_MIN => U256::zero(),
+            consts::ARG_TO => Key::Account(*consts::ACCOUNT_1_ADDR),
+            consts::ARG_DEAD_LINE => U256::MAX,
+        },
+    )
+    .build();
+    builder.exec(add_liquidity_request).expect_success().commit();
+
+    erc20_check_balance_of(&mut builder, &test_context.pair_0_1_contract, Key::from(AccountHash::new([10u8; 32])))
+}
+
+#[test]
+fn should_mint_fee_to_feeto_address_by_protocol_fee_share() {
+    // total supply 38_729, sqrt(k) grows from 38_729 to 38_739:
+    // 38_729 * 10 * share / (38_739 * (10_000 - share) + 38_729 * share)
+    assert_eq!(mint_fee_with_protocol_fee_share(0), U256::zero());
+    assert_eq!(mint_fee_with_protocol_fee_share(1_667), U256::from(1u64));
+    assert_eq!(mint_fee_with_protocol_fee_share(2_500), U256::from(2u64));
+    assert_eq!(mint_fee_with_protocol_fee_share(5_000), U256::from(4u64));
+}
+
+#[test]
+fn should_not_set_protocol_fee_share_without_permission() {
+    let (mut builder, test_context) = setup();
+
+    let set_protocol_fee_share_request =
+        make_set_protocol_fee_share_request(*consts::ACCOUNT_1_ADDR, &test_context, 1_000);
+    builder.exec(set_protocol_fee_share_request).commit();
+
+    let error = builder.get_error().expect("should have error");
+    assert!(
+        matches!(error, CoreError::Exec(ExecError::Revert(ApiError::User(user_error))) if user_error == consts::ERROR_PERMISSION),
+        "{:?}",
+        error
+    );
+}
+
+#[test]
+fn should_not_set_protocol_fee_share_above_maximum() {
+    let (mut builder, test_context) = setup();
+
+    let set_protocol_fee_share_request =
+        make_set_protocol_fee_share_request(*DEFAULT_ACCOUNT_ADDR, &test_context, 5_001);
+    builder.exec(set_protocol_fee_share_request).commit();
+
+    let error = builder.get_error().expect("should have error");
+    assert!(
+        matches!(error, CoreError::Exec(ExecError::Revert(ApiError::User(user_error))) if user_error == consts::ERROR_INVALID_PROTOCOL_FEE_SHARE),
+        "{:?}",
+        error
+    );
+}
+
+#[test]
+fn should_trade_with_near_max_reserves() {
+    // The largest reserves whose fee-adjusted K check still fits in U512.
+    let reserve = U256::MAX / 20_000;
+    let (mut builder, test_context) = setup_with_total_supplies(U256::MAX, U256::MAX);
+    let test_context = in_pair_order(test_context);
+
+    for token in [test_context.token0_contract, test_context.token1_contract] {
+        let approve_request = ExecuteRequestBuilder::contract_call_by_hash(
+            *DEFAULT_ACCOUNT_ADDR,
+            token,
+            consts::METHOD_APPROVE,
+            runtime_args! {
+                consts::ARG_OWNER => Key::Account(*DEFAULT_ACCOUNT_ADDR),
+                consts::ARG_SPENDER => Key::Hash(test_context.router_package.value()),
+                consts::ARG_AMOUNT => U256::MAX,
+            }
+        )
+        .build();
+        builder.exec(approve_request).expect_success().commit();
+    }
+
+    let add_liquidity_request: ExecuteRequest = ExecuteRequestBuilder::versioned_contract_call_by_hash(
+        *DEFAULT_ACCOUNT_ADDR,
+        test_context.router_package,
+        None,
+        consts::METHOD_ADD_LIQUIDITY,
+        runtime_args! {
+            consts::ARG_TOKEN0 => test_context.token0_contract,
+            consts::ARG_TOKEN1 => test_context.token1_contract,
+            consts::ARG_AMOUNT0_DESIRED => reserve,
+            consts::ARG_AMOUNT1_DESIRED => reserve,
+            consts::ARG_AMOUNT0_MIN => U256::zero(),
+            consts::ARG_AMOUNT1_MIN => U256::zero(),
+            consts::ARG_TO => Key::Account(*DEFAULT_ACCOUNT_ADDR),
+            consts::ARG_DEAD_LINE => U256::MAX,
+        },
+    )
+    .build();
+    builder.exec(add_liquidity_request).expect_success().commit();
+
+    let lp_balance: U256 = erc20_check_balance_of(&mut builder, &test_context.pair_0_1_contract, Key::Account(*DEFAULT_ACCOUNT_ADDR));
+    assert_eq!(lp_balance, reserve - 1_000u64);
+
+    let path: Vec<ContractHash> = vec![test_context.token0_contract, test_context.token1_contract];
+    let swap_request: ExecuteRequest = ExecuteRequestBuilder::versioned_contract_call_by_hash(
+        *DEFAULT_ACCOUNT_ADDR,
+        test_context.router_package,
+        None,
+        consts::METHOD_SWAP_EXACT_TOKENS_FOR_TOKENS,
+        runtime_args! {
+            consts::ARG_AMOUNT_IN => reserve / 10,
+            consts::ARG_AMOUNT_OUT_MIN => U256::zero(),
+            consts::ARG_PATH => path,
+            consts::ARG_TO => Key::Account(*DEFAULT_ACCOUNT_ADDR),
+            consts::ARG_DEAD_LINE => U256::MAX,
+        },
+    )
+    .build();
+    builder.exec(swap_request).expect_success().commit();
+
+    let reserve1: U256 = builder.get_value(test_context.pair_0_1_contract, consts::RESERVE1_KEY_NAME);
+    assert_eq!(
+        reserve - reserve1,
+        U256::from_dec_str("525370544911991103076576845965950773038568124642249876847511678668381993").unwrap()
+    );
+
+    let lp_approve_request = ExecuteRequestBuilder::contract_call_by_hash(
+        *DEFAULT_ACCOUNT_ADDR,
+        test_context.pair_0_1_contract,
+        consts::METHOD_APPROVE,
+        runtime_args! {
+            consts::ARG_OWNER => Key::Account(*DEFAULT_ACCOUNT_ADDR),
+            consts::ARG_SPENDER => Key::Hash(test_context.router_package.value()),
+            consts::ARG_AMOUNT => lp_balance,
+        }
+    )
+    .build();
+    builder.exec(lp_approve_request).expect_success().commit();
+
+    let remove_liquidity_request: ExecuteRequest = ExecuteRequestBuilder::versioned_contract_call_by_hash(
+        *DEFAULT_ACCOUNT_ADDR,
+        test_context.router_package,
+        None,
+        consts::METHOD_REMOVE_LIQUIDITY,
+        runtime_args! {
+            consts::ARG_TOKEN0 => test_context.token0_contract,
+            consts::ARG_TOKEN1 => test_context.token1_contract,
+            consts::ARG_LIQUIDITY => lp_balance,
+            consts::ARG_AMOUNT0_MIN => U256::zero(),
+            consts::ARG_AMOUNT1_MIN => U256::zero(),
+            consts::ARG_TO => Key::Account(*DEFAULT_ACCOUNT_ADDR),
+            consts::ARG_DEAD_LINE => U256::MAX,
+        },
+    )
+    .build();
+    builder.exec(remove_liquidity_request).expect_success().commit();
+
+    let fee_balance: U256 = erc20_check_balance_of(&mut builder, &test_context.pair_0_1_contract, Key::from(AccountHash::new([10u8; 32])));
+    assert_eq!(
+        fee_balance,
+        U256::from_dec_str("131590891966036452107156320620193378201843895735845715100758256551629").unwrap()
+    );
+
+    let token0_balance: U256 = erc20_check_balance_of(&mut builder, &test_context.token0_contract, Key::Account(*DEFAULT_ACCOUNT_ADDR));
+    assert_eq!(
+        token0_balance,
+        U256::from_dec_str("115792089092569504182778738754368726427725931829105157920020390106396339052286").unwrap()
+    );
+}
+
+
+/// Creates the token0/WCSPR pair and adds 30_000 token0 and 50_000 WCSPR of liquidity from the
+/// default account, returning the pair's contract package hash.
+fn provide_cspr_liquidity(
+    builder: &mut InMemoryWasmTestBuilder,
+    test_context: &TestContext,
+) -> ContractPackageHash {
+    let create_pair_request = ExecuteRequestBuilder::versioned_contract_call_by_hash(
+        *DEFAULT_ACCOUNT_ADDR,
+        test_context.router_package,
+        None,
+        consts::METHOD_CREATE_PAIR,
+        runtime_args! {
+            consts::ARG_TOKEN0 => test_context.token0_contract,
+            consts::ARG_TOKEN1 => test_context.wcspr_contract,
+        },
+    )
+    .build();
+    builder.exec(create_pair_request).expect_success().commit();
+
+    for token in [test_context.token0_contract, test_context.wcspr_contract] {
+        let approve_request = ExecuteRequestBuilder::contract_call_by_hash(
+            *DEFAULT_ACCOUNT_ADDR,
+            token,
+            consts::METHOD_APPROVE,
+            runtime_args! {
+                consts::ARG_OWNER => Key::Account(*DEFAULT_ACCOUNT_ADDR),
+                consts::ARG_SPENDER => Key::Hash(test_context.router_package.value()),
+                consts::ARG_AMOUNT => U256::from(100_000u64),
+            }
+        )
+        .build();
+        builder.exec(approve_request).expect_success().commit();
+    }
+
+    let add_liquidity_request = ExecuteRequestBuilder::versioned_contract_call_by_hash(
+        *DEFAULT_ACCOUNT_ADDR,
+        test_context.router_package,
+        None,
+        consts::METHOD_ADD_LIQUIDITY,
+        runtime_args! {
+            consts::ARG_TOKEN0 => test_context.token0_contract,
+            consts::ARG_TOKEN1 => test_context.wcspr_contract,
+            consts::ARG_AMOUNT0_DESIRED => U256::from(30_000u64),
+            consts::ARG_AMOUNT1_DESIRED => U256::from(50_000u64),
+            consts::ARG_AMOUNT0_MIN => U256::zero(),
+            consts::ARG_AMOUNT1_MIN => U256::zero(),
+            consts::ARG_TO => Key::Account(*DEFAULT_ACCOUNT_ADDR),
+            consts::ARG_DEAD_LINE => U256::MAX,
+        },
+    )
+    .build();
+    builder.exec(add_liquidity_request).expect_success().commit();
+
+    get_pair_hashes(builder, test_context.router_package, test_context.token0_contract, test_context.wcspr_contract).0
+}
+
+/// Runs the CSPR session wasm, which funds a purse from `sender`'s main purse and forwards `args`
+/// to `entry_point` on the router.
+fn make_cspr_session_request(
+    sender: AccountHash,
+    test_context: &TestContext,
+    entry_point: &str,
+    mut args: RuntimeArgs,
+) -> ExecuteRequest {
+    args.insert(consts::ARG_ROUTER, Key::Hash(test_context.router_package.value())).unwrap();
+    args.insert(consts::ARG_ENTRY_POINT, String::from(entry_point)).unwrap();
+    ExecuteRequestBuilder::standard(sender, consts::CONTRACT_CSPR_SESSION, args).build()
+}
+
+/// Swaps 10_000 motes of `ACCOUNT_1` for token0 through the session wasm.
+fn swap_exact_cspr_for_token0(builder: &mut InMemoryWasmTestBuilder, test_context: &TestContext) {
+    let swap_request = make_cspr_session_request(
+        *consts::ACCOUNT_1_ADDR,
+        test_context,
+        consts::METHOD_SWAP_EXACT_CSPR_FOR_TOKENS,
+        runtime_args! {
+            consts::ARG_AMOUNT_IN => U256::from(10_000u64),
+            consts::ARG_AMOUNT_OUT_MIN => U256::zero(),
+            consts::ARG_PATH => vec![test_context.wcspr_contract, test_context.token0_contract],
+            consts::ARG_TO => Key::Account(*consts::ACCOUNT_1_ADDR),
+            consts::ARG_DEAD_LINE => U256::MAX,
+        },
+    );
+    builder.exec(swap_request).expect_success().commit();
+}
+
+fn get_main_purse_balance(builder: &InMemoryWasmTestBuilder, account: AccountHash) -> U512 {
+    let main_purse = builder
+        .get_account(account)
+        .expect("should have account")
+        .main_purse();
+    builder.get_purse_balance(main_purse)
+}
+
+#[test]
+fn should_swap_exact_cspr_for_tokens() {
+    let (mut builder, test_context) = setup();
+    let pair_package = provide_cspr_liquidity(&mut builder, &test_context);
+
+    swap_exact_cspr_for_token0(&mut builder, &test_context);
+
+    let token0_balance: U256 = erc20_check_balance_of(&mut builder, &test_context.token0_contract, Key::Account(*consts::ACCOUNT_1_ADDR));
+    assert_eq!(token0_balance, U256::from(4_991u64));
+    let pair_wcspr_balance: U256 = erc20_check_balance_of(&mut builder, &test_context.wcspr_contract, Key::Hash(pair_package.value()));
+    assert_eq!(pair_wcspr_balance, U256::from(60_000u64));
+}
+
+#[test]
+fn should_swap_cspr_for_exact_tokens() {
+    let (mut builder, test_context) = setup();
+    let pair_package = provide_cspr_liquidity(&mut builder, &test_context);
+
+    // Only the 7_708 motes actually needed are wrapped; the rest goes back to the main purse.
+    let swap_request = make_cspr_session_request(
+        *consts::ACCOUNT_1_ADDR,
+        &test_context,
+        consts::METHOD_SWAP_CSPR_FOR_EXACT_TOKENS,
+        runtime_args! {
+            consts::ARG_AMOUNT_OUT => U256::from(4_000u64),
+            consts::ARG_AMOUNT_IN_MAX => U256::from(20_000u64),
+            consts::ARG_PATH => vec![test_context.wcspr_contract, test_context.token0_contract],
+            consts::ARG_TO => Key::Account(*consts::ACCOUNT_1_ADDR),
+            consts::ARG_DEAD_LINE => U256::MAX,
+        },
+    );
+    builder.exec(swap_request).expect_success().commit();
+
+    let token0_balance: U256 = erc20_check_balance_of(&mut builder, &test_context.token0_contract, Key::Account(*consts::ACCOUNT_1_ADDR));
+    assert_eq!(token0_balance, U256::from(4_000u64));
+    let pair_wcspr_balance: U256 = erc20_check_balance_of(&mut builder, &test_context.wcspr_contract, Key::Hash(pair_package.value()));
+    assert_eq!(pair_wcspr_balance, U256::from(57_708u64));
+}
+
+#[test]
+fn should_not_swap_cspr_when_path_does_not_start_with_wcspr() {
+    let (mut builder, test_context) = setup();
+    provide_cspr_liquidity(&mut builder, &test_context);
+
+    let swap_request = make_cspr_session_request(
+        *consts::ACCOUNT_1_ADDR,
+        &test_context,
+        consts::METHOD_SWAP_EXACT_CSPR_FOR_TOKENS,
+        runtime_args! {
+            consts::ARG_AMOUNT_IN => U256::from(10_000u64),
+            consts::ARG_AMOUNT_OUT_MIN => U256::zero(),
+            consts::ARG_PATH => vec![test_context.token0_contract, test_context.wcspr_contract],
+            consts::ARG_TO => Key::Account(*consts::ACCOUNT_1_ADDR),
+            consts::ARG_DEAD_LINE => U256::MAX,
+        },
+    );
+    builder.exec(swap_request).commit();
+
+    let error = builder.get_error().expect("should have error");
+    assert!(
+        matches!(error, CoreError::Exec(ExecError::Revert(ApiError::User(user_error))) if user_error == consts::ERROR_INVALID_PATH),
+        "{:?}",
+        error
+    );
+}
+
+/// Sells the token0 bought with CSPR back for CSPR paid to the default account, so the WCSPR
+/// being unwrapped is backed by motes the swap deposited.
+#[test]
+fn should_swap_exact_tokens_for_cspr() {
+    let (mut builder, test_context) = setup();
+    provide_cspr_liquidity(&mut builder, &test_context);
+    swap_exact_cspr_for_token0(&mut builder, &test_context);
+
+    let approve_request = ExecuteRequestBuilder::contract_call_by_hash(
+        *consts::ACCOUNT_1_ADDR,
+        test_context.token0_contract,
+        consts::METHOD_APPROVE,
+        runtime_args! {
+            consts::ARG_OWNER => Key::Account(*consts::ACCOUNT_1_ADDR),
+            consts::ARG_SPENDER => Key::Hash(test_context.router_package.value()),
+            consts::ARG_AMOUNT => U256::from(4_991u64),
+        }
+    )
+    .build();
+    builder.exec(approve_request).expect_success().commit();
+
+    let balance_before = get_main_purse_balance(&builder, *DEFAULT_ACCOUNT_ADDR);
+    let swap_request = ExecuteRequestBuilder::versioned_contract_call_by_hash(
+        *consts::ACCOUNT_1_ADDR,
+        test_context.router_package,
+        None,
+        consts::METHOD_SWAP_EXACT_TOKENS_FOR_CSPR,
+        runtime_args! {
+            consts::ARG_AMOUNT_IN => U256::from(4_991u64),
+            consts::ARG_AMOUNT_OUT_MIN => U256::zero(),
+            consts::ARG_PATH => vec![test_context.token0_contract, test_context.wcspr_contract],
+            consts::ARG_TO => Key::Account(*DEFAULT_ACCOUNT_ADDR),
+            consts::ARG_DEAD_LINE => U256::MAX,
+        },
+    )
+    .build();
+    builder.exec(swap_request).expect_success().commit();
+
+    let balance_after = get_main_purse_balance(&builder, *DEFAULT_ACCOUNT_ADDR);
+    assert_eq!(balance_after - balance_before, U512::from(9_965u64));
+    let token0_balance: U256 = erc20_check_balance_of(&mut builder, &test_context.token0_contract, Key::Account(*consts::ACCOUNT_1_ADDR));
+    assert_eq!(token0_balance, U256::zero());
+}
+
+fn assert_invalid_recipient(builder: &InMemoryWasmTestBuilder) {
+    let error = builder.get_error().expect("should have error");
+    assert!(
+        matches!(error, CoreError::Exec(ExecError::Revert(ApiError::User(user_error))) if user_error == consts::ERROR_INVALID_RECIPIENT),
+        "{:?}",
+        error
+    );
+}
+
+/// Contract packages have no main purse, so CSPR outputs to them revert before any transfer.
+#[test]
+fn should_not_swap_exact_tokens_for_cspr_to_contract() {
+    let (mut builder, test_context) = setup();
+    provide_cspr_liquidity(&mut builder, &test_context);
+    swap_exact_cspr_for_token0(&mut builder, &test_context);
+
+    let swap_request = ExecuteRequestBuilder::versioned_contract_call_by_hash(
+        *consts::ACCOUNT_1_ADDR,
+        test_context.router_package,
+        None,
+        consts::METHOD_SWAP_EXACT_TOKENS_FOR_CSPR,
+        runtime_args! {
+            consts::ARG_AMOUNT_IN => U256::from(4_991u64),
+            consts::ARG_AMOUNT_OUT_MIN => U256::zero(),
+            consts::ARG_PATH => vec![test_context.token0_contract, test_context.wcspr_contract],
+            consts::ARG_TO => Key::Hash(test_context.pair_0_1_package.value()),
+            consts::ARG_DEAD_LINE => U256::MAX,
+        },
+    )
+    .build();
+    builder.exec(swap_request).commit();
+
+    assert_invalid_recipient(&builder);
+}
+
+#[test]
+fn should_swap_tokens_for_exact_cspr() {
+    let (mut builder, test_context) = setup();
+    provide_cspr_liquidity(&mut builder, &test_context);
+    swap_exact_cspr_for_token0(&mut builder, &test_context);
+
+    let approve_request = ExecuteRequestBuilder::contract_call_by_hash(
+        *consts::ACCOUNT_1_ADDR,
+        test_context.token0_contract,
+        consts::METHOD_APPROVE,
+        runtime_args! {
+            consts::ARG_OWNER => Key::Account(*consts::ACCOUNT_1_ADDR),
+            consts::ARG_SPENDER => Key::Hash(test_context.router_package.value()),
+            consts::ARG_AMOUNT => U256::from(4_991u64),
+        }
+    )
+    .build();
+    builder.exec(approve_request).expect_success().commit();
+
+    let balance_before = get_main_purse_balance(&builder, *DEFAULT_ACCOUNT_ADDR);
+    let swap_request = ExecuteRequestBuilder::versioned_contract_call_by_hash(
+        *consts::ACCOUNT_1_ADDR,
+        test_context.router_package,
+        None,
+        consts::METHOD_SWAP_TOKENS_FOR_EXACT_CSPR,
+        runtime_args! {
+            consts::ARG_AMOUNT_OUT => U256::from(5_000u64),
+            consts::ARG_AMOUNT_IN_MAX => U256::from(4_991u64),
+            consts::ARG_PATH => vec![test_context.token0_contract, test_context.wcspr_contract],
+            consts::ARG_TO => Key::Account(*DEFAULT_ACCOUNT_ADDR),
+            consts::ARG_DEAD_LINE => U256::MAX,
+        },
+    )
+    .build();
+    builder.exec(swap_request).expect_success().commit();
+
+    let balance_after = get_main_purse_balance(&builder, *DEFAULT_ACCOUNT_ADDR);
+    assert_eq!(balance_after - balance_before, U512::from(5_000u64));
+    let token0_balance: U256 = erc20_check_balance_of(&mut builder, &test_context.token0_contract, Key::Account(*consts::ACCOUNT_1_ADDR));
+    assert_eq!(token0_balance, U256::from(2_712u64));
+}
+
+/// Creates the token0/WCSPR pair and funds `ACCOUNT_1` with token0 approved to the router.
+fn setup_cspr_pair(
+    builder: &mut InMemoryWasmTestBuilder,
+    test_context: &TestContext,
+) -> (ContractPackageHash, ContractHash) {
+    let create_pair_request = ExecuteRequestBuilder::versioned_contract_call_by_hash(
+        *DEFAULT_ACCOUNT_ADDR,
+        test_context.router_package,
+        None,
+        consts::METHOD_CREATE_PAIR,
+        runtime_args! {
+            consts::ARG_TOKEN0 => test_context.token0_contract,
+            consts::ARG_TOKEN1 => test_context.wcspr_contract,
+        },
+    )
+    .build();
+    builder.exec(create_pair_request).expect_success().commit();
+
+    let token0_transfer_request = make_erc20_transfer_request(
+        Key::Account(*DEFAULT_ACCOUNT_ADDR),
+        &test_context.token0_contract,
+        Key::Account(*consts::ACCOUNT_1_ADDR),
+        U256::from(100_000u64),
+    );
+    builder.exec(token0_transfer_request).expect_success().commit();
+
+    let approve_request = ExecuteRequestBuilder::contract_call_by_hash(
+        *consts::ACCOUNT_1_ADDR,
+        test_context.token0_contract,
+        consts::METHOD_APPROVE,
+        runtime_args! {
+            consts::ARG_OWNER => Key::Account(*consts::ACCOUNT_1_ADDR),
+            consts::ARG_SPENDER => Key::Hash(test_context.router_package.value()),
+            consts::ARG_AMOUNT => U256::from(100_000u64),
+        }
+    )
+    .build();
+    builder.exec(approve_request).expect_success().commit();
+
+    get_pair_hashes(builder, test_context.router_package, test_context.token0_contract, test_context.wcspr_contract)
+}
+
+fn add_liquidity_cspr(
+    builder: &mut InMemoryWasmTestBuilder,
+    test_context: &TestContext,
+    amount_token_desired: u64,
+    amount_cspr_desired: u64,
+) {
+    let add_liquidity_request = make_cspr_session_request(
+        *consts::ACCOUNT_1_ADDR,
+        test_context,
+        consts::METHOD_ADD_LIQUIDITY_CSPR,
+        runtime_args! {
+            consts::ARG_TOKEN => test_context.token0_contract,
+            consts::ARG_AMOUNT_TOKEN_DESIRED => U256::from(amount_token_desired),
+            consts::ARG_AMOUNT_CSPR_DESIRED => U256::from(amount_cspr_desired),
+            consts::ARG_AMOUNT_TOKEN_MIN => U256::zero(),
+            consts::ARG_AMOUNT_CSPR_MIN => U256::zero(),
+            consts::ARG_TO => Key::Account(*consts::ACCOUNT_1_ADDR),
+            consts::ARG_DEAD_LINE => U256::MAX,
+        },
+    );
+    builder.exec(add_liquidity_request).expect_success().commit();
+}
+
+#[test]
+fn should_add_liquidity_cspr() {
+    let (mut builder, test_context) = setup();
+    let (pair_package, pair_contract) = setup_cspr_pair(&mut builder, &test_context);
+
+    add_liquidity_cspr(&mut builder, &test_context, 30_000, 50_000);
+
+    let lp_balance: U256 = erc20_check_balance_of(&mut builder, &pair_contract, Key::Account(*consts::ACCOUNT_1_ADDR));
+    assert_eq!(lp_balance, U256::from(37_729u64));
+    let pair_wcspr_balance: U256 = erc20_check_balance_of(&mut builder, &test_context.wcspr_contract, Key::Hash(pair_package.value()));
+    assert_eq!(pair_wcspr_balance, U256::from(50_000u64));
+
+    // At a 3:5 price only 5_000 of the 10_000 motes are wrapped; the rest is refunded.
+    add_liquidity_cspr(&mut builder, &test_context, 3_000, 10_000);
+
+    let lp_balance: U256 = erc20_check_balance_of(&mut builder, &pair_contract, Key::Account(*consts::ACCOUNT_1_ADDR));
+    assert_eq!(lp_balance, U256::from(41_601u64));
+    let pair_wcspr_balance: U256 = erc20_check_balance_of(&mut builder, &test_context.wcspr_contract, Key::Hash(pair_package.value()));
+    assert_eq!(pair_wcspr_balance, U256::from(55_000u64));
+}
+
+#[test]
+fn should_remove_liquidity_cspr() {
+    let (mut builder, test_context) = setup();
+    let (_, pair_contract) = setup_cspr_pair(&mut builder, &test_context);
+    add_liquidity_cspr(&mut builder, &test_context, 30_000, 50_000);
+
+    let approve_request = ExecuteRequestBuilder::contract_call_by_hash(
+        *consts::ACCOUNT_1_ADDR,
+        pair_contract,
+        consts::METHOD_APPROVE,
+        runtime_args! {
+            consts::ARG_OWNER => Key::Account(*consts::ACCOUNT_1_ADDR),
+            consts::ARG_SPENDER => Key::Hash(test_context.router_package.value()),
+            consts::ARG_AMOUNT => U256::from(37_729u64),
+        }
+    )
+    .build();
+    builder.exec(approve_request).expect_success().commit();
+
+    let token0_balance_before: U256 = erc20_check_balance_of(&mut builder, &test_context.token0_contract, Key::Account(*DEFAULT_ACCOUNT_ADDR));
+    let cspr_balance_before = get_main_purse_balance(&builder, *DEFAULT_ACCOUNT_ADDR);
+
+    let remove_liquidity_request = ExecuteRequestBuilder::versioned_contract_call_by_hash(
+        *consts::ACCOUNT_1_ADDR,
+        test_context.router_package,
+        None,
+        consts::METHOD_REMOVE_LIQUIDITY_CSPR,
+        runtime_args! {
+            consts::ARG_TOKEN => test_context.token0_contract,
+            consts::ARG_LIQUIDITY => U256::from(37_729u64),
+            consts::ARG_AMOUNT_TOKEN_MIN => U256::zero(),
+            consts::ARG_AMOUNT_CSPR_MIN => U256::zero(),
+            consts::ARG_TO => Key::Account(*DEFAULT_ACCOUNT_ADDR),
+            consts::ARG_DEAD_LINE => U256::MAX,
+        },
+    )
+    .build();
+    builder.exec(remove_liquidity_request).expect_success().commit();
+
+    // Read the purse before any balance check, which the default account pays gas for.
+    let cspr_balance_after = get_main_purse_balance(&builder, *DEFAULT_ACCOUNT_ADDR);
+    assert_eq!(cspr_balance_after - cspr_balance_before, U512::from(48_708u64));
+    let token0_balance_after: U256 = erc20_check_balance_of(&mut builder, &test_context.token0_contract, Key::Account(*DEFAULT_ACCOUNT_ADDR));
+    assert_eq!(token0_balance_after - token0_balance_before, U256::from(29_225u64));
+    let router_wcspr_balance: U256 = erc20_check_balance_of(&mut builder, &test_context.wcspr_contract, Key::Hash(test_context.router_package.value()));
+    assert_eq!(router_wcspr_balance, U256::zero());
+}
+
+#[test]
+fn should_not_remove_liquidity_cspr_to_contract() {
+    let (mut builder, test_context) = setup();
+    setup_cspr_pair(&mut builder, &test_context);
+    add_liquidity_cspr(&mut builder, &test_context, 30_000, 50_000);
+
+    let remove_liquidity_request = ExecuteRequestBuilder::versioned_contract_call_by_hash(
+        *consts::ACCOUNT_1_ADDR,
+        test_context.router_package,
+        None,
+        consts::METHOD_REMOVE_LIQUIDITY_CSPR,
+        runtime_args! {
+            consts::ARG_TOKEN => test_context.token0_contract,
+            consts::ARG_LIQUIDITY => U256::from(37_729u64),
+            consts::ARG_AMOUNT_TOKEN_MIN => U256::zero(),
+            consts::ARG_AMOUNT_CSPR_MIN => U256::zero(),
+            consts::ARG_TO => Key::Hash(test_context.pair_0_1_package.value()),
+            consts::ARG_DEAD_LINE => U256::MAX,
+        },
+    )
+    .build();
+    builder.exec(remove_liquidity_request).commit();
+
+    assert_invalid_recipient(&builder);
+}
+
+/// Block time the expiry tests execute at, one past their `dead_line`.
+const EXPIRED_BLOCK_TIME: u64 = 1_001;
+
+fn assert_expired(builder: &InMemoryWasmTestBuilder) {
+    let error = builder.get_error().expect("should have error");
+    assert!(
+        matches!(error, CoreError::Exec(ExecError::Revert(ApiError::User(user_error))) if user_error == consts::ERROR_EXPIRED),
+        "{:?}",
+        error
+    );
+}
+
+#[test]
+fn should_not_add_liquidity_after_dead_line() {
+    let (mut builder, test_context) = setup();
+
+    let add_liquidity_request = ExecuteRequestBuilder::versioned_contract_call_by_hash(
+        *DEFAULT_ACCOUNT_ADDR,
+        test_context.router_package,
+        None,
+        consts::METHOD_ADD_LIQUIDITY,
+        runtime_args! {
+            consts::ARG_TOKEN0 => test_context.token0_contract,
+            consts::ARG_TOKEN1 => test_context.token1_contract,
+            consts::ARG_AMOUNT0_DESIRED => U256::from(30_000u64),
+            consts::ARG_AMOUNT1_DESIRED => U256::from(50_000u64),
+            consts::ARG_AMOUNT0_MIN => U256::zero(),
+            consts::ARG_AMOUNT1_MIN => U256::zero(),
+            consts::ARG_TO => Key::Account(*DEFAULT_ACCOUNT_ADDR),
+            consts::ARG_DEAD_LINE => U256::from(EXPIRED_BLOCK_TIME - 1),
+        },
+    )
+    .with_block_time(EXPIRED_BLOCK_TIME)
+    .build();
+    builder.exec(add_liquidity_request).commit();
+
+    assert_expired(&builder);
+}
+
+#[test]
+fn should_not_remove_liquidity_after_dead_line() {
+    let (mut builder, test_context) = setup();
+    provide_initial_liquidity(&mut builder, &test_context, 0);
+
+    let remove_liquidity_request = ExecuteRequestBuilder::versioned_contract_call_by_hash(
+        *consts::ACCOUNT_1_ADDR,
+        test_context.router_package,
+        None,
+        consts::METHOD_REMOVE_LIQUIDITY,
+        runtime_args! {
+            consts::ARG_TOKEN0 => test_context.token0_contract,
+            consts::ARG_TOKEN1 => test_context.token1_contract,
+            consts::ARG_LIQUIDITY => U256::from(1_000u64),
+            consts::ARG_AMOUNT0_MIN => U256::zero(),
+            consts::ARG_AMOUNT1_MIN => U256::zero(),
+            consts::ARG_TO => Key::Account(*consts::ACCOUNT_1_ADDR),
+            consts::ARG_DEAD_LINE => U256::from(EXPIRED_BLOCK_TIME - 1),
+        },
+    )
+    .with_block_time(EXPIRED_BLOCK_TIME)
+    .build();
+    builder.exec(remove_liquidity_request).commit();
+
+    assert_expired(&builder);
+}
+
+#[test]
+fn should_not_swap_after_dead_line() {
+    let (mut builder, test_context) = setup();
+    provide_initial_liquidity(&mut builder, &test_context, 0);
+
+    let path = vec![test_context.token0_contract, test_context.token1_contract];
+    let swaps = [
+        (consts::METHOD_SWAP_EXACT_TOKENS_FOR_TOKENS, consts::ARG_AMOUNT_IN, consts::ARG_AMOUNT_OUT_MIN, 1_000u64, 0u64),
+        (consts::METHOD_SWAP_TOKENS_FOR_EXACT_TOKENS, consts::ARG_AMOUNT_OUT, consts::ARG_AMOUNT_IN_MAX, 1_000, 10_000),
+        (consts::METHOD_SWAP_EXACT_TOKENS_FOR_TOKENS_SUPPORTING_FEE, consts::ARG_AMOUNT_IN, consts::ARG_AMOUNT_OUT_MIN, 1_000, 0),
+    ];
+    for (method, amount_arg, limit_arg, amount, limit) in swaps {
+        let swap_request = ExecuteRequestBuilder::versioned_contract_call_by_hash(
+            *consts::ACCOUNT_1_ADDR,
+            test_context.router_package,
+            None,
+            method,
+            runtime_args! {
+                amount_arg => U256::from(amount),
+                limit_arg => U256::from(limit),
+                consts::ARG_PATH => path.clone(),
+                consts::ARG_TO => Key::Account(*consts::ACCOUNT_1_ADDR),
+                consts::ARG_DEAD_LINE => U256::from(EXPIRED_BLOCK_TIME - 1),
+            },
+        )
+        .with_block_time(EXPIRED_BLOCK_TIME)
+        .build();
+        builder.exec(swap_request).commit();
+
+        assert_expired(&builder);
+    }
+}
+
+#[test]
+fn should_swap_to_contract_recipient() {
+    let (mut builder, test_context) = setup();
+    provide_initial_liquidity(&mut builder, &test_context, 0);
+
+    // Vaults and aggregators receive output as contract packages.
+    let vault = Key::Hash([7u8; 32]);
+    let swap_request = ExecuteRequestBuilder::versioned_contract_call_by_hash(
+        *consts::ACCOUNT_1_ADDR,
+        test_context.router_package,
+        None,
+        consts::METHOD_SWAP_EXACT_TOKENS_FOR_TOKENS,
+        runtime_args! {
+            consts::ARG_AMOUNT_IN => U256::from(10_000u64),
+            consts::ARG_AMOUNT_OUT_MIN => U256::zero(),
+            consts::ARG_PATH => vec![test_context.token0_contract, test_context.token1_contract],
+            consts::ARG_TO => vault,
+            consts::ARG_DEAD_LINE => U256::MAX,
+        },
+    )
+    .build();
+    builder.exec(swap_request).expect_success().commit();
+
+    let vault_balance: U256 = erc20_check_balance_of(&mut builder, &test_context.token1_contract, vault);
+    assert_eq!(vault_balance, U256::from(12_481u64));
+}
+
+#[test]
+fn should_add_liquidity_to_contract_recipient() {
+    let (mut builder, test_context) = setup();
+    provide_initial_liquidity(&mut builder, &test_context, 0);
+
+    let vault = Key::Hash([7u8; 32]);
+    let add_liquidity_request = ExecuteRequestBuilder::versioned_contract_call_by_hash(
+        *consts::ACCOUNT_1_ADDR,
+        test_context.router_package,
+        None,
+        consts::METHOD_ADD_LIQUIDITY,
+        runtime_args! {
+            consts::ARG_TOKEN0 => test_context.token0_contract,
+            consts::ARG_TOKEN1 => test_context.token1_contract,
+            consts::ARG_AMOUNT0_DESIRED => U256::from(3_000u64),
+            consts::ARG_AMOUNT1_DESIRED => U256::from(5_000u64),
+            consts::ARG_AMOUNT0_MIN => U256::zero(),
+            consts::ARG_AMOUNT1_MIN => U256::zero(),
+            consts::ARG_TO => vault,
+            consts::ARG_DEAD_LINE => U256::MAX,
+        },
+    )
+    .build();
+    builder.exec(add_liquidity_request).expect_success().commit();
+
+    let lp_balance: U256 = erc20_check_balance_of(&mut builder, &test_context.pair_0_1_contract, vault);
+    assert_eq!(lp_balance, U256::from(3_872u64));
+}
+
+#[test]
+fn should_quote_amounts_out_as_swapped() {
+    let (mut builder, test_context) = setup();
+    provide_initial_liquidity(&mut builder, &test_context, 0);
+
+    let path = vec![test_context.token0_contract, test_context.token1_contract];
+    let amounts = router_check_amounts_out(&mut builder, test_context.router_package, U256::from(10_000u64), path.clone());
+    assert_eq!(amounts, vec![U256::from(10_000u64), U256::from(12_481u64)]);
+
+    let swap_request = ExecuteRequestBuilder::versioned_contract_call_by_hash(
+        *consts::ACCOUNT_1_ADDR,
+        test_context.router_package,
+        None,
+        consts::METHOD_SWAP_EXACT_TOKENS_FOR_TOKENS,
+        runtime_args! {
+            consts::ARG_AMOUNT_IN => amounts[0],
+            consts::ARG_AMOUNT_OUT_MIN => amounts[1],
+            consts::ARG_PATH => path,
+            consts::ARG_TO => Key::Account(*consts::ACCOUNT_1_ADDR),
+            consts::ARG_DEAD_LINE => U256::MAX,
+        },
+    )
+    .build();
+    builder.exec(swap_request).expect_success().commit();
+
+    let token1_balance: U256 = erc20_check_balance_of(&mut builder, &test_context.token1_contract, Key::Account(*consts::ACCOUNT_1_ADDR));
+    assert_eq!(token1_balance, U256::from(50_000u64) + amounts[1]);
+}
+
+#[test]
+fn should_quote_amounts_in_as_swapped() {
+    let (mut builder, test_context) = setup();
+    provide_initial_liquidity(&mut builder, &test_context, 0);
+
+    let path = vec![test_context.token0_contract, test_context.token1_contract];
+    let amounts = router_check_amounts_in(&mut builder, test_context.router_package, U256::from(5_000u64), path.clone());
+    assert_eq!(amounts, vec![U256::from(3_341u64), U256::from(5_000u64)]);
+
+    let swap_request = ExecuteRequestBuilder::versioned_contract_call_by_hash(
+        *consts::ACCOUNT_1_ADDR,
+        test_context.router_package,
+        None,
+        consts::METHOD_SWAP_TOKENS_FOR_EXACT_TOKENS,
+        runtime_args! {
+            consts::ARG_AMOUNT_OUT => amounts[1],
+            consts::ARG_AMOUNT_IN_MAX => amounts[0],
+            consts::ARG_PATH => path,
+            consts::ARG_TO => Key::Account(*consts::ACCOUNT_1_ADDR),
+            consts::ARG_DEAD_LINE => U256::MAX,
+        },
+    )
+    .build();
+    builder.exec(swap_request).expect_success().commit();
+
+    let token0_balance: U256 = erc20_check_balance_of(&mut builder, &test_context.token0_contract, Key::Account(*consts::ACCOUNT_1_ADDR));
+    assert_eq!(token0_balance, U256::from(70_000u64) - amounts[0]);
+}
+
+#[test]
+fn should_quote_at_reserve_ratio() {
+    let (mut builder, test_context) = setup();
+
+    let amount_b = router_check_quote(
+        &mut builder,
+        test_context.router_package,
+        U256::from(3_000u64),
+        U256::from(30_000u64),
+        U256::from(50_000u64),
+    );
+    assert_eq!(amount_b, U256::from(5_000u64));
+}
+
+/// Installs a token burning 1% of every transfer, with its whole supply held by the default
+/// account.
+fn install_fee_on_transfer_token(builder: &mut InMemoryWasmTestBuilder) -> ContractHash {
+    let install_request = ExecuteRequestBuilder::standard(
+        *DEFAULT_ACCOUNT_ADDR,
+        consts::CONTRACT_FEE_ON_TRANSFER_TOKEN,
+        runtime_args! {
+            consts::ARG_NAME => "FeeOnTransferToken",
+            consts::ARG_SYMBOL => "FOT",
+            consts::ARG_DECIMALS => 8u8,
+            consts::ARG_TOTAL_SUPPLY => U256::from(1_000_000u64),
+        },
+    )
+    .build();
+    builder.exec(install_request).expect_success().commit();
+
+    builder
+        .get_account(*DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account")
+        .named_keys()
+        .get(consts::FEE_ON_TRANSFER_TOKEN_CONTRACT_HASH_KEY_NAME)
+        .and_then(|key| key.into_hash())
+        .map(ContractHash::new)
+        .expect("should have contract hash")
+}
+
+fn create_pair_for(
+    builder: &mut InMemoryWasmTestBuilder,
+    test_context: &TestContext,
+    token0: ContractHash,
+    token1: ContractHash,
+) -> ContractHash {
+    let create_pair_request = ExecuteRequestBuilder::versioned_contract_call_by_hash(
+        *DEFAULT_ACCOUNT_ADDR,
+        test_context.router_package,
+        None,
+        consts::METHOD_CREATE_PAIR,
+        runtime_args! {
+            consts::ARG_TOKEN0 => token0,
+            consts::ARG_TOKEN1 => token1,
+        },
+    )
+    .build();
+    builder.exec(create_pair_request).expect_success().commit();
+
+    get_pair_hashes(builder, test_context.router_package, token0, token1).1
+}
+
+fn approve(
+    builder: &mut InMemoryWasmTestBuilder,
+    owner: AccountHash,
+    token: ContractHash,
+    spender: ContractPackageHash,
+    amount: U256,
+) {
+    let approve_request = ExecuteRequestBuilder::contract_call_by_hash(
+        owner,
+        token,
+        consts::METHOD_APPROVE,
+        runtime_args! {
+            consts::ARG_OWNER => Key::Account(owner),
+            consts::ARG_SPENDER => Key::Hash(spender.value()),
+            consts::ARG_AMOUNT => amount,
+        }
+    )
+    .build();
+    builder.exec(approve_request).expect_success().commit();
+}
+
+#[test]
+fn should_remove_liquidity_supporting_fee() {
+    let (mut builder, test_context) = setup();
+    let fot_token = install_fee_on_transfer_token(&mut builder);
+    let pair_contract = create_pair_for(&mut builder, &test_context, test_context.token0_contract, fot_token);
+
+    approve(&mut builder, *DEFAULT_ACCOUNT_ADDR, test_context.token0_contract, test_context.router_package, U256::from(30_000u64));
+    approve(&mut builder, *DEFAULT_ACCOUNT_ADDR, fot_token, test_context.router_package, U256::from(50_000u64));
+    // The pair receives 49_500 of the 50_000 FOT sent.
+    let add_liquidity_request = ExecuteRequestBuilder::versioned_contract_call_by_hash(
+        *DEFAULT_ACCOUNT_ADDR,
+        test_context.router_package,
+        None,
+        consts::METHOD_ADD_LIQUIDITY,
+        runtime_args! {
+            consts::ARG_TOKEN0 => test_context.token0_contract,
+            consts::ARG_TOKEN1 => fot_token,
+            consts::ARG_AMOUNT0_DESIRED => U256::from(30_000u64),
+            consts::ARG_AMOUNT1_DESIRED => U256::from(50_000u64),
+            consts::ARG_AMOUNT0_MIN => U256::zero(),
+            consts::ARG_AMOUNT1_MIN => U256::zero(),
+            consts::ARG_TO => Key::Account(*DEFAULT_ACCOUNT_ADDR),
+            consts::ARG_DEAD_LINE => U256::MAX,
+        },
+    )
+    .build();
+    builder.exec(add_liquidity_request).expect_success().commit();
+
+    let lp_balance: U256 = erc20_check_balance_of(&mut builder, &pair_contract, Key::Account(*DEFAULT_ACCOUNT_ADDR));
+    assert_eq!(lp_balance, U256::from(37_535u64));
+    approve(&mut builder, *DEFAULT_ACCOUNT_ADDR, pair_contract, test_context.router_package, U256::from(37_535u64));
+    // Tokens the router already held are not part of the withdrawal.
+    let stray_transfer_request = make_erc20_transfer_request(
+        Key::Account(*DEFAULT_ACCOUNT_ADDR),
+        &test_context.token0_contract,
+        Key::Hash(test_context.router_package.value()),
+        U256::from(1_000u64),
+    );
+    builder.exec(stray_transfer_request).expect_success().commit();
+
+    // The burn releases 48_215 FOT, taxed once into the router and again on to the recipient.
+    let remove_liquidity_request = ExecuteRequestBuilder::versioned_contract_call_by_hash(
+        *DEFAULT_ACCOUNT_ADDR,
+        test_context.router_package,
+        None,
+        consts::METHOD_REMOVE_LIQUIDITY_SUPPORTING_FEE,
+        runtime_args! {
+            consts::ARG_TOKEN0 => test_context.token0_contract,
+            consts::ARG_TOKEN1 => fot_token,
+            consts::ARG_LIQUIDITY => U256::from(37_535u64),
+            consts::ARG_AMOUNT0_MIN => U256::from(29_221u64),
+            consts::ARG_AMOUNT1_MIN => U256::from(47_256u64),
+            consts::ARG_TO => Key::Account(*consts::ACCOUNT_1_ADDR),
+            consts::ARG_DEAD_LINE => U256::MAX,
+        },
+    )
+    .build();
+    builder.exec(remove_liquidity_request).expect_success().commit();
+
+    let token0_balance: U256 = erc20_check_balance_of(&mut builder, &test_context.token0_contract, Key::Account(*consts::ACCOUNT_1_ADDR));
+    assert_eq!(token0_balance, U256::from(29_221u64));
+    let fot_balance: U256 = erc20_check_balance_of(&mut builder, &fot_token, Key::Account(*consts::ACCOUNT_1_ADDR));
+    assert_eq!(fot_balance, U256::from(47_256u64));
+    let router_fot_balance: U256 = erc20_check_balance_of(&mut builder, &fot_token, Key::Hash(test_context.router_package.value()));
+    assert_eq!(router_fot_balance, U256::zero());
+    let router_token0_balance: U256 = erc20_check_balance_of(&mut builder, &test_context.token0_contract, Key::Hash(test_context.router_package.value()));
+    assert_eq!(router_token0_balance, U256::from(1_000u64));
+}
+
+#[test]
+fn should_remove_liquidity_cspr_supporting_fee() {
+    let (mut builder, test_context) = setup();
+    let fot_token = install_fee_on_transfer_token(&mut builder);
+    let pair_contract = create_pair_for(&mut builder, &test_context, fot_token, test_context.wcspr_contract);
+
+    let fot_transfer_request = make_erc20_transfer_request(
+        Key::Account(*DEFAULT_ACCOUNT_ADDR),
+        &fot_token,
+        Key::Account(*consts::ACCOUNT_1_ADDR),
+        U256::from(100_000u64),
+    );
+    builder.exec(fot_transfer_request).expect_success().commit();
+    approve(&mut builder, *consts::ACCOUNT_1_ADDR, fot_token, test_context.router_package, U256::from(30_000u64));
+
+    let add_liquidity_request = make_cspr_session_request(
+        *consts::ACCOUNT_1_ADDR,
+        &test_context,
+        consts::METHOD_ADD_LIQUIDITY_CSPR,
+        runtime_args! {
+            consts::ARG_TOKEN => fot_token,
+            consts::ARG_AMOUNT_TOKEN_DESIRED => U256::from(30_000u64),
+            consts::ARG_AMOUNT_CSPR_DESIRED => U256::from(50_000u64),
+            consts::ARG_AMOUNT_TOKEN_MIN => U256::zero(),
+            consts::ARG_AMOUNT_CSPR_MIN => U256::zero(),
+            consts::ARG_TO => Key::Account(*consts::ACCOUNT_1_ADDR),
+            consts::ARG_DEAD_LINE => U256::MAX,
+        },
+    );
+    builder.exec(add_liquidity_request).expect_success().commit();
+    approve(&mut builder, *consts::ACCOUNT_1_ADDR, pair_contract, test_context.router_package, U256::from(37_535u64));
+
+    let fot_balance_before: U256 = erc20_check_balance_of(&mut builder, &fot_token, Key::Account(*DEFAULT_ACCOUNT_ADDR));
+    let cspr_balance_before = get_main_purse_balance(&builder, *DEFAULT_ACCOUNT_ADDR);
+
+    let remove_liquidity_request = ExecuteRequestBuilder::versioned_contract_call_by_hash(
+        *consts::ACCOUNT_1_ADDR,
+        test_context.router_package,
+        None,
+        consts::METHOD_REMOVE_LIQUIDITY_CSPR_SUPPORTING_FEE,
+        runtime_args! {
+            consts::ARG_TOKEN => fot_token,
+            consts::ARG_LIQUIDITY => U256::from(37_535u64),
+            consts::ARG_AMOUNT_TOKEN_MIN => U256::from(28_354u64),
+            consts::ARG_AMOUNT_CSPR_MIN => U256::from(48_702u64),
+            consts::ARG_TO => Key::Account(*DEFAULT_ACCOUNT_ADDR),
+            consts::ARG_DEAD_LINE => U256::MAX,
+        },
+    )
+    .build();
+    builder.exec(remove_liquidity_request).expect_success().commit();
+
+    let cspr_balance_after = get_main_purse_balance(&builder, *DEFAULT_ACCOUNT_ADDR);
+    assert_eq!(cspr_balance_after - cspr_balance_before, U512::from(48_702u64));
+    let fot_balance_after: U256 = erc20_check_balance_of(&mut builder, &fot_token, Key::Account(*DEFAULT_ACCOUNT_ADDR));
+    assert_eq!(fot_balance_after - fot_balance_before, U256::from(28_354u64));
+}
+
+fn get_router_contract(
+    builder: &InMemoryWasmTestBuilder,
+    router_package: ContractPackageHash,
+) -> ContractHash {
+    builder
+        .get_contract_package(router_package)
+        .and_then(|package| package.current_contract_hash())
+        .expect("should have router contract")
+}
+
+#[test]
+fn should_register_router_event_schemas() {
+    let (builder, test_context) = setup();
+    let router_contract = get_router_contract(&builder, test_context.router_package);
+
+    let schemas = events::schemas(&builder, router_contract);
+    let names: Vec<&str> = schemas.keys().map(String::as_str).collect();
+    assert_eq!(
+        names,
+        vec![
+            "add_liquidity",
+            "create_pair",
+            "installed",
+            "remove_liquidity",
+            "swap_exact_in",
+            "swap_exact_out",
+            "swap_split",
+        ]
+    );
+    let ces_version: String = builder.get_value(router_contract, events::CES_VERSION_KEY);
+    assert_eq!(ces_version, "0.1.0");
+}
+
+#[test]
+fn should_emit_router_events() {
+    let (mut builder, test_context) = setup();
+    let test_context = in_pair_order(test_context);
+    let router_contract = get_router_contract(&builder, test_context.router_package);
+    provide_initial_liquidity(&mut builder, &test_context, 0);
+
+    let path = vec![test_context.token0_contract, test_context.token1_contract];
+    let swap_request = ExecuteRequestBuilder::versioned_contract_call_by_hash(
+        *consts::ACCOUNT_1_ADDR,
+        test_context.router_package,
+        None,
+        consts::METHOD_SWAP_EXACT_TOKENS_FOR_TOKENS,
+        runtime_args! {
+            consts::ARG_AMOUNT_IN => U256::from(10_000u64),
+            consts::ARG_AMOUNT_OUT_MIN => U256::zero(),
+            consts::ARG_PATH => path.clone(),
+            consts::ARG_TO => Key::Account(*DEFAULT_ACCOUNT_ADDR),
+            consts::ARG_DEAD_LINE => U256::MAX,
+        },
+    )
+    .build();
+    builder.exec(swap_request).expect_success().commit();
+
+    let emitted: Vec<RouterEvent> = events::events(&builder, router_contract);
+    assert_eq!(
+        emitted,
+        vec![
+            RouterEvent::Installed { contract_hash: router_contract },
+            RouterEvent::CreatePair {
+                token0: test_context.token0_contract,
+                token1: test_context.token1_contract,
+                pair: Address::from(test_context.pair_0_1_package),
+                events: events::events_uref(&builder, test_context.pair_0_1_contract)
+                    .remove_access_rights(),
+            },
+            RouterEvent::AddLiquidity {
+                token0: test_context.token0_contract,
+                token1: test_context.token1_contract,
+                amount0: U256::from(30_000u64),
+                amount1: U256::from(50_000u64),
+                recipient: Address::from(*consts::ACCOUNT_1_ADDR),
+            },
+            RouterEvent::SwapExactIn {
+                amount_in: U256::from(10_000u64),
+                amount_out: U256::from(12_481u64),
+                path,
+                recipient: Address::from(*DEFAULT_ACCOUNT_ADDR),
+            },
+        ]
+    );
+}
+
+#[test]
+fn should_emit_pair_events() {
+    let (mut builder, test_context) = setup();
+    let test_context = in_pair_order(test_context);
+    provide_initial_liquidity(&mut builder, &test_context, 0);
+
+    let swap_request = ExecuteRequestBuilder::versioned_contract_call_by_hash(
+        *consts::ACCOUNT_1_ADDR,
+        test_context.router_package,
+        None,
+        consts::METHOD_SWAP_EXACT_TOKENS_FOR_TOKENS,
+        runtime_args! {
+            consts::ARG_AMOUNT_IN => U256::from(10_000u64),
+            consts::ARG_AMOUNT_OUT_MIN => U256::zero(),
+            consts::ARG_PATH => vec![test_context.token0_contract, test_context.token1_contract],
+            consts::ARG_TO => Key::Account(*consts::ACCOUNT_1_ADDR),
+            consts::ARG_DEAD_LINE => U256::MAX,
+        },
+    )
+    .build();
+    builder.exec(swap_request).expect_success().commit();
+
+    let router = Address::from(test_context.router_package);
+    let account_1 = Address::from(*consts::ACCOUNT_1_ADDR);
+    let emitted: Vec<PairEvent> = events::events(&builder, test_context.pair_0_1_contract);
+    assert_eq!(
+        emitted,
+        vec![
+            PairEvent::Sync {
+                reserve0: U256::from(30_000u64),
+                reserve1: U256::from(50_000u64),
+            },
+            PairEvent::Mint {
+                sender: router,
+                amount0: U256::from(30_000u64),
+                amount1: U256::from(50_000u64),
+                to: account_1,
+                liquidity: U256::from(37_729u64),
+            },
+            PairEvent::Sync {
+                reserve0: U256::from(40_000u64),
+                reserve1: U256::from(37_519u64),
+            },
+            PairEvent::Swap {
+                sender: router,
+                amount0_in: U256::from(10_000u64),
+                amount1_in: U256::zero(),
+                amount0_out: U256::zero(),
+                amount1_out: U256::from(12_481u64),
+                to: account_1,
+            },
+        ]
+    );
+}
+
+#[test]
+fn should_index_pair_events() {
+    let (mut builder, test_context) = setup();
+    let test_context = in_pair_order(test_context);
+    provide_initial_liquidity(&mut builder, &test_context, 0);
+
+    let path = vec![
+        Key::from(test_context.token0_contract),
+        Key::from(test_context.token1_contract),
+    ];
+    let swap_request = ExecuteRequestBuilder::versioned_contract_call_by_hash(
+        *consts::ACCOUNT_1_ADDR,
+        test_context.router_package,
+        None,
+        consts::METHOD_SWAP_EXACT_TOKENS_FOR_TOKENS,
+        runtime_args! {
+            consts::ARG_AMOUNT_IN => U256::from(10_000u64),
+            consts::ARG_AMOUNT_OUT_MIN => U256::zero(),
+            consts::ARG_PATH => path,
+            consts::ARG_TO => Key::Account(*consts::ACCOUNT_1_ADDR),
+            consts::ARG_DEAD_LINE => U256::MAX,
+        },
+    )
+    .build();
+    builder.exec(swap_request).expect_success().commit();
+
+    let executions = engine::executions(&builder.get_transforms());
+    let pair_events: Vec<PairEvent> = executions
+        .iter()
+        .flat_map(|execution| &execution.events)
+        .filter_map(|emitted| match &emitted.event {
+            Event::Pair(event) => Some(event.clone()),
+            Event::Router(_) => None,
+        })
+        .collect();
+    assert_eq!(
+        pair_events,
+        events::events::<PairEvent>(&builder, test_context.pair_0_1_contract),
+        "transforms should hold the events the pair stored"
+    );
+
+    let mut store = Store::open_in_memory().expect("should open store");
+    let ingested: usize = executions
+        .iter()
+        .map(|execution| store.ingest(execution).expect("should ingest"))
+        .sum();
+    assert_eq!(ingested, 4 + pair_events.len());
+    let reingested: usize = executions
+        .iter()
+        .map(|execution| store.ingest(execution).expect("should ingest"))
+        .sum();
+    assert_eq!(reingested, 0);
+
+    let tokens = Some((
+        test_context.token0_contract.to_formatted_string(),
+        test_context.token1_contract.to_formatted_string(),
+    ));
+    let pair = Some(test_context.pair_0_1_package.to_formatted_string());
+    let source = hex::encode(events::events_uref(&builder, test_context.pair_0_1_contract).addr());
+    let volumes = store.pair_volumes().expect("should read volumes");
+    assert_eq!(volumes.len(), 1);
+    assert_eq!(volumes[0].source, source);
+    assert_eq!(volumes[0].pair, pair);
+    assert_eq!(volumes[0].tokens, tokens);
+    assert_eq!(volumes[0].swaps, 1);
+    assert_eq!(volumes[0].amount0_in, U256::from(10_000u64));
+    assert_eq!(volumes[0].amount1_in, U256::zero());
+    assert_eq!(volumes[0].amount0_out, U256::zero());
+    assert_eq!(volumes[0].amount1_out, U256::from(12_481u64));
+
+    let liquidity = store.pair_liquidity().expect("should read liquidity");
+    assert_eq!(liquidity.len(), 1);
+    assert_eq!(liquidity[0].source, source);
+    assert_eq!(liquidity[0].pair, pair);
+    assert_eq!(liquidity[0].tokens, tokens);
+    assert_eq!(liquidity[0].reserve0, U256::from(40_000u64));
+    assert_eq!(liquidity[0].reserve1, U256::from(37_519u64));
+    assert_eq!(liquidity[0].total_supply(), Some(U256::from(38_729u64)));
+    assert_eq!(liquidity[0].mints, 1);
+
+    // Executions ingested in reverse order fold into the same tables.
+    let mut reversed = Store::open_in_memory().expect("should open store");
+    for execution in executions.iter().rev() {
+        reversed.ingest(execution).expect("should ingest");
+    }
+    assert_eq!(reversed.pair_volumes().expect("should read volumes"), volumes);
+    assert_eq!(reversed.pair_liquidity().expect("should read liquidity"), liquidity);
+}
+
+#[test]
+fn should_enumerate_pairs() {
+    let (mut builder, test_context) = setup();
+    let pair_0_1 = Key::Hash(test_context.pair_0_1_package.value());
+    assert_eq!(router_check_all_pairs_length(&mut builder, test_context.router_package), 1);
+
+    create_pair_for(&mut builder, &test_context, test_context.token0_contract, test_context.wcspr_contract);
+    let (pair_0_wcspr_package, _) =
+        get_pair_hashes(&builder, test_context.router_package, test_context.token0_contract, test_context.wcspr_contract);
+    let pair_0_wcspr = Key::Hash(pair_0_wcspr_package.value());
+
+    assert_eq!(router_check_all_pairs_length(&mut builder, test_context.router_package), 2);
+    assert_eq!(router_check_pair_at(&mut builder, test_context.router_package, 0), pair_0_1);
+    assert_eq!(router_check_pair_at(&mut builder, test_context.router_package, 1), pair_0_wcspr);
+
+    let token0_pairs = router_check_pairs_for_token(&mut builder, test_context.router_package, test_context.token0_contract, 0, 10);
+    assert_eq!(token0_pairs, vec![pair_0_1, pair_0_wcspr]);
+    let token0_pairs = router_check_pairs_for_token(&mut builder, test_context.router_package, test_context.token0_contract, 1, 10);
+    assert_eq!(token0_pairs, vec![pair_0_wcspr]);
+    let token0_pairs = router_check_pairs_for_token(&mut builder, test_context.router_package, test_context.token0_contract, 0, 1);
+    assert_eq!(token0_pairs, vec![pair_0_1]);
+    let token1_pairs = router_check_pairs_for_token(&mut builder, test_context.router_package, test_context.token1_contract, 0, 10);
+    assert_eq!(token1_pairs, vec![pair_0_1]);
+    let wcspr_pairs = router_check_pairs_for_token(&mut builder, test_context.router_package, test_context.wcspr_contract, 5, 10);
+    assert!(wcspr_pairs.is_empty());
+}
+
+#[test]
+fn should_not_get_pair_past_all_pairs_length() {
+    let (mut builder, test_context) = setup();
+
+    let pair_at_request = ExecuteRequestBuilder::versioned_contract_call_by_hash(
+        *DEFAULT_ACCOUNT_ADDR,
+        test_context.router_package,
+        None,
+        consts::METHOD_PAIR_AT,
+        runtime_args! {
+            consts::ARG_INDEX => 1u64,
+        },
+    )
+    .build();
+    builder.exec(pair_at_request).expect_failure();
+
+    let error = builder.get_error().expect("should have error");
+    assert!(
+        matches!(error, CoreError::Exec(ExecError::Revert(ApiError::User(user_error))) if user_error == consts::ERROR_PAIR_NOT_FOUND),
+        "{:?}",
+        error
+    );
+}
+
+/// Creates the `token_a`/`token_b` pair and adds `amount_a`/`amount_b` of liquidity from
+/// `DEFAULT_ACCOUNT`.
+fn provide_liquidity_for(
+    builder: &mut InMemoryWasmTestBuilder,
+    test_context: &TestContext,
+    token_a: ContractHash,
+    token_b: ContractHash,
+    amount_a: u64,
+    amount_b: u64,
+) {
+    create_pair_for(builder, test_context, token_a, token_b);
+    approve(builder, *DEFAULT_ACCOUNT_ADDR, token_a, test_context.router_package, amount_a);
+    approve(builder, *DEFAULT_ACCOUNT_ADDR, token_b, test_context.router_package, amount_b);
+
+    let add_liquidity_request = ExecuteRequestBuilder::versioned_contract_call_by_hash(
+        *DEFAULT_ACCOUNT_ADDR,
+        test_context.router_package,
+        None,
+        consts::METHOD_ADD_LIQUIDITY,
+        runtime_args! {
+            consts::ARG_TOKEN0 => token_a,
+            consts::ARG_TOKEN1 => token_b,
+            consts::ARG_AMOUNT0_DESIRED => U256::from(amount_a),
+            consts::ARG_AMOUNT1_DESIRED => U256::from(amount_b),
+            consts::ARG_AMOUNT0_MIN => U256::zero(),
+            consts::ARG_AMOUNT1_MIN => U256::zero(),
+            consts::ARG_TO => Key::Account(*DEFAULT_ACCOUNT_ADDR),
+            consts::ARG_DEAD_LINE => U256::MAX,
+        },
+    )
+    .build();
+    builder.exec(add_liquidity_request).expect_success().commit();
+}
+
+fn make_split_swap_request(
+    test_context: &TestContext,
+    legs: Vec<(Vec<ContractHash>, U256)>,
+    amount_out_min: u64,
+) -> ExecuteRequest {
+    let call = router::SwapExactTokensForTokensSplit {
+        legs,
+        amount_out_min: U256::from(amount_out_min),
+        to: Key::Account(*consts::ACCOUNT_1_ADDR),
+        dead_line: U256::MAX,
+    };
+    make_stored_call_request(*consts::ACCOUNT_1_ADDR, test_context.router_package, &call)
+}
+
+#[test]
+fn should_swap_split_across_routes() {
+    let (mut builder, test_context) = setup();
+    provide_initial_liquidity(&mut builder, &test_context, 0);
+    let (token0, token1, wcspr) =
+        (test_context.token0_contract, test_context.token1_contract, test_context.wcspr_contract);
+    provide_liquidity_for(&mut builder, &test_context, token0, wcspr, 30_000, 50_000);
+    provide_liquidity_for(&mut builder, &test_context, wcspr, token1, 50_000, 50_000);
+
+    let token1_balance_before: U256 = erc20_check_balance_of(&mut builder, &token1, Key::Account(*consts::ACCOUNT_1_ADDR));
+    let direct = vec![token0, token1];
+    let through_wcspr = vec![token0, wcspr, token1];
+    let split_swap_request = make_split_swap_request(
+        &test_context,
+        vec![
+            (direct.clone(), U256::from(6_000u64)),
+            (through_wcspr.clone(), U256::from(4_000u64)),
+        ],
+        13_563,
+    );
+    builder.exec(split_swap_request).expect_success().commit();
+
+    // A single 10_000 swap through the direct pair would only return 12_481.
+    let token1_balance_after: U256 = erc20_check_balance_of(&mut builder, &token1, Key::Account(*consts::ACCOUNT_1_ADDR));
+    assert_eq!(token1_balance_after - token1_balance_before, U256::from(13_563u64));
+
+    let router_contract = get_router_contract(&builder, test_context.router_package);
+    let length = events::events_length(&builder, router_contract);
+    let event: RouterEvent = events::event_at(&builder, router_contract, length - 1);
+    assert_eq!(
+        event,
+        RouterEvent::SwapSplit {
+            legs: vec![
+                (direct, U256::from(6_000u64), U256::from(8_319u64)),
+                (through_wcspr, U256::from(4_000u64), U256::from(5_244u64)),
+            ],
+            amount_in: U256::from(10_000u64),
+            amount_out: U256::from(13_563u64),
+            recipient: Address::from(*consts::ACCOUNT_1_ADDR),
+        }
+    );
+}
+
+#[test]
+fn should_not_swap_split_below_aggregate_amount_out_min() {
+    let (mut builder, test_context) = setup();
+    provide_initial_liquidity(&mut builder, &test_context, 0);
+    let direct = vec![test_context.token0_contract, test_context.token1_contract];
+
+    // The second leg is priced after the first moved the pair, so the legs return 7_130 + 5_349.
+    let split_swap_request = make_split_swap_request(
+        &test_context,
+        vec![(direct.clone(), U256::from(5_000u64)), (direct, U256::from(5_000u64))],
+        12_480,
+    );
+    builder.exec(split_swap_request).expect_failure();
+
+    let error = builder.get_error().expect("should have error");
+    assert!(
+        matches!(error, CoreError::Exec(ExecError::Revert(ApiError::User(user_error))) if user_error == consts::ERROR_INSUFFICIENT_OUTPUT_AMOUNT),
+        "{:?}",
+        error
+    );
+}
+
+#[test]
+fn should_not_swap_split_with_mismatched_legs() {
+    let (mut builder, test_context) = setup();
+    provide_initial_liquidity(&mut builder, &test_context, 0);
+    let (token0, token1) = (test_context.token0_contract, test_context.token1_contract);
+
+    let split_swap_request = make_split_swap_request(
+        &test_context,
+        vec![
+            (vec![token0, token1], U256::from(5_000u64)),
+            (vec![token1, token0], U256::from(5_000u64)),
+        ],
+        0,
+    );
+    builder.exec(split_swap_request).expect_failure();
+
+    let error = builder.get_error().expect("should have error");
+    assert!(
+        matches!(error, CoreError::Exec(ExecError::Revert(ApiError::User(user_error))) if user_error == consts::ERROR_INVALID_PATH),
+        "{:?}",
+        error
+    );
+}
+
+fn make_best_swap_request(
+    test_context: &TestContext,
+    token_in: ContractHash,
+    token_out: ContractHash,
+    amount_in: u64,
+    amount_out_min: u64,
+) -> ExecuteRequest {
+    let call = router::SwapExactTokensForTokensBest {
+        token_in,
+        token_out,
+        amount_in: U256::from(amount_in),
+        amount_out_min: U256::from(amount_out_min),
+        intermediates: vec![],
+        to: Key::Account(*consts::ACCOUNT_1_ADDR),
+        dead_line: U256::MAX,
+    };
+    make_stored_call_request(*consts::ACCOUNT_1_ADDR, test_context.router_package, &call)
+}
+
+fn make_set_base_tokens_request(
+    test_context: &TestContext,
+    sender: AccountHash,
+    base_tokens: Vec<ContractHash>,
+) -> ExecuteRequest {
+    let call = router::SetBaseTokens { base_tokens };
+    make_stored_call_request(sender, test_context.router_package, &call)
+}
+
+#[test]
+fn should_get_best_amount_out() {
+    let (mut builder, test_context) = setup();
+    provide_initial_liquidity(&mut builder, &test_context, 0);
+    let (token0, token1, wcspr) =
+        (test_context.token0_contract, test_context.token1_contract, test_context.wcspr_contract);
+
+    let (amount_out, path) = router_check_best_amount_out(&mut builder, test_context.router_package, token0, token1, U256::from(10_000u64), vec![]);
+    assert_eq!(amount_out, U256::from(12_481u64));
+    assert_eq!(path, vec![Key::from(token0), Key::from(token1)]);
+
+    // token0 is scarce against WCSPR, so the detour beats the direct pair.
+    provide_liquidity_for(&mut builder, &test_context, token0, wcspr, 10_000, 50_000);
+    provide_liquidity_for(&mut builder, &test_context, wcspr, token1, 50_000, 50_000);
+    let (amount_out, path) = router_check_best_amount_out(&mut builder, test_context.router_package, token0, token1, U256::from(10_000u64), vec![]);
+    assert_eq!(amount_out, U256::from(16_632u64));
+    assert_eq!(path, vec![Key::from(token0), Key::from(wcspr), Key::from(token1)]);
+}
+
+#[test]
+fn should_get_best_amount_out_through_base_tokens() {
+    let (mut builder, test_context) = setup();
+    provide_initial_liquidity(&mut builder, &test_context, 0);
+    let (token0, token1, wcspr) =
+        (test_context.token0_contract, test_context.token1_contract, test_context.wcspr_contract);
+    provide_liquidity_for(&mut builder, &test_context, token1, wcspr, 50_000, 50_000);
+
+    let best_without_intermediates = make_stored_call_request(
+        *DEFAULT_ACCOUNT_ADDR,
+        test_context.router_package,
+        &router::GetBestAmountOut {
+            token_in: token0,
+            token_out: wcspr,
+            amount_in: U256::from(10_000u64),
+            intermediates: vec![],
+        },
+    );
+    builder.exec(best_without_intermediates).expect_failure();
+    let error = builder.get_error().expect("should have error");
+    assert!(
+        matches!(error, CoreError::Exec(ExecError::Revert(ApiError::User(user_error))) if user_error == consts::ERROR_NO_ROUTE),
+        "{:?}",
+        error
+    );
+
+    let (amount_out, path) = router_check_best_amount_out(&mut builder, test_context.router_package, token0, wcspr, U256::from(10_000u64), vec![token1]);
+    assert_eq!(path, vec![Key::from(token0), Key::from(token1), Key::from(wcspr)]);
+
+    let set_base_tokens_request = make_set_base_tokens_request(&test_context, *DEFAULT_ACCOUNT_ADDR, vec![token1]);
+    builder.exec(set_base_tokens_request).expect_success().commit();
+    let best = router_check_best_amount_out(&mut builder, test_context.router_package, token0, wcspr, U256::from(10_000u64), vec![]);
+    assert_eq!(best, (amount_out, path));
+}
+
+#[test]
+fn should_swap_exact_tokens_for_tokens_best() {
+    let (mut builder, test_context) = setup();
+    provide_initial_liquidity(&mut builder, &test_context, 0);
+    let (token0, token1, wcspr) =
+        (test_context.token0_contract, test_context.token1_contract, test_context.wcspr_contract);
+    provide_liquidity_for(&mut builder, &test_context, token0, wcspr, 10_000, 50_000);
+    provide_liquidity_for(&mut builder, &test_context, wcspr, token1, 50_000, 50_000);
+
+    let token1_balance_before: U256 = erc20_check_balance_of(&mut builder, &token1, Key::Account(*consts::ACCOUNT_1_ADDR));
+    let best_swap_request = make_best_swap_request(&test_context, token0, token1, 10_000, 16_632);
+    builder.exec(best_swap_request).expect_success().commit();
+
+    let token1_balance_after: U256 = erc20_check_balance_of(&mut builder, &token1, Key::Account(*consts::ACCOUNT_1_ADDR));
+    assert_eq!(token1_balance_after - token1_balance_before, U256::from(16_632u64));
+
+    let router_contract = get_router_contract(&builder, test_context.router_package);
+    let length = events::events_length(&builder, router_contract);
+    let event: RouterEvent = events::event_at(&builder, router_contract, length - 1);
+    assert_eq!(
+        event,
+        RouterEvent::SwapExactIn {
+            amount_in: U256::from(10_000u64),
+            amount_out: U256::from(16_632u64),
+            path: vec![token0, wcspr, token1],
+            recipient: Address::from(*consts::ACCOUNT_1_ADDR),
+        }
+    );
+}
+
+#[test]
+fn should_not_swap_best_without_route() {
+    let (mut builder, test_context) = setup();
+    provide_initial_liquidity(&mut builder, &test_context, 0);
+
+    let best_swap_request = make_best_swap_request(
+        &test_context,
+        test_context.token0_contract,
+        test_context.wcspr_contract,
+        10_000,
+        0,
+    );
+    builder.exec(best_swap_request).expect_failure();
+
+    let error = builder.get_error().expect("should have error");
+    assert!(
+        matches!(error, CoreError::Exec(ExecError::Revert(ApiError::User(user_error))) if user_error == consts::ERROR_NO_ROUTE),
+        "{:?}",
+        error
+    );
+}
+
+fn install_intermediate_token(builder: &mut InMemoryWasmTestBuilder, index: usize) -> ContractHash {
+    let contract_key_name = format!("intermediate{}", index);
+    let install_request = ExecuteRequestBuilder::standard(
+        *DEFAULT_ACCOUNT_ADDR,
+        consts::CONTRACT_ERC20_TOKEN,
+        runtime_args! {
+            consts::ARG_NAME => format!("IntermediateToken{}", index),
+            consts::ARG_SYMBOL => format!("INT{}", index),
+            consts::ARG_DECIMALS => 8u8,
+            consts::ARG_TOTAL_SUPPLY => U256::from(1_000_000u64),
+            consts::ARG_CONTRACT_KEY_NAME => contract_key_name.clone(),
+        },
+    )
+    .build();
+    builder.exec(install_request).expect_success().commit();
+
+    builder
+        .get_account(*DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account")
+        .named_keys()
+        .get(&format!("{}_contract_hash", contract_key_name))
+        .and_then(|key| key.into_hash())
+        .map(ContractHash::new)
+        .expect("should have contract hash")
+}
+
+/// Every two- and three-hop candidate exists at the maximum intermediate count, so the search
+/// quotes all of them; each pair is still read only once.
+#[test]
+fn should_swap_best_within_gas_budget_at_max_intermediates() {
+    let (mut builder, test_context) = setup();
+    provide_initial_liquidity(&mut builder, &test_context, 0);
+    let (token0, token1) = (test_context.token0_contract, test_context.token1_contract);
+
+    let intermediates: Vec<ContractHash> = (0..consts::MAX_INTERMEDIATES)
+        .map(|index| install_intermediate_token(&mut builder, index))
+        .collect();
+    for (index, intermediate) in intermediates.iter().enumerate() {
+        provide_liquidity_for(&mut builder, &test_context, token0, *intermediate, 10_000, 10_000);
+        provide_liquidity_for(&mut builder, &test_context, *intermediate, token1, 10_000, 10_000);
+        for other in intermediates.iter().skip(index + 1) {
+            provide_liquidity_for(&mut builder, &test_context, *intermediate, *other, 10_000, 10_000);
+        }
+    }
+
+    let call = router::SwapExactTokensForTokensBest {
+        token_in: token0,
+        token_out: token1,
+        amount_in: U256::from(1_000u64),
+        amount_out_min: U256::zero(),
+        intermediates,
+        to: Key::Account(*consts::ACCOUNT_1_ADDR),
+        dead_line: U256::MAX,
+    };
+    let best_swap_request = make_stored_call_request(*consts::ACCOUNT_1_ADDR, test_context.router_package, &call);
+    builder.exec(best_swap_request).expect_success().commit();
+
+    let gas = builder.last_exec_gas_cost().value();
+    assert!(gas <= U512::from(consts::MAX_BEST_SWAP_GAS), "{}", gas);
+}
+
+#[test]
+fn should_get_error_set_base_tokens_without_permission() {
+    let (mut builder, test_context) = setup();
+
+    let set_base_tokens_request = make_set_base_tokens_request(
+        &test_context,
+        *consts::ACCOUNT_1_ADDR,
+        vec![test_context.token0_contract],
+    );
+    builder.exec(set_base_tokens_request).expect_failure();
+
+    let error = builder.get_error().expect("should have error");
+    assert!(
+        matches!(error, CoreError::Exec(ExecError::Revert(ApiError::User(user_error))) if user_error == consts::ERROR_PERMISSION),
+        "{:?}",
+        error
+    );
+}
+
+/// Asserts `call` passes exactly the arguments `contract` declares for its entry point, besides
+/// the `added_by_session` ones the CSPR session fills in.
+fn assert_call_matches_entry_point<C: EntryPointCall>(
+    builder: &InMemoryWasmTestBuilder,
+    contract: ContractHash,
+    call: &C,
+    added_by_session: &[&str],
+) {
+    let contract = builder.get_contract(contract).expect("should have contract");
+    let entry_point = contract
+        .entry_point(C::ENTRY_POINT)
+        .unwrap_or_else(|| panic!("should have entry point {}", C::ENTRY_POINT));
+    let declared: BTreeSet<&str> = entry_point.args().iter().map(|arg| arg.name()).collect();
+    let runtime_args = call.runtime_args();
+    let passed: BTreeSet<&str> = runtime_args
+        .named_args()
+        .map(|arg| arg.name())
+        .chain(added_by_session.iter().copied())
+        .collect();
+    assert_eq!(passed, declared, "arguments of {}", C::ENTRY_POINT);
+}
+
+#[test]
+fn should_match_sdk_calls_to_entry_points() {
+    let (builder, test_context) = setup();
+    let router_contract = get_router_contract(&builder, test_context.router_package);
+    let pair_contract = test_context.pair_0_1_contract;
+    let (token0, token1) = (test_context.token0_contract, test_context.token1_contract);
+    let (amount, to, dead_line) = (U256::one(), Key::Account(*consts::ACCOUNT_1_ADDR), U256::MAX);
+    let path = vec![token0, token1];
+    let address = Key::Account(AccountHash::new([10u8; 32]));
+
+    macro_rules! assert_router_call {
+        ($call:expr) => {
+            assert_call_matches_entry_point(&builder, router_contract, &$call, &[])
+        };
+    }
+    assert_router_call!(router::CreatePair { token0, token1 });
+    assert_router_call!(router::GetPair { token0, token1 });
+    assert_router_call!(router::AllPairsLength {});
+    assert_router_call!(router::PairAt { index: 0 });
+    assert_router_call!(router::PairsForToken { token: token0, offset: 0, limit: 1 });
+    assert_router_call!(router::GetBaseTokens {});
+    assert_router_call!(router::SetBaseTokens { base_tokens: vec![token0] });
+    assert_router_call!(router::GetBestAmountOut { token_in: token0, token_out: token1, amount_in: amount, intermediates: vec![] });
+    assert_router_call!(router::GetFeeto {});
+    assert_router_call!(router::SetFeeto { feeto: address });
+    assert_router_call!(router::SetFeetoSetter { feeto_setter: address });
+    assert_router_call!(router::GetProtocolFeeShare {});
+    assert_router_call!(router::SetProtocolFeeShare { protocol_fee_share_bps: 0 });
+    assert_router_call!(router::SetPairSwapFee { token0, token1, swap_fee_bps: 0 });
+    assert_router_call!(router::GetAmountsOut { amount_in: amount, path: path.clone() });
+    assert_router_call!(router::GetAmountsIn { amount_out: amount, path: path.clone() });
+    assert_router_call!(router::Quote { amount_a: amount, reserve_a: amount, reserve_b: amount });
+    assert_router_call!(router::AddLiquidity { token0, token1, amount0_desired: amount, amount1_desired: amount, amount0_min: amount, amount1_min: amount, to, dead_line });
+    assert_router_call!(router::RemoveLiquidity { token0, token1, liquidity: amount, amount0_min: amount, amount1_min: amount, to, dead_line });
+    assert_router_call!(router::RemoveLiquiditySupportingFee { token0, token1, liquidity: amount, amount0_min: amount, amount1_min: amount, to, dead_line });
+    assert_router_call!(router::RemoveLiquidityCspr { token: token0, liquidity: amount, amount_token_min: amount, amount_cspr_min: amount, to, dead_line });
+    assert_router_call!(router::RemoveLiquidityCsprSupportingFee { token: token0, liquidity: amount, amount_token_min: amount, amount_cspr_min: amount, to, dead_line });
+    assert_router_call!(router::SwapExactTokensForTokens { amount_in: amount, amount_out_min: amount, path: path.clone(), to, dead_line });
+    assert_router_call!(router::SwapExactTokensForTokensSupportingFee { amount_in: amount, amount_out_min: amount, path: path.clone(), to, dead_line });
+    assert_router_call!(router::SwapExactTokensForTokensSplit { legs: vec![(path.clone(), amount)], amount_out_min: amount, to, dead_line });
+    assert_router_call!(router::SwapExactTokensForTokensBest { token_in: token0, token_out: token1, amount_in: amount, amount_out_min: amount, intermediates: vec![], to, dead_line });
+    assert_router_call!(router::SwapTokensForExactTokens { amount_out: amount, amount_in_max: amount, path: path.clone(), to, dead_line });
+    assert_router_call!(router::SwapExactTokensForCspr { amount_in: amount, amount_out_min: amount, path: path.clone(), to, dead_line });
+    assert_router_call!(router::SwapTokensForExactCspr { amount_out: amount, amount_in_max: amount, path: path.clone(), to, dead_line });
+
+    let purse = [consts::ARG_PURSE];
+    assert_call_matches_entry_point(&builder, router_contract, &cspr::AddLiquidityCspr { token: token0, amount_token_desired: amount, amount_cspr_desired: amount, amount_token_min: amount, amount_cspr_min: amount, to, dead_line }, &purse);
+    assert_call_matches_entry_point(&builder, router_contract, &cspr::SwapExactCsprForTokens { amount_in: amount, amount_out_min: amount, path: path.clone(), to, dead_line }, &purse);
+    assert_call_matches_entry_point(&builder, router_contract, &cspr::SwapCsprForExactTokens { amount_out: amount, amount_in_max: amount, path, to, dead_line }, &purse);
+
+    macro_rules! assert_pair_call {
+        ($call:expr) => {
+            assert_call_matches_entry_point(&builder, pair_contract, &$call, &[])
+        };
+    }
+    assert_pair_call!(pair::Name {});
+    assert_pair_call!(pair::Symbol {});
+    assert_pair_call!(pair::Decimals {});
+    assert_pair_call!(pair::TotalSupply {});
+    assert_pair_call!(pair::BalanceOf { address });
+    assert_pair_call!(pair::Transfer { recipient: address, amount });
+    assert_pair_call!(pair::Approve { spender: address, amount });
+    assert_pair_call!(pair::Allowance { owner: address, spender: address });
+    assert_pair_call!(pair::TransferFrom { owner: address, recipient: address, amount });
+    assert_pair_call!(pair::Mint { to: address });
+    assert_pair_call!(pair::Burn { to: address });
+    assert_pair_call!(pair::Swap { amount0: amount, amount1: amount, to: address, data: Bytes::new() });
+    assert_pair_call!(pair::Skim { to: address });
+    assert_pair_call!(pair::Sync {});
+    assert_pair_call!(pair::GetReserves {});
+    assert_pair_call!(pair::GetCumulativePrices {});
+    assert_pair_call!(pair::GetSwapFee {});
+    assert_pair_call!(pair::SetSwapFee { swap_fee_bps: 0 });
+}
+
+type SimulatedAction = Action<ContractHash, AccountHash>;
+
+/// The accounts random scenarios hand the protocol fee to, starting with the feeto of the setup.
+fn simulated_feetos() -> [AccountHash; 2] {
+    [AccountHash::new([10u8; 32]), AccountHash::new([11u8; 32])]
+}
+
+/// Draws reproducible scenarios with xorshift64*, so a failing seed can be replayed. Token
+/// amounts are multiplied by `scale`; WCSPR has a fixed supply, so scaled scenarios leave its pairs
+/// alone.
+struct Scenario {
+    state: u64,
+    scale: U256,
+}
+
+impl Scenario {
+    fn next(&mut self) -> u64 {
+        self.state ^= self.state >> 12;
+        self.state ^= self.state << 25;
+        self.state ^= self.state >> 27;
+        self.state.wrapping_mul(0x2545_f491_4f6c_dd1d)
+    }
+
+    fn below(&mut self, bound: u64) -> u64 {
+        self.next() % bound
+    }
+
+    fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len() as u64) as usize]
+    }
+
+    /// Returns an amount between 1 and 50_000 times the scale, spread over orders of magnitude.
+    fn amount(&mut self) -> U256 {
+        U256::from(10u64.pow(self.below(5) as u32) * (1 + self.below(5))) * self.scale
+    }
+
+    fn trades_wcspr(&self) -> bool {
+        self.scale == U256::one()
+    }
+
+    fn action(
+        &mut self,
+        dex: &Dex<ContractHash, AccountHash>,
+        tokens: [ContractHash; 3],
+    ) -> SimulatedAction {
+        let [token0, token1, wcspr] = tokens;
+        let providers = [*DEFAULT_ACCOUNT_ADDR, *consts::ACCOUNT_1_ADDR];
+        let trades_wcspr = self.trades_wcspr();
+        let pairs: Vec<_> = [(token0, token1), (token0, wcspr), (wcspr, token1)]
+            .into_iter()
+            .filter(|(token_a, token_b)| trades_wcspr || ![token_a, token_b].contains(&&wcspr))
+            .collect();
+        let (token_a, token_b) = *self.pick(&pairs);
+        let (token_a, token_b) =
+            if self.below(2) == 0 { (token_a, token_b) } else { (token_b, token_a) };
+        // Paths through WCSPR, back and forth over one pair, and from an intermediate.
+        let paths: Vec<_> = [
+            vec![token0, token1],
+            vec![token1, token0],
+            vec![token0, wcspr, token1],
+            vec![token1, wcspr, token0],
+            vec![wcspr, token0],
+            vec![token0, token1, token0],
+            vec![token1, token0, wcspr],
+        ]
+        .into_iter()
+        .filter(|path| trades_wcspr || !path.contains(&wcspr))
+        .collect();
+        let path = self.pick(&paths).clone();
+
+        match self.below(11) {
+            0..=2 => Action::AddLiquidity(action::AddLiquidity {
+                provider: *self.pick(&providers),
+                token_a,
+                token_b,
+                amount_a_desired: U256::from(self.below(40_001)) * self.scale,
+                amount_b_desired: U256::from(self.below(40_001)) * self.scale,
+                amount_a_min: U256::zero(),
+                amount_b_min: U256::zero(),
+            }),
+            3 => {
+                let provider = *self.pick(&providers);
+                let balance = dex
+                    .pair(&token_a, &token_b)
+                    .map_or(U256::zero(), |pair| pair.balance_of(&provider));
+                Action::RemoveLiquidity(action::RemoveLiquidity {
+                    provider,
+                    token_a,
+                    token_b,
+                    // Up to 101% of the balance.
+                    liquidity: balance * self.below(102) / 100,
+                    amount0_min: U256::zero(),
+                    amount1_min: U256::zero(),
+                })
+            }
+            4..=6 => {
+                let amount_in = self.amount();
+                let quoted = dex
+                    .get_amounts_out(amount_in, &path)
+                    .ok()
+                    .and_then(|amounts| amounts.last().copied());
+                let amount_out_min = match quoted {
+                    Some(quoted) if self.below(4) == 0 => quoted + 1,
+                    _ => U256::zero(),
+                };
+                Action::SwapExactTokensForTokens(action::SwapExactTokensForTokens {
+                    amount_in,
+                    amount_out_min,
+                    path,
+                })
+            }
+            7..=8 => Action::SwapTokensForExactTokens(action::SwapTokensForExactTokens {
+                amount_out: self.amount(),
+                amount_in_max: U256::from(50_000u64) * self.scale,
+                path,
+            }),
+            9 if self.below(2) == 0 => Action::SetPairSwapFee(action::SetPairSwapFee {
+                token_a,
+                token_b,
+                swap_fee_bps: self.below(120) as u32,
+            }),
+            9 => Action::SetProtocolFeeShare(self.below(6_000) as u32),
+            // Switches the protocol fee off a third of the time.
+            _ => {
+                let [feeto, other_feeto] = simulated_feetos();
+                Action::SetFeeto(*self.pick(&[None, Some(feeto), Some(other_feeto)]))
+            }
+        }
+    }
+}
+
+/// Builds the router call a simulated action stands for, sent by its provider or by the default
+/// account, which trades and holds the feeto setter role.
+fn make_simulated_request(test_context: &TestContext, step: &SimulatedAction) -> ExecuteRequest {
+    let router_package = test_context.router_package;
+    let trader = *DEFAULT_ACCOUNT_ADDR;
+    let (to, dead_line) = (Key::Account(trader), U256::MAX);
+    match step {
+        Action::CreatePair(call) => make_stored_call_request(
+            trader,
+            router_package,
+            &router::CreatePair { token0: call.token_a, token1: call.token_b },
+        ),
+        Action::AddLiquidity(call) => make_stored_call_request(
+            call.provider,
+            router_package,
+            &router::AddLiquidity {
+                token0: call.token_a,
+                token1: call.token_b,
+                amount0_desired: call.amount_a_desired,
+                amount1_desired: call.amount_b_desired,
+                amount0_min: call.amount_a_min,
+                amount1_min: call.amount_b_min,
+                to: Key::Account(call.provider),
+                dead_line,
+            },
+        ),
+        Action::RemoveLiquidity(call) => make_stored_call_request(
+            call.provider,
+            router_package,
+            &router::RemoveLiquidity {
+                token0: call.token_a,
+                token1: call.token_b,
+                liquidity: call.liquidity,
+                amount0_min: call.amount0_min,
+                amount1_min: call.amount1_min,
+                to: Key::Account(call.provider),
+                dead_line,
+            },
+        ),
+        Action::SwapExactTokensForTokens(call) => make_stored_call_request(
+            trader,
+            router_package,
+            &router::SwapExactTokensForTokens {
+                amount_in: call.amount_in,
+                amount_out_min: call.amount_out_min,
+                path: call.path.clone(),
+                to,
+                dead_line,
+            },
+        ),
+        Action::SwapTokensForExactTokens(call) => make_stored_call_request(
+            trader,
+            router_package,
+            &router::SwapTokensForExactTokens {
+                amount_out: call.amount_out,
+                amount_in_max: call.amount_in_max,
+                path: call.path.clone(),
+                to,
+                dead_line,
+            },
+        ),
+        Action::SetPairSwapFee(call) => make_stored_call_request(
+            trader,
+            router_package,
+            &router::SetPairSwapFee {
+                token0: call.token_a,
+                token1: call.token_b,
+                swap_fee_bps: call.swap_fee_bps,
+            },
+        ),
+        Action::SetProtocolFeeShare(protocol_fee_share_bps) => make_stored_call_request(
+            trader,
+            router_package,
+            &router::SetProtocolFeeShare { protocol_fee_share_bps: *protocol_fee_share_bps },
+        ),
+        // The pair reads the zero account as the protocol fee being off.
+        Action::SetFeeto(feeto) => make_stored_call_request(
+            trader,
+            router_package,
+            &router::SetFeeto {
+                feeto: Key::Account(feeto.unwrap_or_else(|| AccountHash::new([0u8; 32]))),
+            },
+        ),
+    }
+}
+
+/// Runs a random scenario against both the contracts and the simulator, asserting they agree on
+/// which steps revert and, after each step, on every pair's reserves, LP supply and `klast`. The
+/// scenario starts from random swap fees and protocol fee share, and its token amounts are
+/// multiplied by `scale`.
+fn run_simulated_scenario(seed: u64, steps: usize, scale: U256) {
+    let total_supply = U256::from(10u64.pow(12)).saturating_mul(scale);
+    let (mut builder, test_context) = setup_with_total_supplies(total_supply, total_supply);
+    let (token0, token1, wcspr) =
+        (test_context.token0_contract, test_context.token1_contract, test_context.wcspr_contract);
+    let feetos = simulated_feetos();
+    let providers = [*DEFAULT_ACCOUNT_ADDR, *consts::ACCOUNT_1_ADDR];
+
+    // Keyed by sorted tokens, so that the contracts line up with the simulator's pairs.
+    let mut pair_contracts = BTreeMap::new();
+    let mut dex: Dex<ContractHash, AccountHash> = Dex::new(Some(feetos[0]));
+    for (token_a, token_b) in [(token0, token1), (token0, wcspr), (wcspr, token1)] {
+        let contract = if (token_a, token_b) == (token0, token1) {
+            test_context.pair_0_1_contract
+        } else {
+            create_pair_for(&mut builder, &test_context, token_a, token_b)
+        };
+        pair_contracts.insert((token_a.min(token_b), token_a.max(token_b)), contract);
+        dex.apply(&Action::CreatePair(action::CreatePair { token_a, token_b })).unwrap();
+    }
+
+    let transfers = [
+        (token0, U256::from(1_000_000u64) * scale),
+        (token1, U256::from(1_000_000u64) * scale),
+        (wcspr, U256::from(700_000u64)),
+    ];
+    for (token, amount) in transfers {
+        let transfer_request = make_erc20_transfer_request(
+            Key::Account(*DEFAULT_ACCOUNT_ADDR),
+            &token,
+            Key::Account(*consts::ACCOUNT_1_ADDR),
+            amount,
+        );
+        builder.exec(transfer_request).expect_success().commit();
+    }
+    for provider in providers {
+        for token in [token0, token1, wcspr].into_iter().chain(pair_contracts.values().copied()) {
+            approve(&mut builder, provider, token, test_context.router_package, U256::MAX);
+        }
+    }
+
+    let mut scenario = Scenario { state: seed, scale };
+    let mut initial_actions = vec![Action::SetProtocolFeeShare(
+        scenario.below(u64::from(MAX_PROTOCOL_FEE_SHARE_BPS) + 1) as u32,
+    )];
+    for (token_a, token_b) in [(token0, token1), (token0, wcspr), (wcspr, token1)] {
+        initial_actions.push(Action::SetPairSwapFee(action::SetPairSwapFee {
+            token_a,
+            token_b,
+            swap_fee_bps: scenario.below(u64::from(MAX_SWAP_FEE_BPS) + 1) as u32,
+        }));
+    }
+    let initial_liquidity = [
+        (token0, token1, U256::from(30_000u64) * scale, U256::from(50_000u64) * scale),
+        (token0, wcspr, U256::from(20_000u64), U256::from(40_000u64)),
+        (wcspr, token1, U256::from(40_000u64), U256::from(30_000u64)),
+    ];
+    for (token_a, token_b, amount_a_desired, amount_b_desired) in initial_liquidity {
+        initial_actions.push(Action::AddLiquidity(action::AddLiquidity {
+            provider: *DEFAULT_ACCOUNT_ADDR,
+            token_a,
+            token_b,
+            amount_a_desired,
+            amount_b_desired,
+            amount_a_min: U256::zero(),
+            amount_b_min: U256::zero(),
+        }));
+    }
+    for step in 0..initial_actions.len() + steps {
+        let simulated_action = match initial_actions.get(step) {
+            Some(simulated_action) => simulated_action.clone(),
+            None => scenario.action(&dex, [token0, token1, wcspr]),
+        };
+        let outcome = dex.apply(&simulated_action);
+
+        let request = make_simulated_request(&test_context, &simulated_action);
+        let failed = builder.exec(request).is_error();
+        if !failed {
+            builder.commit();
+        }
+        assert_eq!(
+            failed,
+            outcome.is_err(),
+            "seed {} step {}: {:?} simulated as {:?}, executed with {:?}",
+            seed,
+            step,
+            simulated_action,
+            outcome,
+            builder.get_error()
+        );
+
+        let pairs = dex.pairs().zip(pair_contracts.values().copied());
+        for ((token_a, token_b, pair), contract) in pairs {
+            let (reserve0, reserve1) = pair.reserves();
+            let reserves: (U256, U256) = (
+                builder.get_value(contract, consts::RESERVE0_KEY_NAME),
+                builder.get_value(contract, consts::RESERVE1_KEY_NAME),
+            );
+            let total_supply: U256 = builder.get_value(contract, consts::TOTAL_SUPPLY_KEY_NAME);
+            let klast: U512 = builder.get_value(contract, consts::KLAST_KEY_NAME);
+            assert_eq!(
+                (reserves, total_supply, klast),
+                ((reserve0, reserve1), pair.total_supply(), pair.klast()),
+                "seed {} step {}: pair {}/{} after {:?}",
+                seed,
+                step,
+                token_a,
+                token_b,
+                simulated_action
+            );
+        }
+    }
+
+    for ((_, _, pair), contract) in dex.pairs().zip(pair_contracts.values().copied()) {
+        for holder in providers.into_iter().chain(feetos) {
+            let balance = erc20_check_balance_of(&mut builder, &contract, Key::Account(holder));
+            let expected = pair.balance_of(&holder);
+            assert_eq!(balance, expected, "seed {}: LP balance of {}", seed, holder);
+        }
+    }
+}
+
+#[test]
+fn should_simulate_contracts_exactly() {
+    for seed in 0x5eed_0001..=0x5eed_0040 {
+        run_simulated_scenario(seed, 16, U256::one());
+    }
+}
+
+#[test]
+fn should_simulate_contracts_exactly_near_max_reserves() {
+    // Reserves reach about `U256::MAX / 10^5`, just below where the K check outgrows a U512.
+    let scale = U256::MAX / U256::from(10u64.pow(11));
+    for seed in 0x5eed_1001..=0x5eed_1010 {
+        run_simulated_scenario(seed, 16, scale);
+    }
+}
@@ -5,9 +5,10 @@ use casper_execution_engine::core::{
     engine_state::{ExecuteRequest},
 };
 use casper_types::{
-    bytesrepr::FromBytes, runtime_args, CLTyped,
+    account::AccountHash, bytesrepr::FromBytes, runtime_args, CLTyped,
     ContractHash, ContractPackageHash, Key, RuntimeArgs, U256,
 };
+use swappery_sdk::EntryPointCall;
 use crate::constants as consts;
 
 pub(crate) fn erc20_check_total_supply(
@@ -59,6 +60,172 @@ pub(crate) fn get_test_result<T: FromBytes + CLTyped>(
     builder.get_value(*contract_hash, consts::RESULT_KEY)
 }
 
+/// Builds the request of `sender` making `call` against the latest version of `package`.
+pub(crate) fn make_stored_call_request<C: EntryPointCall>(
+    sender: AccountHash,
+    package: ContractPackageHash,
+    call: &C,
+) -> ExecuteRequest {
+    ExecuteRequestBuilder::versioned_contract_call_by_hash(
+        sender,
+        package,
+        None,
+        C::ENTRY_POINT,
+        call.runtime_args(),
+    )
+    .build()
+}
+
+fn router_test_call(
+    builder: &mut InMemoryWasmTestBuilder,
+    entry_point: &str,
+    mut args: RuntimeArgs,
+    router_package: ContractPackageHash,
+) -> ContractPackageHash {
+    args.insert(consts::ARG_ROUTER, Key::Hash(router_package.value()))
+        .expect("should insert router");
+    test_call(builder, entry_point, args)
+}
+
+fn test_call(
+    builder: &mut InMemoryWasmTestBuilder,
+    entry_point: &str,
+    args: RuntimeArgs,
+) -> ContractPackageHash {
+    let account = builder
+        .get_account(*DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account");
+
+    let router_test_contract_hash = account
+        .named_keys()
+        .get(consts::ROUTER_TEST_CALL_KEY)
+        .and_then(|key| key.into_hash())
+        .map(ContractPackageHash::new)
+        .expect("should have test contract hash");
+
+    let exec_request = ExecuteRequestBuilder::versioned_contract_call_by_hash(
+        *DEFAULT_ACCOUNT_ADDR,
+        router_test_contract_hash,
+        None,
+        entry_point,
+        args,
+    )
+    .build();
+    builder.exec(exec_request).expect_success().commit();
+
+    router_test_contract_hash
+}
+
+pub(crate) fn router_check_amounts_out(
+    builder: &mut InMemoryWasmTestBuilder,
+    router_package: ContractPackageHash,
+    amount_in: U256,
+    path: Vec<ContractHash>,
+) -> Vec<U256> {
+    let check_amounts_out_args = runtime_args! {
+        consts::ARG_AMOUNT_IN => amount_in,
+        consts::ARG_PATH => path.into_iter().map(Key::from).collect::<Vec<Key>>(),
+    };
+    let router_test_contract_hash = router_test_call(
+        builder,
+        consts::CHECK_AMOUNTS_OUT_ENTRYPOINT,
+        check_amounts_out_args,
+        router_package,
+    );
+    get_test_result(builder, router_test_contract_hash)
+}
+
+pub(crate) fn router_check_amounts_in(
+    builder: &mut InMemoryWasmTestBuilder,
+    router_package: ContractPackageHash,
+    amount_out: U256,
+    path: Vec<ContractHash>,
+) -> Vec<U256> {
+    let check_amounts_in_args = runtime_args! {
+        consts::ARG_AMOUNT_OUT => amount_out,
+        consts::ARG_PATH => path.into_iter().map(Key::from).collect::<Vec<Key>>(),
+    };
+    let router_test_contract_hash = router_test_call(
+        builder,
+        consts::CHECK_AMOUNTS_IN_ENTRYPOINT,
+        check_amounts_in_args,
+        router_package,
+    );
+    get_test_result(builder, router_test_contract_hash)
+}
+
+pub(crate) fn router_check_quote(
+    builder: &mut InMemoryWasmTestBuilder,
+    router_package: ContractPackageHash,
+    amount_a: U256,
+    reserve_a: U256,
+    reserve_b: U256,
+) -> U256 {
+    let check_quote_args = runtime_args! {
+        consts::ARG_AMOUNT_A => amount_a,
+        consts::ARG_RESERVE_A => reserve_a,
+        consts::ARG_RESERVE_B => reserve_b,
+    };
+    let router_test_contract_hash = router_test_call(
+        builder,
+        consts::CHECK_QUOTE_ENTRYPOINT,
+        check_quote_args,
+        router_package,
+    );
+    get_test_result(builder, router_test_contract_hash)
+}
+
+pub(crate) fn router_check_all_pairs_length(
+    builder: &mut InMemoryWasmTestBuilder,
+    router_package: ContractPackageHash,
+) -> u64 {
+    let router_test_contract_hash = router_test_call(
+        builder,
+        consts::CHECK_ALL_PAIRS_LENGTH_ENTRYPOINT,
+        RuntimeArgs::default(),
+        router_package,
+    );
+    get_test_result(builder, router_test_contract_hash)
+}
+
+pub(crate) fn router_check_pair_at(
+    builder: &mut InMemoryWasmTestBuilder,
+    router_package: ContractPackageHash,
+    index: u64,
+) -> Key {
+    let check_pair_at_args = runtime_args! {
+        consts::ARG_INDEX => index,
+    };
+    let router_test_contract_hash = router_test_call(
+        builder,
+        consts::CHECK_PAIR_AT_ENTRYPOINT,
+        check_pair_at_args,
+        router_package,
+    );
+    get_test_result(builder, router_test_contract_hash)
+}
+
+pub(crate) fn router_check_pairs_for_token(
+    builder: &mut InMemoryWasmTestBuilder,
+    router_package: ContractPackageHash,
+    token: ContractHash,
+    offset: u64,
+    limit: u64,
+) -> Vec<Key> {
+    let check_pairs_for_token_args = runtime_args! {
+        consts::ARG_TOKEN => Key::from(token),
+        consts::ARG_OFFSET => offset,
+        consts::ARG_LIMIT => limit,
+    };
+    let router_test_contract_hash = router_test_call(
+        builder,
+        consts::CHECK_PAIRS_FOR_TOKEN_ENTRYPOINT,
+        check_pairs_for_token_args,
+        router_package,
+    );
+    get_test_result(builder, router_test_contract_hash)
+}
+
 pub(crate) fn erc20_check_balance_of(
     builder: &mut InMemoryWasmTestBuilder,
     erc20_contract_hash: &ContractHash,
@@ -195,4 +362,42 @@ pub(crate) fn make_erc20_approve_request(
         .build(),
         _ => panic!("Unknown variant"),
     }
-}
\ No newline at end of file
+}
+
+pub(crate) fn router_check_best_amount_out(
+    builder: &mut InMemoryWasmTestBuilder,
+    router_package: ContractPackageHash,
+    token_in: ContractHash,
+    token_out: ContractHash,
+    amount_in: U256,
+    intermediates: Vec<ContractHash>,
+) -> (U256, Vec<Key>) {
+    let check_best_amount_out_args = runtime_args! {
+        consts::ARG_TOKEN_IN => Key::from(token_in),
+        consts::ARG_TOKEN_OUT => Key::from(token_out),
+        consts::ARG_AMOUNT_IN => amount_in,
+        consts::ARG_INTERMEDIATES => intermediates.into_iter().map(Key::from).collect::<Vec<Key>>(),
+    };
+    let router_test_contract_hash = router_test_call(
+        builder,
+        consts::CHECK_BEST_AMOUNT_OUT_ENTRYPOINT,
+        check_best_amount_out_args,
+        router_package,
+    );
+    get_test_result(builder, router_test_contract_hash)
+}
+
+pub(crate) fn pair_check_cumulative_prices(
+    builder: &mut InMemoryWasmTestBuilder,
+    pair_contract: ContractHash,
+) -> (U256, U256, u64) {
+    let check_cumulative_prices_args = runtime_args! {
+        consts::ARG_PAIR => pair_contract,
+    };
+    let router_test_contract_hash = test_call(
+        builder,
+        consts::CHECK_CUMULATIVE_PRICES_ENTRYPOINT,
+        check_cumulative_prices_args,
+    );
+    get_test_result(builder, router_test_contract_hash)
+}